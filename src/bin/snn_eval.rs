@@ -0,0 +1,211 @@
+use std::env;
+use std::io;
+use std::process;
+use std::thread;
+use pds_snn::DynSNN;
+use pds_snn::coding::decoder::Decoding;
+use pds_snn::coding::encoder::RateEncoder;
+use pds_snn::datasets::idx;
+use pds_snn::evaluation::classification::{self, ClassificationReport};
+use pds_snn::format::binary::{self, SerializableNeuron};
+use pds_snn::format::graph::{self, GraphNode};
+use pds_snn::models::neuron::coba_lif::CobaLifNeuron;
+use pds_snn::models::neuron::cuba_lif::CubaLifNeuron;
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::neuron::Neuron;
+
+/* * Evaluation harness for IDX (MNIST, Fashion-MNIST, ...) classification datasets * */
+
+const USAGE: &str = "\
+Usage: snn-eval --network FILE --images FILE --labels FILE [OPTIONS]
+
+Network:
+    --network FILE          network file (binary format, or JSON graph if it ends with .json),
+                            of LIF, CubaLIF or CobaLIF (binary format only) neurons
+    --mmap                  memory-map the weights of a binary network file
+    --dt DT                 time interval between two instants, for JSON graphs (default: 1.0)
+
+Dataset (decompressed IDX files):
+    --images FILE           images, e.g. t10k-images-idx3-ubyte (the pixels are divided by 255)
+    --labels FILE           labels, e.g. t10k-labels-idx1-ubyte
+    --limit N               evaluate only the first N images
+
+Encoding and decoding:
+    --steps N               number of instants of the rate-encoded spike trains (default: 100)
+    --max-prob P            firing probability of a white pixel (default: 1.0)
+    --seed S                seed of the rate encoding (default: 0)
+    --decoding count|first-spike
+                            decoding of the predicted class (default: count)
+    --threads N             number of parallel replicas (default: available cores)
+    --help                  print this message
+";
+
+/* Object containing the command-line options */
+#[derive(Debug)]
+struct Options {
+    network: Option<String>,
+    mmap: bool,
+    dt: f64,
+    images: Option<String>,
+    labels: Option<String>,
+    limit: Option<usize>,
+    steps: usize,
+    max_prob: f64,
+    seed: u64,
+    decoding: Decoding,
+    threads: Option<usize>,
+}
+
+fn main() {
+    if let Err(error) = run(env::args().skip(1).collect()) {
+        eprintln!("snn-eval: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let options = match parse_args(args)? {
+        Some(options) => options,
+        None => {
+            print!("{}", USAGE);
+            return Ok(());
+        }
+    };
+
+    /* load the network (with the neuron model of the file), then evaluate it */
+    let network_path = options.network.clone().ok_or("missing --network (see --help)")?;
+    let error = |e: io::Error| format!("{}: {}", network_path, e);
+
+    if network_path.ends_with(".json") {
+        let graph = graph::read_graph(&network_path).map_err(error)?;
+        if graph.nodes.values().any(|node| matches!(node, GraphNode::CubaLif { .. })) {
+            evaluate_network(graph::import::<CubaLifNeuron>(&graph, options.dt).map_err(error)?, &options)
+        } else {
+            evaluate_network(graph::import::<LifNeuron>(&graph, options.dt).map_err(error)?, &options)
+        }
+    } else {
+        match binary::load_model_id(&network_path).map_err(error)? {
+            LifNeuron::MODEL_ID => evaluate_binary::<LifNeuron>(&network_path, &options),
+            CubaLifNeuron::MODEL_ID => evaluate_binary::<CubaLifNeuron>(&network_path, &options),
+            CobaLifNeuron::MODEL_ID => evaluate_binary::<CobaLifNeuron>(&network_path, &options),
+            model_id => Err(format!("{}: unknown neuron model {} (the supported ones are LIF ({}), CubaLIF ({}) \
+                                     and CobaLIF ({}))", network_path, model_id, LifNeuron::MODEL_ID,
+                                    CubaLifNeuron::MODEL_ID, CobaLifNeuron::MODEL_ID)),
+        }
+    }
+}
+
+/* It loads the dataset and evaluates the network on it, printing the report */
+fn evaluate_network<N: Neuron + Clone + Send + 'static>(snn: DynSNN<N>, options: &Options) -> Result<(), String> {
+    let images_path = options.images.clone().ok_or("missing --images (see --help)")?;
+    let labels_path = options.labels.clone().ok_or("missing --labels (see --help)")?;
+    let images = idx::load_images(&images_path, options.limit).map_err(|e| format!("{}: {}", images_path, e))?;
+    let labels = idx::load_labels(&labels_path, options.limit).map_err(|e| format!("{}: {}", labels_path, e))?;
+
+    if images.len() != labels.len() {
+        return Err(format!("{} images found, but {} labels", images.len(), labels.len()));
+    }
+
+    let input_dimension = snn.get_input_layer_dimension();
+    if let Some(image) = images.iter().find(|image| image.len() != input_dimension) {
+        return Err(format!("{} pixels found, but the network expects {} input neurons", image.len(), input_dimension));
+    }
+    let num_classes = snn.get_output_layer_dimension();
+    if let Some(label) = labels.iter().find(|label| **label >= num_classes) {
+        return Err(format!("label {} found, but the network has {} output neurons", label, num_classes));
+    }
+    if !(0.0..=1.0).contains(&options.max_prob) {
+        return Err("--max-prob must be in the range [0, 1]".to_string());
+    }
+
+    /* evaluate */
+    let threads = options.threads.unwrap_or_else(||
+        thread::available_parallelism().map_or(1, |n| n.get()));
+    let mut encoder = RateEncoder::new(options.steps, options.max_prob, options.seed);
+    let report = classification::evaluate(&snn, &images, &labels, &mut encoder, options.decoding, threads);
+
+    print_report(&report);
+
+    Ok(())
+}
+
+fn parse_args(args: Vec<String>) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        network: None,
+        mmap: false,
+        dt: 1.0,
+        images: None,
+        labels: None,
+        limit: None,
+        steps: 100,
+        max_prob: 1.0,
+        seed: 0,
+        decoding: Decoding::SpikeCount,
+        threads: None,
+    };
+
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value for {}", name));
+
+        match arg.as_str() {
+            "--help" | "-h" => return Ok(None),
+            "--network" => options.network = Some(value(&arg)?),
+            "--mmap" => options.mmap = true,
+            "--dt" => options.dt = parse_number(&arg, &value(&arg)?)?,
+            "--images" => options.images = Some(value(&arg)?),
+            "--labels" => options.labels = Some(value(&arg)?),
+            "--limit" => options.limit = Some(parse_number(&arg, &value(&arg)?)?),
+            "--steps" => options.steps = parse_number(&arg, &value(&arg)?)?,
+            "--max-prob" => options.max_prob = parse_number(&arg, &value(&arg)?)?,
+            "--seed" => options.seed = parse_number(&arg, &value(&arg)?)?,
+            "--decoding" => options.decoding = match value(&arg)?.as_str() {
+                "count" => Decoding::SpikeCount,
+                "first-spike" => Decoding::FirstSpike,
+                other => return Err(format!("unknown decoding '{}'", other)),
+            },
+            "--threads" => options.threads = Some(parse_number(&arg, &value(&arg)?)?),
+            other => return Err(format!("unknown option '{}' (see --help)", other)),
+        }
+    }
+
+    Ok(Some(options))
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value.parse::<T>().map_err(|_| format!("invalid value '{}' for {}", value, name))
+}
+
+/* It loads the binary network file at *path* with the neuron model N, then evaluates it */
+fn evaluate_binary<N: SerializableNeuron>(path: &str, options: &Options) -> Result<(), String> {
+    let snn = if options.mmap { binary::load_mapped::<N, _>(path) } else { binary::load(path) };
+    evaluate_network(snn.map_err(|e| format!("{}: {}", path, e))?, options)
+}
+
+fn print_report(report: &ClassificationReport) {
+    let num_classes = report.get_num_classes();
+
+    println!("samples: {}", report.get_total());
+    println!("accuracy: {:.4} ({}/{})", report.accuracy(), report.get_correct(), report.get_total());
+    println!("unclassified: {}", report.unclassified.iter().sum::<usize>());
+
+    /* confusion matrix: one row for each true class, one column for each predicted class */
+    println!();
+    println!("confusion matrix (rows: true class, columns: predicted class):");
+    let header: Vec<String> = (0..num_classes).map(|p| format!("{:>6}", p)).collect();
+    println!("{:>6}{} {:>6}", "", header.join(""), "none");
+    for c in 0..num_classes {
+        let row: Vec<String> = report.confusion[c].iter().map(|count| format!("{:>6}", count)).collect();
+        println!("{:>6}{} {:>6}", c, row.join(""), report.unclassified[c]);
+    }
+
+    println!();
+    println!("recall:");
+    for (c, recall) in report.recalls().iter().enumerate() {
+        match recall {
+            Some(recall) => println!("{:>6} {:.4}", c, recall),
+            None => println!("{:>6} -", c),
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use std::env;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process;
+use std::thread;
+use pds_snn::DynSNN;
+use pds_snn::coding::decoder::{self, Decoding};
+use pds_snn::coding::encoder::{Encoder, LatencyEncoder, RateEncoder};
+use pds_snn::format::binary::{self, SerializableNeuron};
+use pds_snn::format::graph::{self, GraphNode};
+use pds_snn::io::{self as spikes_io, csv, events, npy, SpikesFormat};
+use pds_snn::models::neuron::coba_lif::CobaLifNeuron;
+use pds_snn::models::neuron::cuba_lif::CubaLifNeuron;
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::neuron::Neuron;
+
+/* * Command-line runner for network files and spike datasets * */
+
+const USAGE: &str = "\
+Usage: snn-run --network FILE [OPTIONS] (--spikes FILE... | --events FILE... | --analog FILE)
+
+Network:
+    --network FILE          network file (binary format, or JSON graph if it ends with .json),
+                            of LIF, CubaLIF or CobaLIF (binary format only) neurons
+    --format binary|graph   force the format of the network file
+    --mmap                  memory-map the weights of a binary network file
+    --dt DT                 time interval between two instants, for JSON graphs (default: 1.0)
+
+Inputs (each file, or each row of the analog file, is a sample):
+    --spikes FILE           dense spikes matrix, as CSV or .npy (one row for each input neuron)
+    --events FILE           sparse (index, time) events list
+    --duration N            number of instants of the events lists (default: last event + 1)
+    --analog FILE           CSV or .npy analog values (one row for each sample), encoded into spikes
+
+Encoding (analog inputs only):
+    --encoding rate|latency encoding of the analog values (default: rate)
+    --steps N               number of instants of the encoded spike trains (default: 100)
+    --max-prob P            firing probability of a value of 1, for the rate encoding (default: 1.0)
+    --scale S               the analog values are divided by S before encoding (default: 1.0)
+    --seed S                seed of the rate encoding (default: 0)
+
+Processing and outputs:
+    --batch                 process the samples in parallel, on replicas of the network
+    --threads N             number of parallel replicas (default: available cores)
+    --decoding count|first-spike
+                            decode the predicted class of each sample
+    --output FILE           write the output rasters into FILE (default: stdout)
+    --stats FILE            write the statistics into FILE (default: stdout)
+    --no-rasters            do not write the output rasters
+    --help                  print this message
+";
+
+/* Object containing the command-line options */
+#[derive(Debug)]
+struct Options {
+    network: Option<String>,
+    format: Option<String>,
+    mmap: bool,
+    dt: f64,
+    spikes: Vec<String>,
+    events: Vec<String>,
+    duration: Option<usize>,
+    analog: Option<String>,
+    encoding: String,
+    steps: usize,
+    max_prob: f64,
+    scale: f64,
+    seed: u64,
+    batch: bool,
+    threads: Option<usize>,
+    decoding: Option<Decoding>,
+    output: Option<String>,
+    stats: Option<String>,
+    no_rasters: bool,
+}
+
+fn main() {
+    if let Err(error) = run(env::args().skip(1).collect()) {
+        eprintln!("snn-run: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let options = match parse_args(args)? {
+        Some(options) => options,
+        None => {
+            print!("{}", USAGE);
+            return Ok(());
+        }
+    };
+
+    /* load the network (with the neuron model of the file), then run it */
+    let network_path = options.network.clone().ok_or("missing --network (see --help)")?;
+    let error = |e: io::Error| format!("{}: {}", network_path, e);
+    let format = options.format.clone().unwrap_or_else(||
+        if network_path.ends_with(".json") { "graph".to_string() } else { "binary".to_string() });
+
+    match format.as_str() {
+        "binary" => match binary::load_model_id(&network_path).map_err(error)? {
+            LifNeuron::MODEL_ID => run_binary::<LifNeuron>(&network_path, &options),
+            CubaLifNeuron::MODEL_ID => run_binary::<CubaLifNeuron>(&network_path, &options),
+            CobaLifNeuron::MODEL_ID => run_binary::<CobaLifNeuron>(&network_path, &options),
+            model_id => Err(format!("{}: unknown neuron model {} (the supported ones are LIF ({}), CubaLIF ({}) \
+                                     and CobaLIF ({}))", network_path, model_id, LifNeuron::MODEL_ID,
+                                    CubaLifNeuron::MODEL_ID, CobaLifNeuron::MODEL_ID)),
+        },
+        "graph" => {
+            let graph = graph::read_graph(&network_path).map_err(error)?;
+            if graph.nodes.values().any(|node| matches!(node, GraphNode::CubaLif { .. })) {
+                run_network(graph::import::<CubaLifNeuron>(&graph, options.dt).map_err(error)?, &options)
+            } else {
+                run_network(graph::import::<LifNeuron>(&graph, options.dt).map_err(error)?, &options)
+            }
+        },
+        other => Err(format!("{}: unknown format '{}'", network_path, other)),
+    }
+}
+
+/* It runs the network on the inputs, writing the output rasters and the statistics */
+fn run_network<N: Neuron + Clone + Send + 'static>(mut snn: DynSNN<N>, options: &Options) -> Result<(), String> {
+    let input_dimension = snn.get_input_layer_dimension();
+
+    /* read the inputs */
+    let inputs = read_inputs(options, input_dimension)?;
+    if inputs.is_empty() {
+        return Err("no input samples (see --help)".to_string());
+    }
+
+    for (i, input) in inputs.iter().enumerate() {
+        if input.len() != input_dimension {
+            return Err(format!("sample {}: {} input neurons found, but the network expects {}",
+                               i, input.len(), input_dimension));
+        }
+    }
+
+    /* process the inputs */
+    let outputs = if options.batch {
+        let threads = options.threads.unwrap_or_else(||
+            thread::available_parallelism().map_or(1, |n| n.get()));
+        snn.process_batch(&inputs, threads)
+    } else {
+        inputs.iter().map(|input| snn.process(input)).collect()
+    };
+
+    /* write output rasters and statistics */
+    if !options.no_rasters {
+        let mut writer = open_output(&options.output)?;
+        write_rasters(&mut writer, &outputs).map_err(|e| e.to_string())?;
+    }
+
+    let mut writer = open_output(&options.stats)?;
+    write_stats(&mut writer, &outputs, options.decoding).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn parse_args(args: Vec<String>) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        network: None,
+        format: None,
+        mmap: false,
+        dt: 1.0,
+        spikes: vec![],
+        events: vec![],
+        duration: None,
+        analog: None,
+        encoding: "rate".to_string(),
+        steps: 100,
+        max_prob: 1.0,
+        scale: 1.0,
+        seed: 0,
+        batch: false,
+        threads: None,
+        decoding: None,
+        output: None,
+        stats: None,
+        no_rasters: false,
+    };
+
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value for {}", name));
+
+        match arg.as_str() {
+            "--help" | "-h" => return Ok(None),
+            "--network" => options.network = Some(value(&arg)?),
+            "--format" => options.format = Some(value(&arg)?),
+            "--mmap" => options.mmap = true,
+            "--dt" => options.dt = parse_number(&arg, &value(&arg)?)?,
+            "--spikes" => options.spikes.push(value(&arg)?),
+            "--events" => options.events.push(value(&arg)?),
+            "--duration" => options.duration = Some(parse_number(&arg, &value(&arg)?)?),
+            "--analog" => options.analog = Some(value(&arg)?),
+            "--encoding" => options.encoding = value(&arg)?,
+            "--steps" => options.steps = parse_number(&arg, &value(&arg)?)?,
+            "--max-prob" => options.max_prob = parse_number(&arg, &value(&arg)?)?,
+            "--scale" => options.scale = parse_number(&arg, &value(&arg)?)?,
+            "--seed" => options.seed = parse_number(&arg, &value(&arg)?)?,
+            "--batch" => options.batch = true,
+            "--threads" => options.threads = Some(parse_number(&arg, &value(&arg)?)?),
+            "--decoding" => options.decoding = Some(match value(&arg)?.as_str() {
+                "count" => Decoding::SpikeCount,
+                "first-spike" => Decoding::FirstSpike,
+                other => return Err(format!("unknown decoding '{}'", other)),
+            }),
+            "--output" => options.output = Some(value(&arg)?),
+            "--stats" => options.stats = Some(value(&arg)?),
+            "--no-rasters" => options.no_rasters = true,
+            other => return Err(format!("unknown option '{}' (see --help)", other)),
+        }
+    }
+
+    Ok(Some(options))
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value.parse::<T>().map_err(|_| format!("invalid value '{}' for {}", value, name))
+}
+
+/* It loads the binary network file at *path* with the neuron model N, then runs it */
+fn run_binary<N: SerializableNeuron>(path: &str, options: &Options) -> Result<(), String> {
+    let snn = if options.mmap { binary::load_mapped::<N, _>(path) } else { binary::load(path) };
+    run_network(snn.map_err(|e| format!("{}: {}", path, e))?, options)
+}
+
+fn read_inputs(options: &Options, input_dimension: usize) -> Result<Vec<Vec<Vec<u8>>>, String> {
+    let mut inputs = Vec::<Vec<Vec<u8>>>::new();
+
+    for path in &options.spikes {
+        let format = match SpikesFormat::from_path(path) {
+            SpikesFormat::Npy => SpikesFormat::Npy,
+            _ => SpikesFormat::Csv,
+        };
+        inputs.push(spikes_io::load_spikes(path, format, input_dimension).map_err(|e| format!("{}: {}", path, e))?);
+    }
+
+    for path in &options.events {
+        let spikes = events::load_events(path)
+            .and_then(|events| events::to_spikes(&events, input_dimension, options.duration))
+            .map_err(|e| format!("{}: {}", path, e))?;
+        inputs.push(spikes);
+    }
+
+    if let Some(path) = &options.analog {
+        let samples = match SpikesFormat::from_path(path) {
+            SpikesFormat::Npy => npy::load_analog(path),
+            _ => csv::load_analog(path),
+        }.map_err(|e| format!("{}: {}", path, e))?;
+
+        let mut encoder: Box<dyn Encoder> = match options.encoding.as_str() {
+            "rate" => {
+                if !(0.0..=1.0).contains(&options.max_prob) {
+                    return Err("--max-prob must be in the range [0, 1]".to_string());
+                }
+                Box::new(RateEncoder::new(options.steps, options.max_prob, options.seed))
+            },
+            "latency" => Box::new(LatencyEncoder::new(options.steps)),
+            other => return Err(format!("unknown encoding '{}'", other)),
+        };
+
+        for sample in samples {
+            let values: Vec<f64> = sample.iter().map(|value| value / options.scale).collect();
+            inputs.push(encoder.encode(&values));
+        }
+    }
+
+    Ok(inputs)
+}
+
+fn open_output(path: &Option<String>) -> Result<Box<dyn Write>, String> {
+    match path {
+        Some(path) => File::create(Path::new(path))
+            .map(|file| Box::new(BufWriter::new(file)) as Box<dyn Write>)
+            .map_err(|e| format!("{}: {}", path, e)),
+        None => Ok(Box::new(io::stdout())),
+    }
+}
+
+fn write_rasters(writer: &mut Box<dyn Write>, outputs: &[Vec<Vec<u8>>]) -> io::Result<()> {
+    for (i, output) in outputs.iter().enumerate() {
+        if outputs.len() > 1 {
+            writeln!(writer, "# sample {}", i)?;
+        }
+        csv::write_spikes(writer, output)?;
+    }
+    writer.flush()
+}
+
+fn write_stats(writer: &mut Box<dyn Write>, outputs: &[Vec<Vec<u8>>], decoding: Option<Decoding>) -> io::Result<()> {
+    let output_dimension = outputs.first().map_or(0, |output| output.len());
+
+    /* header */
+    let mut header = vec!["sample".to_string(), "duration".to_string(),
+                          "total_spikes".to_string(), "mean_rate".to_string()];
+    if decoding.is_some() {
+        header.push("class".to_string());
+    }
+    header.extend((0..output_dimension).map(|n| format!("spikes_n{}", n)));
+    writeln!(writer, "{}", header.join(","))?;
+
+    /* one line for each sample */
+    for (i, output) in outputs.iter().enumerate() {
+        let counts = decoder::spike_counts(output);
+        let rates = decoder::firing_rates(output);
+        let duration = output.first().map_or(0, |spikes| spikes.len());
+        let mean_rate = if rates.is_empty() { 0.0 } else { rates.iter().sum::<f64>() / rates.len() as f64 };
+
+        let mut line = vec![i.to_string(), duration.to_string(),
+                            counts.iter().sum::<usize>().to_string(), format!("{:.4}", mean_rate)];
+        if let Some(decoding) = decoding {
+            line.push(decoder::decode_class(output, decoding).map_or("none".to_string(), |c| c.to_string()));
+        }
+        line.extend(counts.iter().map(|count| count.to_string()));
+        writeln!(writer, "{}", line.join(","))?;
+    }
+
+    writer.flush()
+}
@@ -0,0 +1,78 @@
+/* * Decoder submodule * */
+
+/**
+    Strategy used to decode the class predicted by the network from its output spikes
+    (the class is the index of an output neuron)
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decoding {
+    SpikeCount,     /* the neuron firing the most (ties are broken by the lowest index) */
+    FirstSpike,     /* the neuron firing first (ties are broken by the spike count) */
+}
+
+/**
+    It returns the number of spikes fired by each neuron
+    - spikes: matrix of 0/1, one row for each neuron and one column for each time instant
+*/
+pub fn spike_counts(spikes: &[Vec<u8>]) -> Vec<usize> {
+    spikes.iter()
+        .map(|neuron_spikes| neuron_spikes.iter().filter(|spike| **spike != 0).count())
+        .collect()
+}
+
+/**
+    It returns the firing rate of each neuron, i.e. its number of spikes per time instant
+*/
+pub fn firing_rates(spikes: &[Vec<u8>]) -> Vec<f64> {
+    spike_counts(spikes).into_iter().zip(spikes.iter())
+        .map(|(count, neuron_spikes)|
+            if neuron_spikes.is_empty() { 0.0 } else { count as f64 / neuron_spikes.len() as f64 })
+        .collect()
+}
+
+/**
+    It returns the first instant in which each neuron fires (None if it never fires)
+*/
+pub fn first_spikes(spikes: &[Vec<u8>]) -> Vec<Option<usize>> {
+    spikes.iter()
+        .map(|neuron_spikes| neuron_spikes.iter().position(|spike| *spike != 0))
+        .collect()
+}
+
+/**
+    It decodes the class predicted by the network from its output spikes,
+    according to the given strategy.
+    - It returns None if no output neuron fires
+*/
+pub fn decode_class(spikes: &[Vec<u8>], decoding: Decoding) -> Option<usize> {
+    let counts = spike_counts(spikes);
+
+    match decoding {
+        Decoding::SpikeCount => counts.iter().enumerate()
+            .filter(|(_, count)| **count > 0)
+            /* (reversing the index, the lowest one wins the ties) */
+            .max_by_key(|(index, count)| (**count, std::cmp::Reverse(*index)))
+            .map(|(index, _)| index),
+        Decoding::FirstSpike => first_spikes(spikes).into_iter().enumerate()
+            .filter_map(|(index, t)| t.map(|t| (index, t)))
+            .min_by_key(|(index, t)| (*t, std::cmp::Reverse(counts[*index]), *index))
+            .map(|(index, _)| index),
+    }
+}
+
+/**
+    It decodes the class predicted by a non-spiking readout layer from its values
+    (see DynSNN::process_readout()): the class is the readout neuron with the highest last value,
+    i.e. the highest value in Max/Final mode or the highest final potential in Trace mode
+    - It returns None if there are no values (ties are broken by the lowest index)
+*/
+pub fn decode_readout_class(values: &[Vec<f64>]) -> Option<usize> {
+    values.iter().enumerate()
+        .filter_map(|(index, neuron_values)| neuron_values.last().map(|value| (index, *value)))
+        .filter(|(_, value)| !value.is_nan())
+        .fold(None, |best: Option<(usize, f64)>, (index, value)| match best {
+            Some((_, best_value)) if best_value >= value => best,
+            _ => Some((index, value)),
+        })
+        .map(|(index, _)| index)
+}
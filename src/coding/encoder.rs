@@ -0,0 +1,80 @@
+/* * Encoder submodule * */
+
+use crate::utils::random::Rng;
+
+/**
+    Trait for the implementation of all the encoders.
+    An encoder turns an analog value for each input neuron into a train of spikes,
+    i.e. into the spikes matrix accepted by DynSNN::process()
+*/
+pub trait Encoder {
+    /**
+        It encodes the given values (one for each input neuron, expected in the range [0, 1])
+        into a matrix of 0/1, one row for each input neuron and one column for each time instant
+    */
+    fn encode(&mut self, values: &[f64]) -> Vec<Vec<u8>>;
+}
+
+/**
+    Rate (Poisson-like) encoder: at each instant, an input neuron fires with a probability
+    proportional to its value.
+    - steps: number of time instants of the generated spike trains
+    - max_probability: firing probability of a neuron whose value is 1
+    - The spike trains are generated by a seeded random generator, so they are reproducible
+*/
+#[derive(Debug, Clone)]
+pub struct RateEncoder {
+    steps: usize,
+    max_probability: f64,
+    rng: Rng,
+}
+
+impl RateEncoder {
+    pub fn new(steps: usize, max_probability: f64, seed: u64) -> Self {
+        if !(0.0..=1.0).contains(&max_probability) {
+            panic!("The maximum firing probability must be in the range [0, 1]");
+        }
+        Self { steps, max_probability, rng: Rng::new(seed) }
+    }
+}
+
+impl Encoder for RateEncoder {
+    fn encode(&mut self, values: &[f64]) -> Vec<Vec<u8>> {
+        values.iter().map(|value| {
+            let probability = value.clamp(0.0, 1.0) * self.max_probability;
+            (0..self.steps).map(|_| self.rng.bernoulli(probability) as u8).collect()
+        }).collect()
+    }
+}
+
+/**
+    Latency (time-to-first-spike) encoder: each input neuron fires exactly once, and the greater
+    its value, the earlier it fires (a neuron whose value is 1 fires at t=0).
+    - steps: number of time instants of the generated spike trains
+    - Neurons whose value is 0 (or less) never fire
+*/
+#[derive(Debug, Clone)]
+pub struct LatencyEncoder {
+    steps: usize,
+}
+
+impl LatencyEncoder {
+    pub fn new(steps: usize) -> Self {
+        Self { steps }
+    }
+}
+
+impl Encoder for LatencyEncoder {
+    fn encode(&mut self, values: &[f64]) -> Vec<Vec<u8>> {
+        values.iter().map(|value| {
+            let mut spikes = vec![0u8; self.steps];
+
+            if *value > 0.0 && self.steps > 0 {
+                let t = ((1.0 - value.min(1.0)) * (self.steps - 1) as f64).round() as usize;
+                spikes[t] = 1;
+            }
+
+            spikes
+        }).collect()
+    }
+}
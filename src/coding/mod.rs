@@ -0,0 +1,5 @@
+/* * Spike coding * */
+
+pub mod decoder;
+pub mod encoder;
+/* (encoders turn analog inputs into spikes, decoders turn output spikes into values/classes) */
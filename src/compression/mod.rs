@@ -0,0 +1,6 @@
+/* * Post-training compression * */
+
+pub mod pruning;
+pub mod quantization;
+pub mod report;
+/* (each tool takes a built DynSNN and returns a new one, leaving the original network untouched) */
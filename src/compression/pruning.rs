@@ -0,0 +1,48 @@
+/* * Pruning submodule * */
+
+use crate::DynSNN;
+use crate::neuron::Neuron;
+
+/**
+    It returns a copy of the weights matrix in which the fraction *sparsity* of the weights
+    with the smallest magnitudes (ties broken by position) is set to 0
+    (the weights which are already 0 count towards the sparsity)
+    - It panics if sparsity is not in the range [0, 1]
+*/
+pub fn prune_matrix(weights: &[Vec<f64>], sparsity: f64) -> Vec<Vec<f64>> {
+    if !(0.0..=1.0).contains(&sparsity) {
+        panic!("The sparsity must be in the range [0, 1]");
+    }
+
+    let cols = weights.first().map_or(0, |row| row.len());
+    let mut indices: Vec<(usize, usize)> = (0..weights.len())
+        .flat_map(|row| (0..cols).map(move |col| (row, col)))
+        .collect();
+    indices.sort_by(|a, b| weights[a.0][a.1].abs().total_cmp(&weights[b.0][b.1].abs()));
+
+    let pruned_number = (sparsity * indices.len() as f64).round() as usize;
+    let mut pruned = weights.to_vec();
+    for (row, col) in indices.into_iter().take(pruned_number) {
+        pruned[row][col] = 0.0;
+    }
+
+    pruned
+}
+
+/**
+    It returns a copy of the network in which the weights of each layer are pruned to the given sparsity
+    - prune_intra: whether the intra weights of each layer are pruned too
+*/
+pub fn prune<N: Neuron + Clone>(snn: &DynSNN<N>, sparsity: f64, prune_intra: bool) -> DynSNN<N> {
+    snn.map_weights(|weights, intra, _| if intra && !prune_intra { weights } else { prune_matrix(&weights, sparsity) })
+}
+
+/**
+    It returns the fraction of the weights of a matrix which are 0
+*/
+pub fn sparsity(weights: &[Vec<f64>]) -> f64 {
+    let total: usize = weights.iter().map(|row| row.len()).sum();
+    let zeros = weights.iter().flatten().filter(|w| **w == 0.0).count();
+
+    if total == 0 { 0.0 } else { zeros as f64 / total as f64 }
+}
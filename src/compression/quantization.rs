@@ -0,0 +1,149 @@
+/* * Quantization submodule * */
+
+use crate::DynSNN;
+use crate::neuron::Neuron;
+
+/**
+    Mapping between the real weights and the k-bit integer levels
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuantizationScheme {
+    /** levels -(2^(k-1) - 1)..=(2^(k-1) - 1), scaled by max|w| (zero is always a level) */
+    Symmetric,
+    /** levels 0..=(2^k - 1), with scale and zero point covering [min(w, 0), max(w, 0)] */
+    Asymmetric,
+}
+
+/**
+    Group of weights sharing the same scale (and zero point)
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    PerLayer,       /* the whole weights matrix of a layer */
+    PerChannel,     /* each row of the matrix, i.e. the incoming weights of each neuron */
+}
+
+/**
+    Object describing a post-training quantization of the weights to k bits.
+    The weights are *fake-quantized*: each one is replaced by the real value of its level,
+    so the quantized network runs on the same neuron models.
+    - Both the schemes keep 0 as a level, so the signs of the weights (checked by the builders) are preserved
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quantization {
+    bits: u32,
+    scheme: QuantizationScheme,
+    granularity: Granularity,
+}
+
+impl Quantization {
+    /**
+        It creates a symmetric per-layer quantization.
+        - It panics if bits is not in the range [2, 32]
+    */
+    pub fn new(bits: u32) -> Self {
+        if !(2..=32).contains(&bits) {
+            panic!("The number of bits of the quantization must be in the range [2, 32]");
+        }
+
+        Self { bits, scheme: QuantizationScheme::Symmetric, granularity: Granularity::PerLayer }
+    }
+
+    pub fn with_scheme(mut self, scheme: QuantizationScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    pub fn with_granularity(mut self, granularity: Granularity) -> Self {
+        self.granularity = granularity;
+        self
+    }
+
+    /* Getters */
+    pub fn get_bits(&self) -> u32 {
+        self.bits
+    }
+
+    pub fn get_scheme(&self) -> QuantizationScheme {
+        self.scheme
+    }
+
+    pub fn get_granularity(&self) -> Granularity {
+        self.granularity
+    }
+
+    /**
+        It returns the quantized version of a weights matrix
+    */
+    pub fn quantize_matrix(&self, weights: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        self.quantize_layer_matrix(weights, false, None)
+    }
+
+    /*
+        It returns the quantized version of a weights matrix of a layer
+        - intra: true for an intra weights matrix, whose reflexive links (never read) are kept as they are
+          and do not contribute to the scales
+        - limit: weight limit of the layer, the levels beyond it are not used (see Layer::set_weight_limit())
+    */
+    fn quantize_layer_matrix(&self, weights: &[Vec<f64>], intra: bool, limit: Option<f64>) -> Vec<Vec<f64>> {
+        let reflexive = |i: usize, j: usize| intra && i == j;
+        let quantize_row = |i: usize, row: &Vec<f64>, quantizer: &dyn Fn(f64) -> f64| row.iter().enumerate()
+            .map(|(j, w)| if reflexive(i, j) { *w } else { quantizer(*w) })
+            .collect();
+
+        match self.granularity {
+            Granularity::PerLayer => {
+                let group = weights.iter().enumerate()
+                    .flat_map(|(i, row)| row.iter().enumerate().filter(move |(j, _)| !reflexive(i, *j)).map(|(_, w)| w));
+                let quantizer = self.quantizer(group, limit);
+                weights.iter().enumerate().map(|(i, row)| quantize_row(i, row, &quantizer)).collect()
+            },
+            Granularity::PerChannel => weights.iter().enumerate().map(|(i, row)| {
+                let group = row.iter().enumerate().filter(|(j, _)| !reflexive(i, *j)).map(|(_, w)| w);
+                quantize_row(i, row, &self.quantizer(group, limit))
+            }).collect(),
+        }
+    }
+
+    /* It returns the function quantizing the weights of a group (within the limit, if any) */
+    fn quantizer<'a, I: Iterator<Item=&'a f64>>(&self, group: I, limit: Option<f64>) -> impl Fn(f64) -> f64 {
+        let (min, max) = group.fold((0f64, 0f64), |(min, max), w| (min.min(*w), max.max(*w)));
+
+        let (scale, zero_point, q_min, q_max) = match self.scheme {
+            QuantizationScheme::Symmetric => {
+                let q_max = ((1u64 << (self.bits - 1)) - 1) as f64;
+                (min.abs().max(max) / q_max, 0.0, -q_max, q_max)
+            },
+            QuantizationScheme::Asymmetric => {
+                let q_max = ((1u64 << self.bits) - 1) as f64;
+                let scale = (max - min) / q_max;
+                let zero_point = if scale > 0.0 { (-min / scale).round() } else { 0.0 };
+                (scale, zero_point, 0.0, q_max)
+            },
+        };
+
+        /* (the levels beyond the limit are excluded, e.g. the asymmetric ones can slightly widen the range) */
+        let (q_min, q_max) = match limit {
+            Some(limit) if scale > 0.0 =>
+                (q_min.max((-limit / scale + zero_point).ceil()), q_max.min((limit / scale + zero_point).floor())),
+            _ => (q_min, q_max),
+        };
+
+        move |w: f64| {
+            if scale == 0.0 {
+                return w;   /* (all the weights of the group are 0) */
+            }
+            let level = ((w / scale).round() + zero_point).clamp(q_min, q_max);
+            /* (clamped against the rounding errors of the scale) */
+            let limit = limit.unwrap_or(f64::INFINITY);
+            ((level - zero_point) * scale).clamp(-limit, limit)
+        }
+    }
+}
+
+/**
+    It returns a copy of the network with quantized weights and intra weights
+*/
+pub fn quantize<N: Neuron + Clone>(snn: &DynSNN<N>, quantization: &Quantization) -> DynSNN<N> {
+    snn.map_weights(|weights, intra, limit| quantization.quantize_layer_matrix(&weights, intra, limit))
+}
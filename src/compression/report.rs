@@ -0,0 +1,70 @@
+/* * Report submodule * */
+
+use crate::DynSNN;
+use crate::coding::decoder::{self, Decoding};
+use crate::neuron::Neuron;
+
+/**
+    Object comparing a compressed network with the original one on a calibration set
+    - accuracies are None if no labels are given
+    - agreement: fraction of the inputs for which the two networks predict the same class
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompressionReport {
+    pub original_accuracy: Option<f64>,
+    pub compressed_accuracy: Option<f64>,
+    pub agreement: f64,
+    pub original_spikes: usize,     /* total number of output spikes of the original network */
+    pub compressed_spikes: usize,   /* total number of output spikes of the compressed network */
+}
+
+impl CompressionReport {
+    /** Accuracy change (compressed - original), if labels were given */
+    pub fn accuracy_change(&self) -> Option<f64> {
+        self.original_accuracy.zip(self.compressed_accuracy).map(|(original, compressed)| compressed - original)
+    }
+
+    /** Relative change of the number of output spikes (0 if the original network never fires) */
+    pub fn spike_count_change(&self) -> f64 {
+        if self.original_spikes == 0 {
+            0.0
+        } else {
+            (self.compressed_spikes as f64 - self.original_spikes as f64) / self.original_spikes as f64
+        }
+    }
+}
+
+/**
+    It compares the two networks on the calibration inputs (each one as in DynSNN::process()),
+    processed in parallel by *num_threads* replicas
+    - labels: classes of the inputs, to compute the accuracies
+*/
+pub fn compare<N: Neuron + Clone>(original: &DynSNN<N>, compressed: &DynSNN<N>, inputs: &[Vec<Vec<u8>>],
+                                  labels: Option<&[usize]>, decoding: Decoding, num_threads: usize) -> CompressionReport {
+    if labels.is_some_and(|labels| labels.len() != inputs.len()) {
+        panic!("The number of labels must be equal to the number of inputs");
+    }
+
+    let original_outputs = original.process_batch(inputs, num_threads);
+    let compressed_outputs = compressed.process_batch(inputs, num_threads);
+
+    let classes = |outputs: &[Vec<Vec<u8>>]| -> Vec<Option<usize>> {
+        outputs.iter().map(|output| decoder::decode_class(output, decoding)).collect()
+    };
+    let original_classes = classes(&original_outputs);
+    let compressed_classes = classes(&compressed_outputs);
+
+    let fraction = |count: usize| if inputs.is_empty() { 0.0 } else { count as f64 / inputs.len() as f64 };
+    let accuracy = |classes: &[Option<usize>]| labels.map(|labels|
+        fraction(classes.iter().zip(labels).filter(|(class, label)| **class == Some(**label)).count()));
+    let total_spikes = |outputs: &[Vec<Vec<u8>>]|
+        outputs.iter().map(|output| decoder::spike_counts(output).iter().sum::<usize>()).sum();
+
+    CompressionReport {
+        original_accuracy: accuracy(&original_classes),
+        compressed_accuracy: accuracy(&compressed_classes),
+        agreement: fraction(original_classes.iter().zip(&compressed_classes).filter(|(a, b)| a == b).count()),
+        original_spikes: total_spikes(&original_outputs),
+        compressed_spikes: total_spikes(&compressed_outputs),
+    }
+}
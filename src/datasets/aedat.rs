@@ -0,0 +1,195 @@
+/* * AEDAT submodule * */
+
+/*
+    AEDAT recordings (jAER / cAER), made of an ASCII header (lines starting with '#')
+    followed by binary data:
+    - AEDAT 2.0: big-endian events of 8 bytes, a 32-bit address and a 32-bit timestamp (microseconds);
+      the meaning of the address bits depends on the camera (see AedatCamera)
+    - AEDAT 3.1: little-endian packets, each one with a 28-byte header
+      (type, source, event size, timestamp offset, timestamp overflow, capacity, number, valid)
+      followed by *capacity* events; only the polarity events (type 1) are decoded:
+      a 32-bit data word (bit 0 valid, bit 1 polarity, bits 2-16 y, bits 17-31 x) and a 32-bit timestamp
+*/
+
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+use crate::datasets::events::PolarityEvent;
+
+const AEDAT3_PACKET_HEADER_SIZE: usize = 28;
+const AEDAT3_POLARITY_EVENT: i16 = 1;
+const AEDAT3_END_HEADER: &str = "#!END-HEADER";
+
+/**
+    Layout of the 32-bit addresses of the AEDAT 2.0 events
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AedatCamera {
+    Dvs128,     /* bit 0 polarity (0 = ON), bits 1-7 x (mirrored), bits 8-14 y */
+    Davis240,   /* bit 31 APS/IMU flag, bits 22-30 y (mirrored), bits 12-21 x (mirrored), bit 11 polarity (1 = ON) */
+}
+
+impl AedatCamera {
+    /* Size of the sensor (width, height) */
+    pub fn get_size(&self) -> (u16, u16) {
+        match self {
+            AedatCamera::Dvs128 => (128, 128),
+            AedatCamera::Davis240 => (240, 180),
+        }
+    }
+
+    /* (None for the events that are not polarity events) */
+    fn decode(&self, address: u32, ts: u64) -> Option<PolarityEvent> {
+        let (width, height) = self.get_size();
+
+        /* (same conventions of the jAER extractors) */
+        match self {
+            AedatCamera::Dvs128 => {
+                let x = ((address >> 1) & 0x7F) as u16;
+                let y = ((address >> 8) & 0x7F) as u16;
+                Some(PolarityEvent::new(width - 1 - x, y, address & 1 == 0, ts))
+            },
+            AedatCamera::Davis240 => {
+                if address & 0x8000_0000 != 0 {
+                    return None;    /* (APS or IMU event) */
+                }
+                let x = ((address >> 12) & 0x3FF) as u16;
+                let y = ((address >> 22) & 0x1FF) as u16;
+                if x >= width || y >= height {
+                    return None;
+                }
+                Some(PolarityEvent::new(width - 1 - x, height - 1 - y, (address >> 11) & 1 == 1, ts))
+            }
+        }
+    }
+}
+
+/**
+    It reads the polarity events of an AEDAT 2.0 or AEDAT 3.1 recording
+    (the version is detected from the header)
+    - camera: layout of the addresses of the AEDAT 2.0 events (ignored for AEDAT 3.1)
+*/
+pub fn read_events<R: Read>(reader: &mut R, camera: AedatCamera) -> io::Result<Vec<PolarityEvent>> {
+    let mut bytes = Vec::<u8>::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let (header_lines, data) = split_header(&bytes);
+
+    match header_lines.first().map(|line| line.trim()) {
+        Some(line) if line.starts_with("#!AER-DAT3") => read_aedat3_events(data),
+        Some(line) if line.starts_with("#!AER-DAT2") || line.starts_with("#!AER-DAT1") =>
+            read_aedat2_events(data, camera),
+        /* (files without a version line are AEDAT 1.0/2.0 files) */
+        _ => read_aedat2_events(data, camera),
+    }
+}
+
+/**
+    It reads the polarity events of the AEDAT recording at *path* (see read_events())
+*/
+pub fn load_events<P: AsRef<Path>>(path: P, camera: AedatCamera) -> io::Result<Vec<PolarityEvent>> {
+    read_events(&mut BufReader::new(File::open(path)?), camera)
+}
+
+/* private functions */
+
+/**
+    It splits the ASCII header (returned as lines) from the binary data
+*/
+fn split_header(bytes: &[u8]) -> (Vec<String>, &[u8]) {
+    let mut lines = Vec::<String>::new();
+    let mut position = 0usize;
+
+    while position < bytes.len() && bytes[position] == b'#' {
+        let end = bytes[position..].iter().position(|b| *b == b'\n')
+            .map_or(bytes.len(), |offset| position + offset + 1);
+        let line = String::from_utf8_lossy(&bytes[position..end]).to_string();
+
+        position = end;
+
+        let is_end = line.trim().starts_with(AEDAT3_END_HEADER);
+        lines.push(line);
+        if is_end {
+            break;
+        }
+    }
+
+    (lines, &bytes[position..])
+}
+
+fn read_aedat2_events(data: &[u8], camera: AedatCamera) -> io::Result<Vec<PolarityEvent>> {
+    if !data.len().is_multiple_of(8) {
+        return Err(invalid_data("The AEDAT 2.0 data must be made of 8-byte events"));
+    }
+
+    Ok(data.chunks_exact(8)
+        .filter_map(|event| {
+            let address = u32::from_be_bytes(event[0..4].try_into().unwrap());
+            let ts = u32::from_be_bytes(event[4..8].try_into().unwrap()) as u64;
+            camera.decode(address, ts)
+        })
+        .collect())
+}
+
+fn read_aedat3_events(data: &[u8]) -> io::Result<Vec<PolarityEvent>> {
+    let mut events = Vec::<PolarityEvent>::new();
+    let mut position = 0usize;
+
+    while position < data.len() {
+        if position + AEDAT3_PACKET_HEADER_SIZE > data.len() {
+            return Err(invalid_data("Truncated AEDAT 3.1 packet header"));
+        }
+
+        let header = &data[position..position + AEDAT3_PACKET_HEADER_SIZE];
+        let event_type = i16::from_le_bytes(header[0..2].try_into().unwrap());
+        let (event_size, ts_overflow, capacity, number) =
+            (read_i32(header, 4), read_i32(header, 12), read_i32(header, 20), read_i32(header, 24));
+
+        if event_size <= 0 || capacity <= 0 || ts_overflow < 0 || number < 0 {
+            return Err(invalid_data("Invalid AEDAT 3.1 packet header (the sizes must be positive)"));
+        }
+        let (event_size, ts_overflow, capacity, number) = (event_size as usize, ts_overflow as u64, capacity as usize, number as usize);
+
+        let payload_start = position + AEDAT3_PACKET_HEADER_SIZE;
+        let payload_end = event_size.checked_mul(capacity)
+            .and_then(|payload_size| payload_start.checked_add(payload_size))
+            .filter(|payload_end| *payload_end <= data.len());
+
+        let payload_end = match payload_end {
+            Some(payload_end) if number <= capacity => payload_end,
+            _ => return Err(invalid_data("Truncated AEDAT 3.1 packet")),
+        };
+
+        if event_type == AEDAT3_POLARITY_EVENT {
+            if event_size < 8 {
+                return Err(invalid_data("AEDAT 3.1 polarity events must be at least 8 bytes long"));
+            }
+
+            for e in 0..number {
+                let event = &data[payload_start + e * event_size..payload_start + (e + 1) * event_size];
+                let word = u32::from_le_bytes(event[0..4].try_into().unwrap());
+                let ts = read_i32(event, 4) as u32 as u64 | (ts_overflow << 31);
+
+                if word & 1 == 0 {
+                    continue;   /* (invalid event) */
+                }
+
+                let x = ((word >> 17) & 0x7FFF) as u16;
+                let y = ((word >> 2) & 0x7FFF) as u16;
+                events.push(PolarityEvent::new(x, y, (word >> 1) & 1 == 1, ts));
+            }
+        }
+
+        position = payload_end;
+    }
+
+    Ok(events)
+}
+
+fn read_i32(bytes: &[u8], offset: usize) -> i32 {
+    i32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
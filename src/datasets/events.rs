@@ -0,0 +1,147 @@
+/* * Polarity events submodule * */
+
+use std::io;
+use crate::snn::SpikeEvent;
+
+/**
+    Object representing an event generated by an event camera (DVS):
+    the pixel (x, y) detected a brightness change at time *ts* (in microseconds);
+    the polarity is true for an increase (ON event) and false for a decrease (OFF event)
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PolarityEvent {
+    pub x: u16,
+    pub y: u16,
+    pub polarity: bool,
+    pub ts: u64,
+}
+
+impl PolarityEvent {
+    pub fn new(x: u16, y: u16, polarity: bool, ts: u64) -> Self {
+        Self { x, y, polarity, ts }
+    }
+}
+
+/**
+    How the polarity of the events is mapped onto the input neurons
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolarityChannels {
+    Separate,   /* two channels (neurons of the OFF events first, then the ones of the ON events) */
+    Merged,     /* one channel, ignoring the polarity */
+    OnOnly,     /* one channel, keeping the ON events only */
+    OffOnly,    /* one channel, keeping the OFF events only */
+}
+
+/**
+    Object describing how a stream of PolarityEvents is binned into a spikes matrix:
+    each pixel (of each polarity channel) becomes an input neuron, and each time bin becomes an instant.
+    A neuron fires in an instant if at least one event of its pixel falls in the corresponding bin.
+    - The neuron of the (downsampled) pixel (x, y) of channel c has index c * (width * height) + y * width + x
+    - The first bin starts at the timestamp of the first event
+*/
+#[derive(Debug, Clone)]
+pub struct EventBinning {
+    width: u16,                 /* width of the sensor (pixels) */
+    height: u16,                /* height of the sensor (pixels) */
+    bin_width: u64,             /* duration of each time bin (microseconds) */
+    downsampling: u16,          /* each (downsampling x downsampling) block of pixels becomes a single neuron */
+    channels: PolarityChannels, /* polarity channels */
+    duration: Option<u64>,      /* duration of the recording (microseconds), otherwise inferred from the events */
+}
+
+impl EventBinning {
+    pub fn new(width: u16, height: u16, bin_width: u64) -> Self {
+        if bin_width == 0 {
+            panic!("The width of the time bins must be greater than 0");
+        }
+        Self { width, height, bin_width, downsampling: 1, channels: PolarityChannels::Separate, duration: None }
+    }
+
+    pub fn with_downsampling(mut self, downsampling: u16) -> Self {
+        if downsampling == 0 {
+            panic!("The downsampling factor must be greater than 0");
+        }
+        self.downsampling = downsampling;
+        self
+    }
+
+    pub fn with_channels(mut self, channels: PolarityChannels) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    pub fn with_duration(mut self, duration: u64) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /* Getters */
+
+    /** Width of the downsampled sensor */
+    pub fn get_width(&self) -> usize {
+        (self.width as usize).div_ceil(self.downsampling as usize)
+    }
+
+    /** Height of the downsampled sensor */
+    pub fn get_height(&self) -> usize {
+        (self.height as usize).div_ceil(self.downsampling as usize)
+    }
+
+    /** Number of input neurons, i.e. number of rows of the spikes matrix */
+    pub fn get_dimension(&self) -> usize {
+        let num_channels = if self.channels == PolarityChannels::Separate { 2 } else { 1 };
+        num_channels * self.get_width() * self.get_height()
+    }
+
+    /**
+        It bins the events into a spikes matrix (one row for each input neuron, one column for each time bin)
+        - It returns an error if an event lies outside the sensor, or if the matrix exceeds io::events::MAX_SPIKES_SIZE entries
+    */
+    pub fn to_spikes(&self, events: &[PolarityEvent]) -> io::Result<Vec<Vec<u8>>> {
+        let t0 = events.iter().map(|event| event.ts).min().unwrap_or(0);
+        let duration = self.duration.unwrap_or_else(||
+            events.iter().map(|event| event.ts - t0 + 1).max().unwrap_or(0));
+        let num_bins = duration.div_ceil(self.bin_width);
+        crate::io::events::check_spikes_size(self.get_dimension(), num_bins)?;
+        let num_bins = num_bins as usize;
+
+        let (width, height) = (self.get_width(), self.get_height());
+        let mut spikes = vec![vec![0u8; num_bins]; self.get_dimension()];
+
+        for event in events {
+            if event.x >= self.width || event.y >= self.height {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          format!("The event at ({}, {}) lies outside the {}x{} sensor",
+                                                  event.x, event.y, self.width, self.height)));
+            }
+
+            let channel = match (self.channels, event.polarity) {
+                (PolarityChannels::Separate, polarity) => polarity as usize,
+                (PolarityChannels::Merged, _) => 0,
+                (PolarityChannels::OnOnly, true) | (PolarityChannels::OffOnly, false) => 0,
+                _ => continue,  /* (the event is filtered out) */
+            };
+
+            let bin = ((event.ts - t0) / self.bin_width) as usize;
+            if bin >= num_bins {
+                continue;   /* (the event is after the end of the recording) */
+            }
+
+            let x = (event.x / self.downsampling) as usize;
+            let y = (event.y / self.downsampling) as usize;
+
+            spikes[channel * width * height + y * width + x][bin] = 1;
+        }
+
+        Ok(spikes)
+    }
+
+    /**
+        It bins the events into a stream of SpikeEvents, one for each time bin
+        in which at least one input neuron fires
+    */
+    pub fn to_spike_events(&self, events: &[PolarityEvent]) -> io::Result<Vec<SpikeEvent>> {
+        Ok(crate::io::to_spike_events(&self.to_spikes(events)?))
+    }
+}
@@ -0,0 +1,141 @@
+/* * IDX submodule * */
+
+/*
+    IDX format (MNIST, Fashion-MNIST, ...), big-endian:
+    - magic number: two 0 bytes, the data type (0x08 u8, 0x09 i8, 0x0B i16, 0x0C i32, 0x0D f32, 0x0E f64)
+      and the number of dimensions
+    - the size of each dimension (u32)
+    - the data, in row-major order
+    The files must be decompressed (the datasets are usually distributed as .gz files).
+*/
+
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+/**
+    Object representing an IDX array, whose values are converted to f64
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdxArray {
+    pub dims: Vec<usize>,
+    pub data: Vec<f64>,
+}
+
+impl IdxArray {
+    /**
+        It returns the items of the array (i.e. the slices along the first dimension),
+        each one flattened, e.g. the 28x28 pixels of each MNIST image
+    */
+    pub fn items(&self) -> Vec<Vec<f64>> {
+        let item_size: usize = self.dims.iter().skip(1).product();
+
+        match self.dims.first() {
+            None => vec![],
+            Some(_) if item_size == 0 => vec![vec![]; self.dims[0]],
+            Some(_) => self.data.chunks(item_size).map(|item| item.to_vec()).collect(),
+        }
+    }
+}
+
+/**
+    It reads an IDX array
+*/
+pub fn read_idx<R: Read>(reader: &mut R) -> io::Result<IdxArray> {
+    read_idx_limited(reader, None)
+}
+
+/**
+    It reads only the first *limit* items of an IDX array (all of them with None), without decoding
+    the rest of the data: the first dimension of the returned array is the number of items read
+*/
+pub fn read_idx_limited<R: Read>(reader: &mut R, limit: Option<usize>) -> io::Result<IdxArray> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+
+    if magic[0] != 0 || magic[1] != 0 {
+        return Err(invalid_data("Wrong magic number: the file is not an IDX file (is it still compressed?)"));
+    }
+
+    let size = match magic[2] {
+        0x08 | 0x09 => 1,
+        0x0B => 2,
+        0x0C | 0x0D => 4,
+        0x0E => 8,
+        code => return Err(invalid_data(&format!("Unknown IDX data type 0x{:02X}", code))),
+    };
+
+    let mut dims = Vec::<usize>::with_capacity(magic[3] as usize);
+    for _ in 0..magic[3] {
+        let mut dim = [0u8; 4];
+        reader.read_exact(&mut dim)?;
+        dims.push(u32::from_be_bytes(dim) as usize);
+    }
+    if let (Some(limit), Some(items)) = (limit, dims.first_mut()) {
+        *items = (*items).min(limit);
+    }
+
+    let len = dims.iter().try_fold(1usize, |len, dim| len.checked_mul(*dim))
+        .and_then(|len| len.checked_mul(size))
+        .ok_or_else(|| invalid_data("Invalid IDX dimensions"))?;
+
+    let mut bytes = vec![0u8; len];
+    reader.read_exact(&mut bytes)?;
+
+    let data = bytes.chunks_exact(size).map(|value| match magic[2] {
+        0x08 => value[0] as f64,
+        0x09 => value[0] as i8 as f64,
+        0x0B => i16::from_be_bytes(value.try_into().unwrap()) as f64,
+        0x0C => i32::from_be_bytes(value.try_into().unwrap()) as f64,
+        0x0D => f32::from_be_bytes(value.try_into().unwrap()) as f64,
+        _ => f64::from_be_bytes(value.try_into().unwrap()),
+    }).collect();
+
+    Ok(IdxArray { dims, data })
+}
+
+/**
+    It reads the IDX array stored in the file at *path*
+*/
+pub fn load_idx<P: AsRef<Path>>(path: P) -> io::Result<IdxArray> {
+    read_idx(&mut BufReader::new(File::open(path)?))
+}
+
+/**
+    It reads the images stored in the IDX file at *path* (e.g. train-images-idx3-ubyte),
+    each one flattened and normalized in the range [0, 1] (the raw pixels are divided by 255)
+    - limit: maximum number of images to read (see read_idx_limited())
+*/
+pub fn load_images<P: AsRef<Path>>(path: P, limit: Option<usize>) -> io::Result<Vec<Vec<f64>>> {
+    let array = read_idx_limited(&mut BufReader::new(File::open(path)?), limit)?;
+
+    if array.dims.len() < 2 {
+        return Err(invalid_data("An IDX images file must have at least 2 dimensions"));
+    }
+
+    Ok(array.items().into_iter()
+        .map(|image| image.into_iter().map(|pixel| pixel / 255.0).collect())
+        .collect())
+}
+
+/**
+    It reads the labels stored in the IDX file at *path* (e.g. train-labels-idx1-ubyte)
+    - limit: maximum number of labels to read (see read_idx_limited())
+*/
+pub fn load_labels<P: AsRef<Path>>(path: P, limit: Option<usize>) -> io::Result<Vec<usize>> {
+    let array = read_idx_limited(&mut BufReader::new(File::open(path)?), limit)?;
+
+    if array.dims.len() != 1 {
+        return Err(invalid_data("An IDX labels file must have 1 dimension"));
+    }
+
+    array.data.into_iter()
+        .map(|label| if label >= 0.0 && label.fract() == 0.0 { Ok(label as usize) } else {
+            Err(invalid_data(&format!("Invalid label {}", label)))
+        })
+        .collect()
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
@@ -0,0 +1,7 @@
+/* * Datasets loaders * */
+
+pub mod aedat;
+pub mod events;
+pub mod idx;
+pub mod nmnist;
+/* (the samples are turned into the spikes, or into the analog values to be encoded, accepted by DynSNN::process()) */
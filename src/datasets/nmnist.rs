@@ -0,0 +1,58 @@
+/* * N-MNIST submodule * */
+
+/*
+    N-MNIST / N-Caltech101 binary format: a sequence of 40-bit (5 bytes) big-endian events
+    - bits 39-32: x address
+    - bits 31-24: y address
+    - bit 23:     polarity (1 = ON, 0 = OFF)
+    - bits 22-0:  timestamp (microseconds)
+    Events with y address 240 are *overflow* events: each of them advances the timestamps
+    of all the following events by 2^13 microseconds (as in the reference Matlab reader).
+*/
+
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+use crate::datasets::events::PolarityEvent;
+
+pub const NMNIST_WIDTH: u16 = 34;
+pub const NMNIST_HEIGHT: u16 = 34;
+
+const EVENT_SIZE: usize = 5;
+const OVERFLOW_Y: u8 = 240;
+const OVERFLOW_INCREMENT: u64 = 1 << 13;
+
+/**
+    It reads all the events of a N-MNIST/N-Caltech101 recording
+*/
+pub fn read_events<R: Read>(reader: &mut R) -> io::Result<Vec<PolarityEvent>> {
+    let mut bytes = Vec::<u8>::new();
+    reader.read_to_end(&mut bytes)?;
+
+    if !bytes.len().is_multiple_of(EVENT_SIZE) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                  "The size of the recording must be a multiple of 5 bytes (40-bit events)"));
+    }
+
+    let mut events = Vec::<PolarityEvent>::with_capacity(bytes.len() / EVENT_SIZE);
+    let mut ts_offset = 0u64;
+
+    for event in bytes.chunks_exact(EVENT_SIZE) {
+        if event[1] == OVERFLOW_Y {
+            ts_offset += OVERFLOW_INCREMENT;
+            continue;
+        }
+
+        let ts = ((event[2] as u64 & 0x7F) << 16) | ((event[3] as u64) << 8) | event[4] as u64;
+        events.push(PolarityEvent::new(event[0] as u16, event[1] as u16, event[2] & 0x80 != 0, ts + ts_offset));
+    }
+
+    Ok(events)
+}
+
+/**
+    It reads all the events of the N-MNIST/N-Caltech101 recording at *path*
+*/
+pub fn load_events<P: AsRef<Path>>(path: P) -> io::Result<Vec<PolarityEvent>> {
+    read_events(&mut BufReader::new(File::open(path)?))
+}
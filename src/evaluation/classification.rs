@@ -0,0 +1,112 @@
+/* * Classification submodule * */
+
+use crate::DynSNN;
+use crate::coding::decoder::{self, Decoding};
+use crate::coding::encoder::Encoder;
+use crate::neuron::Neuron;
+
+/**
+    Number of samples encoded and processed together: the samples are encoded chunk by chunk,
+    so that the spikes of a whole dataset are never kept in memory
+*/
+const CHUNK_SIZE: usize = 256;
+
+/**
+    Object containing the results of a classification evaluation
+    - confusion[c][p]: number of samples of class c predicted as class p
+    - unclassified[c]: number of samples of class c for which no output neuron fired
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassificationReport {
+    pub confusion: Vec<Vec<usize>>,
+    pub unclassified: Vec<usize>,
+}
+
+impl ClassificationReport {
+    pub fn new(num_classes: usize) -> Self {
+        Self {
+            confusion: vec![vec![0; num_classes]; num_classes],
+            unclassified: vec![0; num_classes],
+        }
+    }
+
+    /**
+        It records the prediction of a sample of class *label*
+    */
+    pub fn record(&mut self, label: usize, prediction: Option<usize>) {
+        match prediction {
+            Some(prediction) => self.confusion[label][prediction] += 1,
+            None => self.unclassified[label] += 1,
+        }
+    }
+
+    pub fn get_num_classes(&self) -> usize {
+        self.confusion.len()
+    }
+
+    /**
+        It returns the number of evaluated samples
+    */
+    pub fn get_total(&self) -> usize {
+        self.confusion.iter().flatten().sum::<usize>() + self.unclassified.iter().sum::<usize>()
+    }
+
+    /**
+        It returns the number of correctly classified samples
+    */
+    pub fn get_correct(&self) -> usize {
+        (0..self.get_num_classes()).map(|c| self.confusion[c][c]).sum()
+    }
+
+    /**
+        It returns the fraction of correctly classified samples
+        (the unclassified samples count as errors)
+    */
+    pub fn accuracy(&self) -> f64 {
+        match self.get_total() {
+            0 => 0.0,
+            total => self.get_correct() as f64 / total as f64,
+        }
+    }
+
+    /**
+        It returns the recall of each class (None if there are no samples of that class)
+    */
+    pub fn recalls(&self) -> Vec<Option<f64>> {
+        (0..self.get_num_classes()).map(|c| {
+            let samples = self.confusion[c].iter().sum::<usize>() + self.unclassified[c];
+            if samples == 0 { None } else { Some(self.confusion[c][c] as f64 / samples as f64) }
+        }).collect()
+    }
+}
+
+/**
+    It evaluates the network on a labelled dataset:
+    each sample is encoded into spikes, processed and its class is decoded from the output spikes.
+    - samples: analog values of each sample, one for each input neuron
+    - labels: class of each sample, which must be lower than the output layer dimension
+    - num_threads: number of parallel replicas of the network (see DynSNN::process_batch())
+*/
+pub fn evaluate<N: Neuron + Clone>(snn: &DynSNN<N>, samples: &[Vec<f64>], labels: &[usize],
+                                     encoder: &mut dyn Encoder, decoding: Decoding,
+                                     num_threads: usize) -> ClassificationReport {
+    if samples.len() != labels.len() {
+        panic!("The number of labels must be equal to the number of samples");
+    }
+
+    let num_classes = snn.get_output_layer_dimension();
+    let mut report = ClassificationReport::new(num_classes);
+
+    for (samples, labels) in samples.chunks(CHUNK_SIZE).zip(labels.chunks(CHUNK_SIZE)) {
+        let inputs: Vec<Vec<Vec<u8>>> = samples.iter().map(|sample| encoder.encode(sample)).collect();
+
+        for (output, label) in snn.process_batch(&inputs, num_threads).iter().zip(labels) {
+            if *label >= num_classes {
+                panic!("The label {} is not a class of the network ({} output neurons)", label, num_classes);
+            }
+            report.record(*label, decoder::decode_class(output, decoding));
+        }
+    }
+
+    report
+}
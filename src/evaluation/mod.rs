@@ -0,0 +1,4 @@
+/* * Evaluation of trained networks * */
+
+pub mod classification;
+/* (the harnesses replace the custom scripts wrapped around DynSNN::process()) */
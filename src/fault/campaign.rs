@@ -0,0 +1,430 @@
+/* * Campaign submodule * */
+
+use std::io::{self, Write};
+use std::thread;
+use crate::DynSNN;
+use crate::coding::decoder::{self, Decoding};
+use crate::fault::model::{Fault, FaultTarget, FaultType, NeuronSite};
+use crate::neuron::Neuron;
+use crate::snn::wta::WtaMode;
+use crate::utils::random::Rng;
+
+/**
+    Component of the network which can be affected by the faults of a campaign
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Component {
+    Weight,
+    IntraWeight,
+    Neuron(NeuronSite),
+}
+
+impl Component {
+    /** All the components (the neuron faults need a model supporting them, see Neuron::get_fault_width()) */
+    pub const ALL: [Component; 9] = [
+        Component::Weight,
+        Component::IntraWeight,
+        Component::Neuron(NeuronSite::VTh),
+        Component::Neuron(NeuronSite::VMem),
+        Component::Neuron(NeuronSite::VReset),
+        Component::Neuron(NeuronSite::Tau),
+        Component::Neuron(NeuronSite::Adder),
+        Component::Neuron(NeuronSite::Comparator),
+        Component::Neuron(NeuronSite::Multiplier),
+    ];
+
+    pub fn of(target: &FaultTarget) -> Self {
+        match target {
+            FaultTarget::Weight { .. } => Component::Weight,
+            FaultTarget::IntraWeight { .. } => Component::IntraWeight,
+            FaultTarget::Neuron { site, .. } => Component::Neuron(*site),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Component::Weight => "weight",
+            Component::IntraWeight => "intra_weight",
+            Component::Neuron(NeuronSite::VTh) => "v_th",
+            Component::Neuron(NeuronSite::VMem) => "v_mem",
+            Component::Neuron(NeuronSite::VReset) => "v_reset",
+            Component::Neuron(NeuronSite::Tau) => "tau",
+            Component::Neuron(NeuronSite::Adder) => "adder",
+            Component::Neuron(NeuronSite::Comparator) => "comparator",
+            Component::Neuron(NeuronSite::Multiplier) => "multiplier",
+        }
+    }
+}
+
+/**
+    Effect of a fault on the outputs of the network, compared to the golden (fault-free) ones
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Masked,                 /* every output raster is equal to the golden one */
+    OutputChanged,          /* some output rasters changed, but not their decoded classes */
+    ClassificationChanged,  /* the decoded class of some input changed */
+}
+
+impl Outcome {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Outcome::Masked => "masked",
+            Outcome::OutputChanged => "output_changed",
+            Outcome::ClassificationChanged => "classification_changed",
+        }
+    }
+}
+
+/**
+    Object describing a fault injection campaign: a golden run of the network on the inputs,
+    followed by *runs* runs, each one with a single randomly sampled fault
+    - The layer, the component, the element, the bit and the fault type are sampled uniformly
+    - Stuck-at faults are permanent, while bit-flips last *transient_duration* instants,
+      starting from an instant sampled uniformly in the inputs duration
+*/
+#[derive(Debug, Clone)]
+pub struct Campaign {
+    runs: usize,
+    seed: u64,
+    components: Vec<Component>,
+    fault_types: Vec<FaultType>,
+    transient_duration: u64,
+    decoding: Decoding,
+    num_threads: usize,
+    from_current_state: bool,
+}
+
+impl Campaign {
+    pub fn new(runs: usize, seed: u64) -> Self {
+        Self {
+            runs,
+            seed,
+            components: Component::ALL.to_vec(),
+            fault_types: vec![FaultType::StuckAt0, FaultType::StuckAt1, FaultType::BitFlip],
+            transient_duration: 1,
+            decoding: Decoding::SpikeCount,
+            num_threads: 1,
+            from_current_state: false,
+        }
+    }
+
+    /* Setters of the campaign parameters */
+
+    /** - It panics if no component is given */
+    pub fn with_components(mut self, components: Vec<Component>) -> Self {
+        if components.is_empty() {
+            panic!("A campaign needs at least one component");
+        }
+        self.components = components;
+        self
+    }
+
+    /** - It panics if no fault type is given */
+    pub fn with_fault_types(mut self, fault_types: Vec<FaultType>) -> Self {
+        if fault_types.is_empty() {
+            panic!("A campaign needs at least one fault type");
+        }
+        self.fault_types = fault_types;
+        self
+    }
+
+    /** - It panics if the duration is 0 */
+    pub fn with_transient_duration(mut self, transient_duration: u64) -> Self {
+        if transient_duration == 0 {
+            panic!("The duration of the transient faults must be greater than 0");
+        }
+        self.transient_duration = transient_duration;
+        self
+    }
+
+    /** Decoding used to detect the classification changes */
+    pub fn with_decoding(mut self, decoding: Decoding) -> Self {
+        self.decoding = decoding;
+        self
+    }
+
+    /** Number of runs executed in parallel, each one on a replica of the network */
+    pub fn with_threads(mut self, num_threads: usize) -> Self {
+        self.num_threads = num_threads.max(1);
+        self
+    }
+
+    /**
+        If true, the golden and the faulty runs start from the current state of the network (see
+        DynSNN::process_continue_with_faults()), e.g. a network warmed up or restored from a snapshot,
+        instead of its initial state (false by default)
+        - The time windows of the transient faults follow the instants already processed by the network
+    */
+    pub fn from_current_state(mut self, from_current_state: bool) -> Self {
+        self.from_current_state = from_current_state;
+        self
+    }
+
+    /**
+        It samples the faults of the campaign: the same seed always samples the same faults
+        - The bits of the neuron faults are sampled among the ones of the neuron (see Neuron::get_fault_width())
+        - The faults of each layer are sampled only among the components it has: the intra weights faults never
+          target a reflexive link, so they are not sampled in the single-neuron layers, the weights faults are not
+          sampled in the layers without inputs and the neuron faults are not sampled in the winner-take-all layers
+          or for the models which do not support them
+        - It panics if no layer has any of the components of the campaign (see with_components())
+    */
+    pub fn sample_faults<N: Neuron + Clone>(&self, snn: &DynSNN<N>, duration: usize) -> Vec<Fault> {
+        let mut rng = Rng::new(self.seed);
+        let offset = if self.from_current_state { snn.get_elapsed_instants() } else { 0 };
+        /* (for each layer: the number of inputs, whether it is a winner-take-all layer and the fault width of each neuron) */
+        let layers: Vec<(usize, bool, Vec<Option<u32>>)> = snn.layers().iter()
+            .map(|layer| {
+                let layer = layer.lock().unwrap();
+                (layer.weight_matrix().cols(), layer.get_wta_mode() != WtaMode::None,
+                 layer.neurons().iter().map(|neuron| neuron.get_fault_width()).collect())
+            })
+            .collect();
+        /* (the components of each layer: the intra weights of a single neuron are just its reflexive link, never read) */
+        let components: Vec<Vec<Component>> = layers.iter()
+            .map(|(inputs, wta, widths)| self.components.iter().copied()
+                .filter(|component| match component {
+                    Component::Weight => *inputs > 0,
+                    Component::IntraWeight => widths.len() > 1,
+                    Component::Neuron(_) => !*wta && widths.iter().all(Option::is_some),
+                })
+                .collect())
+            .collect();
+        let candidates: Vec<usize> = (0..layers.len()).filter(|layer| !components[*layer].is_empty()).collect();
+
+        if self.runs > 0 && candidates.is_empty() {
+            panic!("No layer has the components of the campaign (the intra weights need at least 2 neurons, the neuron \
+                    faults a model supporting them outside the winner-take-all layers)");
+        }
+
+        (0..self.runs).map(|_| {
+            let layer = candidates[rng.below(candidates.len())];
+            let (inputs, _, widths) = &layers[layer];
+            let (neurons, inputs) = (widths.len(), *inputs);
+            let component = components[layer][rng.below(components[layer].len())];
+            let fault_type = self.fault_types[rng.below(self.fault_types.len())];
+
+            let neuron = rng.below(neurons);
+            let target = match component {
+                Component::Weight => FaultTarget::Weight { neuron, input: rng.below(inputs) },
+                Component::IntraWeight => {
+                    /* (any other neuron of the layer) */
+                    let from = rng.below(neurons - 1);
+                    FaultTarget::IntraWeight { neuron, from: if from >= neuron { from + 1 } else { from } }
+                },
+                Component::Neuron(site) => FaultTarget::Neuron { neuron, site },
+            };
+            let bit = match component {
+                Component::Neuron(NeuronSite::Comparator) => 0,
+                Component::Neuron(_) => rng.below(widths[neuron].unwrap() as usize) as u8,
+                _ => rng.below(64) as u8,
+            };
+
+            let fault = Fault::new(layer, target, fault_type, bit);
+            if fault_type == FaultType::BitFlip {
+                let start = offset + rng.below(duration.max(1)) as u64;
+                fault.with_window(start, start.saturating_add(self.transient_duration - 1))
+            } else {
+                fault
+            }
+        }).collect()
+    }
+
+    /**
+        It runs the campaign on the given inputs (each one as in DynSNN::process(), or as in
+        DynSNN::process_continue() with from_current_state())
+        - The network itself is never affected by the faults
+    */
+    pub fn run<N: Neuron + Clone>(&self, snn: &DynSNN<N>, inputs: &[Vec<Vec<u8>>]) -> CampaignResult {
+        let process = |input: &Vec<Vec<u8>>, faults: &[Fault]| if self.from_current_state {
+            snn.process_continue_with_faults(input, faults)
+        } else {
+            snn.process_with_faults(input, faults)
+        };
+
+        let golden: Vec<Vec<Vec<u8>>> = inputs.iter().map(|input| process(input, &[])).collect();
+        let golden_classes: Vec<Option<usize>> = golden.iter()
+            .map(|output| decoder::decode_class(output, self.decoding))
+            .collect();
+
+        let duration = inputs.iter().map(|input| input.first().map_or(0, |spikes| spikes.len())).max().unwrap_or(0);
+        let faults = self.sample_faults(snn, duration);
+
+        /* (each run compares the outputs with the golden ones) */
+        let run = |fault: &Fault| {
+            let mut outcome = Outcome::Masked;
+
+            for (input, (golden, golden_class)) in inputs.iter().zip(golden.iter().zip(golden_classes.iter())) {
+                let output = process(input, &[*fault]);
+
+                if decoder::decode_class(&output, self.decoding) != *golden_class {
+                    outcome = Outcome::ClassificationChanged;
+                    break;
+                }
+                if output != *golden {
+                    outcome = Outcome::OutputChanged;
+                }
+            }
+
+            RunResult { fault: *fault, outcome }
+        };
+
+        let chunk_size = faults.len().div_ceil(self.num_threads).max(1);
+        let runs = thread::scope(|scope| {
+            let handles: Vec<_> = faults.chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || chunk.iter().map(run).collect::<Vec<_>>()))
+                .collect();
+
+            handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+        });
+
+        CampaignResult { golden, runs }
+    }
+}
+
+/* Object representing the result of a single run of a campaign */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunResult {
+    pub fault: Fault,
+    pub outcome: Outcome,
+}
+
+/**
+    Number of runs of each outcome, for a group of runs
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutcomeCounts {
+    pub masked: usize,
+    pub output_changed: usize,
+    pub classification_changed: usize,
+}
+
+impl OutcomeCounts {
+    pub fn add(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Masked => self.masked += 1,
+            Outcome::OutputChanged => self.output_changed += 1,
+            Outcome::ClassificationChanged => self.classification_changed += 1,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.masked + self.output_changed + self.classification_changed
+    }
+}
+
+/**
+    Object containing the golden outputs and the result of each run of a campaign
+*/
+#[derive(Debug, Clone)]
+pub struct CampaignResult {
+    pub golden: Vec<Vec<Vec<u8>>>,
+    pub runs: Vec<RunResult>,
+}
+
+impl CampaignResult {
+    /** It returns the outcome counts of all the runs */
+    pub fn counts(&self) -> OutcomeCounts {
+        let mut counts = OutcomeCounts::default();
+        self.runs.iter().for_each(|run| counts.add(run.outcome));
+        counts
+    }
+
+    /**
+        It returns the outcome counts of the runs for each (layer, component), sorted by layer and component
+    */
+    pub fn counts_by_layer_and_component(&self) -> Vec<(usize, Component, OutcomeCounts)> {
+        let mut groups = Vec::<(usize, Component, OutcomeCounts)>::new();
+
+        for run in &self.runs {
+            let key = (run.fault.layer, Component::of(&run.fault.target));
+            match groups.iter_mut().find(|(layer, component, _)| (*layer, *component) == key) {
+                Some((_, _, counts)) => counts.add(run.outcome),
+                None => {
+                    let mut counts = OutcomeCounts::default();
+                    counts.add(run.outcome);
+                    groups.push((key.0, key.1, counts));
+                }
+            }
+        }
+
+        groups.sort_by_key(|(layer, component, _)| (*layer, *component));
+        groups
+    }
+
+    /**
+        It writes the aggregated results as CSV: a line for each (layer, component),
+        followed by the totals of each layer (component "all") and of each component (layer "all")
+    */
+    pub fn write_summary_csv<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "layer,component,runs,masked,output_changed,classification_changed,\
+                          masked_rate,output_changed_rate,classification_changed_rate")?;
+
+        let groups = self.counts_by_layer_and_component();
+        let mut lines = Vec::<(String, String, OutcomeCounts)>::new();
+
+        for (layer, component, counts) in &groups {
+            lines.push((layer.to_string(), component.name().to_string(), *counts));
+        }
+
+        let mut layers: Vec<usize> = groups.iter().map(|(layer, _, _)| *layer).collect();
+        layers.dedup();
+        for layer in layers {
+            lines.push((layer.to_string(), "all".to_string(), Self::sum(groups.iter().filter(|group| group.0 == layer))));
+        }
+
+        let mut components: Vec<Component> = groups.iter().map(|(_, component, _)| *component).collect();
+        components.sort();
+        components.dedup();
+        for component in components {
+            lines.push(("all".to_string(), component.name().to_string(),
+                        Self::sum(groups.iter().filter(|group| group.1 == component))));
+        }
+
+        for (layer, component, counts) in lines {
+            let rate = |count: usize| count as f64 / counts.total() as f64;
+            writeln!(writer, "{},{},{},{},{},{},{:.4},{:.4},{:.4}", layer, component, counts.total(),
+                     counts.masked, counts.output_changed, counts.classification_changed,
+                     rate(counts.masked), rate(counts.output_changed), rate(counts.classification_changed))?;
+        }
+
+        writer.flush()
+    }
+
+    /**
+        It writes a CSV line for each run, with its fault and its outcome
+    */
+    pub fn write_runs_csv<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "run,layer,component,neuron,index,fault_type,bit,start,end,outcome")?;
+
+        for (i, run) in self.runs.iter().enumerate() {
+            let fault = &run.fault;
+            let (neuron, index) = match fault.target {
+                FaultTarget::Weight { neuron, input } => (neuron, input.to_string()),
+                FaultTarget::IntraWeight { neuron, from } => (neuron, from.to_string()),
+                FaultTarget::Neuron { neuron, .. } => (neuron, String::new()),
+            };
+            let fault_type = match fault.fault_type {
+                FaultType::StuckAt0 => "stuck_at_0",
+                FaultType::StuckAt1 => "stuck_at_1",
+                FaultType::BitFlip => "bit_flip",
+            };
+            let end = if fault.end == u64::MAX { String::new() } else { fault.end.to_string() };
+
+            writeln!(writer, "{},{},{},{},{},{},{},{},{},{}", i, fault.layer, Component::of(&fault.target).name(),
+                     neuron, index, fault_type, fault.bit, fault.start, end, run.outcome.name())?;
+        }
+
+        writer.flush()
+    }
+
+    fn sum<'a, I: Iterator<Item=&'a (usize, Component, OutcomeCounts)>>(groups: I) -> OutcomeCounts {
+        groups.fold(OutcomeCounts::default(), |mut sum, (_, _, counts)| {
+            sum.masked += counts.masked;
+            sum.output_changed += counts.output_changed;
+            sum.classification_changed += counts.classification_changed;
+            sum
+        })
+    }
+}
@@ -0,0 +1,5 @@
+/* * Fault injection * */
+
+pub mod campaign;
+pub mod model;
+/* (faults are injected into replicas of the network, see DynSNN::process_with_faults()) */
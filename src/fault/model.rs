@@ -0,0 +1,228 @@
+/* * Fault model submodule * */
+
+use crate::snn::weights::WeightMatrix;
+
+/**
+    Type of a fault affecting a single bit of an element
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultType {
+    StuckAt0,   /* the bit is forced to 0 */
+    StuckAt1,   /* the bit is forced to 1 */
+    BitFlip,    /* the bit is inverted */
+}
+
+/**
+    Element of a neuron which can be affected by a fault:
+    its parameters and state, or the outputs of the internal operations of the neuron model
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NeuronSite {
+    VTh,            /* threshold potential */
+    VMem,           /* membrane potential */
+    VReset,         /* reset potential */
+    Tau,
+    Adder,          /* output of the adder of the weighted sums */
+    Comparator,     /* output of the threshold comparator (the bit of the fault is ignored) */
+    Multiplier,     /* output of the multiplier by the exponential of the potential decay */
+}
+
+/**
+    Element of a layer affected by a fault
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultTarget {
+    Weight { neuron: usize, input: usize },         /* weights[neuron][input] */
+    IntraWeight { neuron: usize, from: usize },     /* intra_weights[neuron][from] */
+    Neuron { neuron: usize, site: NeuronSite },
+}
+
+/**
+    Object representing a fault of a bit of an element of the network.
+    The fault is active only in the instants of its time window (boundaries included):
+    while it is active, each time the element is read (or each time the operation is computed),
+    its bit is forced to 0/1 or inverted; the stored value is never modified, so the element
+    behaves correctly again outside the window.
+    - A permanent fault has a window covering the whole input, a transient one a window of few instants
+    - The bits are the ones of the f64 (IEEE 754) representation of the values: 63 is the sign,
+      62..52 the exponent and 51..0 the mantissa; for the fixed-point neuron models, the neuron faults
+      affect the bits of the raw values instead (see Neuron::get_fault_width())
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fault {
+    pub layer: usize,
+    pub target: FaultTarget,
+    pub fault_type: FaultType,
+    pub bit: u8,
+    pub start: u64,     /* first instant of the time window */
+    pub end: u64,       /* last instant of the time window */
+}
+
+impl Fault {
+    /**
+        It creates a permanent fault.
+        - It panics if the bit is greater than 63
+    */
+    pub fn new(layer: usize, target: FaultTarget, fault_type: FaultType, bit: u8) -> Self {
+        if bit > 63 {
+            panic!("The bit of a fault must be in the range [0, 63]");
+        }
+
+        Self { layer, target, fault_type, bit, start: 0, end: u64::MAX }
+    }
+
+    /**
+        It restricts the fault to the instants in [start, end].
+        - It panics if start > end
+    */
+    pub fn with_window(mut self, start: u64, end: u64) -> Self {
+        if start > end {
+            panic!("The time window of a fault must have start <= end");
+        }
+
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    pub fn is_active(&self, t: u64) -> bool {
+        self.start <= t && t <= self.end
+    }
+
+    /**
+        It returns the faulty version of the value of the target element
+    */
+    pub fn apply(&self, value: f64) -> f64 {
+        if self.target_site() == Some(NeuronSite::Comparator) {
+            /* (the output of the comparator is a single bit) */
+            return match self.fault_type {
+                FaultType::StuckAt0 => 0.0,
+                FaultType::StuckAt1 => 1.0,
+                FaultType::BitFlip => if value != 0.0 { 0.0 } else { 1.0 },
+            };
+        }
+
+        let mask = 1u64 << self.bit;
+        let bits = value.to_bits();
+
+        f64::from_bits(match self.fault_type {
+            FaultType::StuckAt0 => bits & !mask,
+            FaultType::StuckAt1 => bits | mask,
+            FaultType::BitFlip => bits ^ mask,
+        })
+    }
+
+    /**
+        Same as apply(), for a raw fixed-point value of *width* bits (two's complement)
+        - A bit beyond the width does not exist, so it is not affected
+    */
+    pub fn apply_raw(&self, raw: i64, width: u32) -> i64 {
+        if self.target_site() == Some(NeuronSite::Comparator) {
+            return self.apply(raw as f64) as i64;
+        }
+        if self.bit as u32 >= width {
+            return raw;
+        }
+
+        let mask = 1u64 << self.bit;
+        let bits = raw as u64;
+        let bits = match self.fault_type {
+            FaultType::StuckAt0 => bits & !mask,
+            FaultType::StuckAt1 => bits | mask,
+            FaultType::BitFlip => bits ^ mask,
+        };
+
+        /* (sign extension of the *width* least significant bits) */
+        let unused = 64 - width;
+        ((bits << unused) as i64) >> unused
+    }
+
+    fn target_site(&self) -> Option<NeuronSite> {
+        match self.target {
+            FaultTarget::Neuron { site, .. } => Some(site),
+            _ => None,
+        }
+    }
+}
+
+/**
+    Trait used by the neuron models to read their elements through the faults
+    (see Neuron::compute_v_mem_with_faults())
+*/
+pub trait FaultInjector {
+    /** It returns the (possibly faulty) value of the given site */
+    fn inject(&self, site: NeuronSite, value: f64) -> f64;
+
+    /** Same as inject(), for a site stored as a raw fixed-point value of *width* bits */
+    fn inject_raw(&self, site: NeuronSite, raw: i64, width: u32) -> i64;
+}
+
+/* Injector of the faults of a single neuron, at a given instant */
+pub(crate) struct NeuronFaults<'a> {
+    faults: &'a [Fault],
+    neuron: usize,
+    t: u64,
+}
+
+impl<'a> NeuronFaults<'a> {
+    pub(crate) fn new(faults: &'a [Fault], neuron: usize, t: u64) -> Self {
+        Self { faults, neuron, t }
+    }
+}
+
+impl FaultInjector for NeuronFaults<'_> {
+    fn inject(&self, site: NeuronSite, value: f64) -> f64 {
+        self.faults.iter()
+            .filter(|fault| fault.target == FaultTarget::Neuron { neuron: self.neuron, site } && fault.is_active(self.t))
+            .fold(value, |value, fault| fault.apply(value))
+    }
+
+    fn inject_raw(&self, site: NeuronSite, raw: i64, width: u32) -> i64 {
+        self.faults.iter()
+            .filter(|fault| fault.target == FaultTarget::Neuron { neuron: self.neuron, site } && fault.is_active(self.t))
+            .fold(raw, |raw, fault| fault.apply_raw(raw, width))
+    }
+}
+
+/**
+    It returns the correction to be added to the weighted sum of a row of weights,
+    so that the faulty weights are used in place of the correct ones
+    - intra: whether the matrix contains the intra weights (IntraWeight targets) or the extra ones (Weight targets)
+*/
+pub(crate) fn weighted_sum_error(faults: &[Fault], weights: &WeightMatrix, intra: bool, row: usize,
+                                 spikes: &[u8], skip: Option<usize>, t: u64) -> f64 {
+    /* column of the weight of the row affected by the fault, if any */
+    let faulty_col = |fault: &Fault| match fault.target {
+        FaultTarget::Weight { neuron, input } if !intra && neuron == row => Some(input),
+        FaultTarget::IntraWeight { neuron, from } if intra && neuron == row => Some(from),
+        _ => None,
+    };
+
+    let mut cols: Vec<usize> = faults.iter()
+        .filter(|fault| fault.is_active(t))
+        .filter_map(faulty_col)
+        .filter(|col| spikes.get(*col).is_some_and(|spike| *spike != 0) && Some(*col) != skip)
+        .collect();
+    cols.sort_unstable();
+    cols.dedup();
+
+    cols.into_iter().map(|col| {
+        let weight = weights.get(row, col);
+        faulty_weight(faults, intra, row, col, weight, t) - weight
+    }).sum()
+}
+
+/**
+    It returns the weight (row, col) of a matrix as affected by the active faults (see weighted_sum_error()),
+    where more faults on the same weight are applied one after the other
+*/
+pub(crate) fn faulty_weight(faults: &[Fault], intra: bool, row: usize, col: usize, weight: f64, t: u64) -> f64 {
+    faults.iter()
+        .filter(|fault| fault.is_active(t))
+        .filter(|fault| match fault.target {
+            FaultTarget::Weight { neuron, input } => !intra && (neuron, input) == (row, col),
+            FaultTarget::IntraWeight { neuron, from } => intra && (neuron, from) == (row, col),
+            _ => false,
+        })
+        .fold(weight, |weight, fault| fault.apply(weight))
+}
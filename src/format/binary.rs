@@ -0,0 +1,658 @@
+/* * Binary network format submodule * */
+
+/*
+    Compact little-endian binary format for (large) trained networks:
+
+    offset  size    field
+    ------  ----    -----
+    0       4       magic number ("PSNN")
+    4       2       format version (u16)
+    6       1       weights type (0 = f32, 1 = f64)
+    7       1       reserved (0)
+    8       8       input dimension of the network (u64)
+    16      8       number of layers (u64)
+    24      96 * L  layer table, one entry for each layer:
+                        - number of neurons (u64)
+                        - input dimension of the layer (u64)
+                        - neuron model identifier (u32)
+                        - number of parameters of each neuron (u32)
+                        - offset of the neurons' parameters blob (u64)
+                        - offset of the extra weights blob (u64)
+                        - offset of the intra weights blob (u64)
+                        - sign policy (u8: 0 = Strict, 1 = Dale, 2 = Unconstrained)
+                        - winner-take-all mode (u8: 0 = None, 1 = Hard, 2 = KWinners, 3 = Soft)
+                        - recurrence mode (u8: 0 = Legacy, 1 = Delayed, 2 = Settling)
+                        - flags (u8: bit 0 = weight limit, bit 1 = dynamic synapses)
+                        - reserved (4 bytes, 0)
+                        - winner-take-all parameter (the number of winners as u64, or the strength as f64)
+                        - maximum number of iterations of the settling recurrence mode (u64)
+                        - weight limit (f64)
+                        - time interval of the dynamic synapses (f64)
+                        - offset of the dynamic synapses blob (u64)
+    ...             blobs, each one aligned to 8 bytes:
+                        - neurons' parameters: f64, one row for each neuron
+                        - extra weights: (num_neurons x input_dimension) weights, row-major
+                        - intra weights: (num_neurons x num_neurons) weights, row-major
+                        - dynamic synapses (if any): (num_neurons x input_dimension) triples (u, tau_rec,
+                          tau_facil) of f64, row-major
+
+    The files of version 1 have 48-byte entries (up to the offset of the intra weights blob),
+    and their layers have the default configuration (Strict sign policy, no weight limit, ...)
+*/
+
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use memmap2::Mmap;
+use crate::neuron::Neuron;
+use crate::snn::DynSNN;
+use crate::snn::layer::{Layer, RecurrenceMode};
+use crate::snn::synapses::{DynamicSynapses, StpParams};
+use crate::snn::weights::{self, MappedWeights, SignPolicy, WeightMatrix, WeightsType};
+use crate::snn::wta::WtaMode;
+
+pub const MAGIC: [u8; 4] = *b"PSNN";
+pub const VERSION: u16 = 2;
+
+const HEADER_SIZE: usize = 24;
+const LAYER_ENTRY_SIZE: usize = 96;
+const LAYER_ENTRY_SIZE_V1: usize = 48;
+const ALIGNMENT: usize = 8;
+
+/* (flags of the layer configuration) */
+const WEIGHT_LIMIT_FLAG: u8 = 1;
+const SYNAPSES_FLAG: u8 = 2;
+
+/**
+    Trait for the Neuron models that can be stored in a binary network file.
+    Each model is identified by a (unique) MODEL_ID, and its (constant) parameters
+    are stored as a row of PARAMS_NUMBER f64 values
+*/
+pub trait SerializableNeuron: Neuron + Clone + Send + 'static {
+    const MODEL_ID: u32;
+    const PARAMS_NUMBER: usize;
+
+    /** It returns the PARAMS_NUMBER parameters describing the neuron */
+    fn to_params(&self) -> Vec<f64>;
+
+    /** It creates a new neuron from its PARAMS_NUMBER parameters */
+    fn from_params(params: &[f64]) -> Self;
+}
+
+/* Object representing an entry of the layer table */
+#[derive(Debug, Clone)]
+struct LayerEntry {
+    num_neurons: usize,
+    input_dimension: usize,
+    model_id: u32,
+    params_number: usize,
+    params_offset: usize,
+    weights_offset: usize,
+    intra_weights_offset: usize,
+    config: LayerConfig,
+}
+
+/* Object representing the configuration of a layer (stored in its entry of the layer table) */
+#[derive(Debug, Clone, Default)]
+struct LayerConfig {
+    sign_policy: SignPolicy,
+    wta: WtaMode,
+    recurrence: RecurrenceMode,
+    weight_limit: Option<f64>,
+    synapses: Option<(f64, usize)>,     /* time interval and offset of the dynamic synapses blob */
+}
+
+/* Object representing the header (with the layer table) of a binary network file */
+#[derive(Debug, Clone)]
+struct Header {
+    weights_type: WeightsType,
+    layers: Vec<LayerEntry>,
+}
+
+/**
+    It writes the network into *writer*, storing the weights with the given scalar type
+    - Storing the weights as f32 halves the size of the file, but the weights lose precision
+*/
+pub fn write<N: SerializableNeuron, W: Write>(snn: &DynSNN<N>, writer: &mut W, weights_type: WeightsType)
+    -> io::Result<()> {
+    let layers: Vec<_> = snn.layers().iter().map(|layer| layer.lock().unwrap()).collect();
+    let input_dimension = layers.first().map_or(0, |layer| layer.weight_matrix().cols());
+
+    /* compute the layer table (the offsets of all the blobs) */
+    let mut entries = Vec::<LayerEntry>::with_capacity(layers.len());
+    let mut offset = HEADER_SIZE + LAYER_ENTRY_SIZE * layers.len();
+    let mut layer_input_dimension = input_dimension;
+
+    for layer in &layers {
+        let num_neurons = layer.get_neurons_number();
+
+        let params_offset = align(offset);
+        let weights_offset = align(params_offset + num_neurons * N::PARAMS_NUMBER * 8);
+        let intra_weights_offset = align(weights_offset + num_neurons * layer_input_dimension * weights_type.size());
+        offset = intra_weights_offset + num_neurons * num_neurons * weights_type.size();
+
+        let synapses = layer.get_dynamic_synapses().map(|synapses| {
+            let synapses_offset = align(offset);
+            offset = synapses_offset + num_neurons * layer_input_dimension * 3 * 8;
+            (synapses.get_dt(), synapses_offset)
+        });
+
+        entries.push(LayerEntry {
+            num_neurons,
+            input_dimension: layer_input_dimension,
+            model_id: N::MODEL_ID,
+            params_number: N::PARAMS_NUMBER,
+            params_offset,
+            weights_offset,
+            intra_weights_offset,
+            config: LayerConfig {
+                sign_policy: layer.get_sign_policy(),
+                wta: layer.get_wta_mode(),
+                recurrence: layer.get_recurrence_mode(),
+                weight_limit: layer.get_weight_limit(),
+                synapses
+            }
+        });
+
+        layer_input_dimension = num_neurons;
+    }
+
+    /* write header and layer table */
+    let mut written = 0usize;
+
+    written += write_bytes(writer, &MAGIC)?;
+    written += write_bytes(writer, &VERSION.to_le_bytes())?;
+    written += write_bytes(writer, &[weights_type_code(weights_type), 0u8])?;
+    written += write_bytes(writer, &(input_dimension as u64).to_le_bytes())?;
+    written += write_bytes(writer, &(layers.len() as u64).to_le_bytes())?;
+
+    for entry in &entries {
+        written += write_bytes(writer, &(entry.num_neurons as u64).to_le_bytes())?;
+        written += write_bytes(writer, &(entry.input_dimension as u64).to_le_bytes())?;
+        written += write_bytes(writer, &entry.model_id.to_le_bytes())?;
+        written += write_bytes(writer, &(entry.params_number as u32).to_le_bytes())?;
+        written += write_bytes(writer, &(entry.params_offset as u64).to_le_bytes())?;
+        written += write_bytes(writer, &(entry.weights_offset as u64).to_le_bytes())?;
+        written += write_bytes(writer, &(entry.intra_weights_offset as u64).to_le_bytes())?;
+        written += write_config(writer, &entry.config)?;
+    }
+
+    /* write the blobs of each layer */
+    for (layer, entry) in layers.iter().zip(entries.iter()) {
+        written += write_padding(writer, entry.params_offset - written)?;
+        for neuron in layer.neurons() {
+            for param in neuron.to_params() {
+                written += write_bytes(writer, &param.to_le_bytes())?;
+            }
+        }
+
+        written += write_padding(writer, entry.weights_offset - written)?;
+        written += write_matrix(writer, layer.weight_matrix(), entry.input_dimension, weights_type)?;
+
+        written += write_padding(writer, entry.intra_weights_offset - written)?;
+        written += write_matrix(writer, layer.intra_weight_matrix(), entry.num_neurons, weights_type)?;
+
+        if let (Some(synapses), Some((_, synapses_offset))) = (layer.get_dynamic_synapses(), entry.config.synapses) {
+            written += write_padding(writer, synapses_offset - written)?;
+            for neuron in 0..entry.num_neurons {
+                for input in 0..entry.input_dimension {
+                    let params = synapses.get_params(neuron, input);
+                    for param in [params.u, params.tau_rec, params.tau_facil] {
+                        written += write_bytes(writer, &param.to_le_bytes())?;
+                    }
+                }
+            }
+        }
+    }
+
+    writer.flush()
+}
+
+/**
+    It saves the network into the file at *path* (see write())
+*/
+pub fn save<N: SerializableNeuron, P: AsRef<Path>>(snn: &DynSNN<N>, path: P, weights_type: WeightsType)
+    -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write(snn, &mut writer, weights_type)
+}
+
+/**
+    It reads a network from *reader*, copying all the weights into the memory
+    - The layers get the configuration stored in the file (sign policy, weight limit, winner-take-all
+      and recurrence modes, dynamic synapses), and their weights must comply with it
+*/
+pub fn read<N: SerializableNeuron, R: Read>(reader: &mut R) -> io::Result<DynSNN<N>> {
+    read_network(reader, None)
+}
+
+/**
+    It reads a network from *reader* (see read()), with the given policy on the signs of the weights
+    instead of the one stored in the file
+    - It returns an InvalidData error if the weights do not comply with it
+*/
+pub fn read_with_sign_policy<N: SerializableNeuron, R: Read>(reader: &mut R, sign_policy: SignPolicy)
+    -> io::Result<DynSNN<N>> {
+    read_network(reader, Some(sign_policy))
+}
+
+/**
+    It loads the network stored in the file at *path*, copying all the weights into the memory (see read())
+*/
+pub fn load<N: SerializableNeuron, P: AsRef<Path>>(path: P) -> io::Result<DynSNN<N>> {
+    let mut file = File::open(path)?;
+    read_network(&mut file, None)
+}
+
+/**
+    It loads the network stored in the file at *path* (see load()), with the given policy on the signs
+    of the weights instead of the one stored in the file
+    - It returns an InvalidData error if the weights do not comply with it
+*/
+pub fn load_with_sign_policy<N: SerializableNeuron, P: AsRef<Path>>(path: P, sign_policy: SignPolicy)
+    -> io::Result<DynSNN<N>> {
+    let mut file = File::open(path)?;
+    read_network(&mut file, Some(sign_policy))
+}
+
+/**
+    It loads the network stored in the file at *path* by *memory-mapping* the weights blobs:
+    the weights are not copied, but they are read directly from the file while the network is running
+    (the neurons' parameters and the dynamic synapses are still copied, since they are small).
+    - The file must not be modified while the network is alive
+    - The layers get the configuration stored in the file (see read())
+*/
+pub fn load_mapped<N: SerializableNeuron, P: AsRef<Path>>(path: P) -> io::Result<DynSNN<N>> {
+    load_mapped_network(path, None)
+}
+
+/**
+    It loads the network stored in the file at *path* by memory-mapping the weights blobs (see load_mapped()),
+    with the given policy on the signs of the weights instead of the one stored in the file
+    - It returns an InvalidData error if the weights do not comply with it
+*/
+pub fn load_mapped_with_sign_policy<N: SerializableNeuron, P: AsRef<Path>>(path: P, sign_policy: SignPolicy)
+    -> io::Result<DynSNN<N>> {
+    load_mapped_network(path, Some(sign_policy))
+}
+
+/**
+    It returns the identifier of the neuron model of the network stored in the file at *path* (see
+    SerializableNeuron::MODEL_ID), i.e. the model with which the network can be loaded
+    - All the layers of a network have the same model, since a DynSNN has a single one
+*/
+pub fn load_model_id<P: AsRef<Path>>(path: P) -> io::Result<u32> {
+    let mut bytes = [0u8; HEADER_SIZE + 24];
+    File::open(path)?.read_exact(&mut bytes).map_err(|error| match error.kind() {
+        io::ErrorKind::UnexpectedEof => invalid_data("The file is too short to contain a network header"),
+        _ => error,
+    })?;
+
+    if bytes[0..4] != MAGIC {
+        return Err(invalid_data("Wrong magic number: the file is not a binary network file"));
+    }
+    let version = u16::from_le_bytes(bytes[4..6].try_into().unwrap());
+    if version != 1 && version != VERSION {
+        return Err(invalid_data(&format!("Unsupported format version {}", version)));
+    }
+    if read_u64(&bytes, 16) == 0 {
+        return Err(invalid_data("The network must have at least one layer"));
+    }
+
+    Ok(u32::from_le_bytes(bytes[HEADER_SIZE + 16..HEADER_SIZE + 20].try_into().unwrap()))
+}
+
+/* private functions */
+
+/* (read(), with the sign policy of the file if sign_policy is None) */
+fn read_network<N: SerializableNeuron, R: Read>(reader: &mut R, sign_policy: Option<SignPolicy>)
+    -> io::Result<DynSNN<N>> {
+    let mut bytes = Vec::<u8>::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let header = parse_header::<N>(&bytes)?;
+    let mut layers: Vec<Layer<N>> = Vec::with_capacity(header.layers.len());
+
+    for entry in &header.layers {
+        let neurons = read_neurons::<N>(&bytes, entry);
+        let weights = read_matrix(&bytes, entry.weights_offset,
+                                  entry.num_neurons, entry.input_dimension, header.weights_type);
+        let intra_weights = read_matrix(&bytes, entry.intra_weights_offset,
+                                        entry.num_neurons, entry.num_neurons, header.weights_type);
+
+        layers.push(Layer::new(neurons, weights, intra_weights));
+    }
+
+    build_network(&bytes, layers, &header, sign_policy)
+}
+
+/* (load_mapped(), with the sign policy of the file if sign_policy is None) */
+fn load_mapped_network<N: SerializableNeuron, P: AsRef<Path>>(path: P, sign_policy: Option<SignPolicy>)
+    -> io::Result<DynSNN<N>> {
+    let file = File::open(path)?;
+    /* Safety: the mapping is read-only, and the file is required not to change while in use */
+    let mmap = Arc::new(unsafe { Mmap::map(&file)? });
+
+    let header = parse_header::<N>(&mmap)?;
+    let mut layers: Vec<Layer<N>> = Vec::with_capacity(header.layers.len());
+
+    for entry in &header.layers {
+        let neurons = read_neurons::<N>(&mmap, entry);
+        let weights = MappedWeights::new(mmap.clone(), entry.weights_offset,
+                                         entry.num_neurons, entry.input_dimension, header.weights_type);
+        let intra_weights = MappedWeights::new(mmap.clone(), entry.intra_weights_offset,
+                                               entry.num_neurons, entry.num_neurons, header.weights_type);
+
+        let layer = Layer::from_weight_matrices(neurons,
+                                                WeightMatrix::Mapped(weights),
+                                                WeightMatrix::Mapped(intra_weights));
+        layers.push(layer);
+    }
+
+    build_network(&mmap, layers, &header, sign_policy)
+}
+
+/**
+    It parses (and validates) the header and the layer table of a binary network file
+*/
+fn parse_header<N: SerializableNeuron>(bytes: &[u8]) -> io::Result<Header> {
+    if bytes.len() < HEADER_SIZE {
+        return Err(invalid_data("The file is too short to contain a network header"));
+    }
+    if bytes[0..4] != MAGIC {
+        return Err(invalid_data("Wrong magic number: the file is not a binary network file"));
+    }
+
+    let version = u16::from_le_bytes(bytes[4..6].try_into().unwrap());
+    let entry_size = match version {
+        1 => LAYER_ENTRY_SIZE_V1,
+        VERSION => LAYER_ENTRY_SIZE,
+        _ => return Err(invalid_data(&format!("Unsupported format version {}", version))),
+    };
+
+    let weights_type = match bytes[6] {
+        0 => WeightsType::F32,
+        1 => WeightsType::F64,
+        code => return Err(invalid_data(&format!("Unknown weights type {}", code))),
+    };
+
+    let input_dimension = read_u64(bytes, 8) as usize;
+    let num_layers = read_u64(bytes, 16) as usize;
+
+    if num_layers == 0 {
+        return Err(invalid_data("The network must have at least one layer"));
+    }
+    if HEADER_SIZE.saturating_add(num_layers.saturating_mul(entry_size)) > bytes.len() {
+        return Err(invalid_data("The layer table exceeds the size of the file"));
+    }
+
+    let mut layers = Vec::<LayerEntry>::with_capacity(num_layers);
+    let mut expected_input_dimension = input_dimension;
+
+    for l in 0..num_layers {
+        let base = HEADER_SIZE + l * entry_size;
+        let entry = LayerEntry {
+            num_neurons: read_u64(bytes, base) as usize,
+            input_dimension: read_u64(bytes, base + 8) as usize,
+            model_id: u32::from_le_bytes(bytes[base + 16..base + 20].try_into().unwrap()),
+            params_number: u32::from_le_bytes(bytes[base + 20..base + 24].try_into().unwrap()) as usize,
+            params_offset: read_u64(bytes, base + 24) as usize,
+            weights_offset: read_u64(bytes, base + 32) as usize,
+            intra_weights_offset: read_u64(bytes, base + 40) as usize,
+            config: if version == 1 { LayerConfig::default() } else { read_config(bytes, base + 48, l)? },
+        };
+
+        if entry.model_id != N::MODEL_ID || entry.params_number != N::PARAMS_NUMBER {
+            return Err(invalid_data(&format!("Layer {}: the neuron model does not match the requested one", l)));
+        }
+        if entry.input_dimension != expected_input_dimension {
+            return Err(invalid_data(&format!("Layer {}: the input dimension must be equal to the number \
+                                              of neurons of the previous layer", l)));
+        }
+
+        let mut blobs = vec![
+            (entry.params_offset, entry.num_neurons.checked_mul(entry.params_number).and_then(|n| n.checked_mul(8))),
+            (entry.weights_offset, entry.num_neurons.checked_mul(entry.input_dimension)
+                .and_then(|n| n.checked_mul(weights_type.size()))),
+            (entry.intra_weights_offset, entry.num_neurons.checked_mul(entry.num_neurons)
+                .and_then(|n| n.checked_mul(weights_type.size()))),
+        ];
+        if let Some((_, synapses_offset)) = entry.config.synapses {
+            blobs.push((synapses_offset, entry.num_neurons.checked_mul(entry.input_dimension)
+                .and_then(|n| n.checked_mul(3 * 8))));
+        }
+
+        for (offset, size) in blobs {
+            match size.and_then(|size| offset.checked_add(size)) {
+                Some(end) if end <= bytes.len() => {},
+                _ => return Err(invalid_data(&format!("Layer {}: a blob exceeds the size of the file", l)))
+            }
+        }
+
+        expected_input_dimension = entry.num_neurons;
+        layers.push(entry);
+    }
+
+    Ok(Header { weights_type, layers })
+}
+
+/**
+    It builds the network from its layers, configuring them as in the layer table (with the given sign policy,
+    if any) and checking their weights against their sign policy and weight limit (as the setters of the
+    network, but returning an error instead of panicking)
+*/
+fn build_network<N: SerializableNeuron>(bytes: &[u8], mut layers: Vec<Layer<N>>, header: &Header,
+                                        sign_policy: Option<SignPolicy>) -> io::Result<DynSNN<N>> {
+    /* (sign policy and intra weights of the previous layer) */
+    let mut previous: Option<(SignPolicy, Vec<Vec<f64>>)> = None;
+
+    for (l, (layer, entry)) in layers.iter_mut().zip(header.layers.iter()).enumerate() {
+        let config = &entry.config;
+        let sign_policy = sign_policy.unwrap_or(config.sign_policy);
+        let (weights, intra_weights) = (layer.get_weights(), layer.get_intra_weights());
+
+        let result = sign_policy.validate(&weights, false)
+            .and_then(|_| sign_policy.validate(&intra_weights, true))
+            .and_then(|_| previous.as_ref().map_or(Ok(()), |(previous_policy, previous_intra_weights)|
+                previous_policy.validate_across_layers(previous_intra_weights, &weights)))
+            .and_then(|_| weights::validate_limit(&weights, config.weight_limit))
+            .and_then(|_| weights::validate_limit(&intra_weights, config.weight_limit));
+        if let Err(message) = result {
+            return Err(invalid_data(&format!("Layer {}: {} ({:?} sign policy)", l, message, sign_policy)));
+        }
+
+        if let Err(message) = layer.validate_wta_mode(config.wta) {
+            return Err(invalid_data(&format!("Layer {}: {}", l, message)));
+        }
+
+        layer.set_sign_policy(sign_policy);
+        layer.set_weight_limit(config.weight_limit);
+        layer.set_wta_mode(config.wta);
+        layer.set_recurrence_mode(config.recurrence);
+        if let Some((dt, offset)) = config.synapses {
+            layer.set_dynamic_synapses(Some(read_synapses(bytes, offset, entry, dt)?));
+        }
+        previous = Some((sign_policy, intra_weights));
+    }
+
+    Ok(DynSNN::new(layers.into_iter().map(|layer| Arc::new(Mutex::new(layer))).collect()))
+}
+
+/* It reads the dynamic synapses of a layer (one triple of parameters for each weight) */
+fn read_synapses(bytes: &[u8], offset: usize, entry: &LayerEntry, dt: f64) -> io::Result<DynamicSynapses> {
+    let mut synapses: Option<DynamicSynapses> = None;
+
+    for neuron in 0..entry.num_neurons {
+        for input in 0..entry.input_dimension {
+            let start = offset + (neuron * entry.input_dimension + input) * 3 * 8;
+            let (u, tau_rec, tau_facil) = (read_f64(bytes, start), read_f64(bytes, start + 8), read_f64(bytes, start + 16));
+            /* (same checks of StpParams::new()) */
+            if !(u > 0.0 && u <= 1.0 && tau_rec >= 0.0 && tau_facil >= 0.0) {
+                return Err(invalid_data(&format!("Wrong parameters of the dynamic synapse ({}, {})", neuron, input)));
+            }
+
+            let params = StpParams { u, tau_rec, tau_facil };
+            synapses.get_or_insert_with(|| DynamicSynapses::new(entry.num_neurons, entry.input_dimension, params, dt))
+                .set_params(neuron, input, params);
+        }
+    }
+
+    Ok(synapses.unwrap_or_else(|| DynamicSynapses::new(entry.num_neurons, entry.input_dimension,
+                                                        StpParams::new(1.0, 0.0, 0.0), dt)))
+}
+
+/* It reads (and validates) the configuration of the layer l, stored from the offset base of its entry */
+fn read_config(bytes: &[u8], base: usize, l: usize) -> io::Result<LayerConfig> {
+    let wrong = |field: &str| Err(invalid_data(&format!("Layer {}: wrong {}", l, field)));
+    let flags = bytes[base + 3];
+
+    let sign_policy = match bytes[base] {
+        0 => SignPolicy::Strict,
+        1 => SignPolicy::Dale,
+        2 => SignPolicy::Unconstrained,
+        _ => return wrong("sign policy"),
+    };
+    let wta = match bytes[base + 1] {
+        0 => WtaMode::None,
+        1 => WtaMode::Hard,
+        2 => match read_u64(bytes, base + 8) {
+            0 => return wrong("number of winners"),
+            k => WtaMode::KWinners(k as usize),
+        },
+        3 => match read_f64(bytes, base + 8) {
+            strength if strength >= 0.0 => WtaMode::Soft(strength),
+            _ => return wrong("strength of the inhibition"),
+        },
+        _ => return wrong("winner-take-all mode"),
+    };
+    let recurrence = match bytes[base + 2] {
+        0 => RecurrenceMode::Legacy,
+        1 => RecurrenceMode::Delayed,
+        2 => match read_u64(bytes, base + 16) {
+            0 => return wrong("maximum number of iterations"),
+            iterations => RecurrenceMode::Settling(iterations as usize),
+        },
+        _ => return wrong("recurrence mode"),
+    };
+    let weight_limit = match read_f64(bytes, base + 24) {
+        _ if flags & WEIGHT_LIMIT_FLAG == 0 => None,
+        limit if limit >= 0.0 => Some(limit),
+        _ => return wrong("weight limit"),
+    };
+    let synapses = (flags & SYNAPSES_FLAG != 0).then(|| (read_f64(bytes, base + 32), read_u64(bytes, base + 40) as usize));
+
+    if flags & !(WEIGHT_LIMIT_FLAG | SYNAPSES_FLAG) != 0 {
+        return wrong("flags");
+    }
+
+    Ok(LayerConfig { sign_policy, wta, recurrence, weight_limit, synapses })
+}
+
+/* It writes the configuration of a layer, at the end of its entry of the layer table */
+fn write_config<W: Write>(writer: &mut W, config: &LayerConfig) -> io::Result<usize> {
+    let sign_policy = match config.sign_policy {
+        SignPolicy::Strict => 0u8,
+        SignPolicy::Dale => 1,
+        SignPolicy::Unconstrained => 2,
+    };
+    let (wta, wta_param) = match config.wta {
+        WtaMode::None => (0u8, 0u64),
+        WtaMode::Hard => (1, 0),
+        WtaMode::KWinners(k) => (2, k as u64),
+        WtaMode::Soft(strength) => (3, strength.to_bits()),
+    };
+    let (recurrence, iterations) = match config.recurrence {
+        RecurrenceMode::Legacy => (0u8, 0u64),
+        RecurrenceMode::Delayed => (1, 0),
+        RecurrenceMode::Settling(iterations) => (2, iterations as u64),
+    };
+    let mut flags = 0u8;
+    if config.weight_limit.is_some() {
+        flags |= WEIGHT_LIMIT_FLAG;
+    }
+    if config.synapses.is_some() {
+        flags |= SYNAPSES_FLAG;
+    }
+    let (dt, synapses_offset) = config.synapses.unwrap_or((0.0, 0));
+
+    let mut written = write_bytes(writer, &[sign_policy, wta, recurrence, flags, 0, 0, 0, 0])?;
+    written += write_bytes(writer, &wta_param.to_le_bytes())?;
+    written += write_bytes(writer, &iterations.to_le_bytes())?;
+    written += write_bytes(writer, &config.weight_limit.unwrap_or(0.0).to_le_bytes())?;
+    written += write_bytes(writer, &dt.to_le_bytes())?;
+    written += write_bytes(writer, &(synapses_offset as u64).to_le_bytes())?;
+    Ok(written)
+}
+
+fn read_neurons<N: SerializableNeuron>(bytes: &[u8], entry: &LayerEntry) -> Vec<N> {
+    (0..entry.num_neurons).map(|n| {
+        let params: Vec<f64> = (0..entry.params_number)
+            .map(|p| read_f64(bytes, entry.params_offset + (n * entry.params_number + p) * 8))
+            .collect();
+        N::from_params(&params)
+    }).collect()
+}
+
+fn read_matrix(bytes: &[u8], offset: usize, rows: usize, cols: usize, weights_type: WeightsType) -> Vec<Vec<f64>> {
+    let size = weights_type.size();
+
+    (0..rows).map(|row| {
+        (0..cols).map(|col| {
+            let start = offset + (row * cols + col) * size;
+            match weights_type {
+                WeightsType::F32 => f32::from_le_bytes(bytes[start..start + 4].try_into().unwrap()) as f64,
+                WeightsType::F64 => read_f64(bytes, start),
+            }
+        }).collect()
+    }).collect()
+}
+
+fn write_matrix<W: Write>(writer: &mut W, matrix: &WeightMatrix, cols: usize, weights_type: WeightsType)
+    -> io::Result<usize> {
+    let mut written = 0usize;
+
+    for row in 0..matrix.rows() {
+        for col in 0..cols {
+            let weight = matrix.get(row, col);
+            written += match weights_type {
+                WeightsType::F32 => write_bytes(writer, &(weight as f32).to_le_bytes())?,
+                WeightsType::F64 => write_bytes(writer, &weight.to_le_bytes())?,
+            };
+        }
+    }
+
+    Ok(written)
+}
+
+fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<usize> {
+    writer.write_all(bytes)?;
+    Ok(bytes.len())
+}
+
+fn write_padding<W: Write>(writer: &mut W, padding: usize) -> io::Result<usize> {
+    write_bytes(writer, &vec![0u8; padding])
+}
+
+fn weights_type_code(weights_type: WeightsType) -> u8 {
+    match weights_type {
+        WeightsType::F32 => 0,
+        WeightsType::F64 => 1,
+    }
+}
+
+fn align(offset: usize) -> usize {
+    offset.div_ceil(ALIGNMENT) * ALIGNMENT
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+fn read_f64(bytes: &[u8], offset: usize) -> f64 {
+    f64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
@@ -0,0 +1,549 @@
+/* * Graph submodule * */
+
+/*
+    JSON graph of a network, made of named nodes and of directed edges between them.
+    A DynSNN is mapped onto the following nodes:
+    - "input" (Input) and "output" (Output) nodes, with the dimensions of the network
+    - "fc{l}" (Affine, with zero bias) for the extra weights of the l-th layer
+    - "lif{l}" (LIF or CubaLIF, see GraphNeuron) for the neurons of the l-th layer
+    - "rec{l}" (Linear) for the intra weights of the l-th layer,
+      connected as a loop lif{l} -> rec{l} -> lif{l} (recurrent intra-layer connections)
+
+    The file is a "nodes" object (each node with its "type" and its parameters' arrays) and an "edges"
+    array of [source, target] pairs. The node types are named after the ones of NIR (Neuromorphic
+    Intermediate Representation), but the files are a format of this library, not .nir (HDF5) files.
+    - LIF nodes are mapped onto LifNeurons and CubaLIF nodes onto CubaLifNeurons
+*/
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use serde_json::{json, Map, Value};
+use crate::models::neuron::cuba_lif::SynapseKernel;
+use crate::snn::neuron::Neuron;
+use crate::snn::DynSNN;
+use crate::snn::layer::Layer;
+use crate::snn::weights::SignPolicy;
+
+/**
+    Object representing a node of a graph (only the node types supported by the library
+    are decoded, all the other ones are kept as Unsupported)
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphNode {
+    Input { shape: Vec<usize> },
+    Output { shape: Vec<usize> },
+    Affine { weight: Vec<Vec<f64>>, bias: Vec<f64> },
+    Linear { weight: Vec<Vec<f64>> },
+    Lif { tau: Vec<f64>, r: Vec<f64>, v_leak: Vec<f64>, v_threshold: Vec<f64>, v_reset: Vec<f64> },
+    CubaLif { tau_syn: Vec<f64>, tau_syn_inh: Vec<f64>, tau_mem: Vec<f64>, r: Vec<f64>, v_leak: Vec<f64>,
+              v_threshold: Vec<f64>, v_reset: Vec<f64>, w_in: Vec<f64>, kernel: Vec<SynapseKernel> },
+    Unsupported { node_type: String },
+}
+
+impl GraphNode {
+    /** It returns the type name of the node */
+    pub fn node_type(&self) -> &str {
+        match self {
+            GraphNode::Input { .. } => "Input",
+            GraphNode::Output { .. } => "Output",
+            GraphNode::Affine { .. } => "Affine",
+            GraphNode::Linear { .. } => "Linear",
+            GraphNode::Lif { .. } => "LIF",
+            GraphNode::CubaLif { .. } => "CubaLIF",
+            GraphNode::Unsupported { node_type } => node_type,
+        }
+    }
+}
+
+/**
+    Object representing a network graph (see the JSON encoding above)
+    - nodes: nodes of the graph, identified by their name
+    - edges: (source, target) pairs of node names
+*/
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NetworkGraph {
+    pub nodes: BTreeMap<String, GraphNode>,
+    pub edges: Vec<(String, String)>,
+}
+
+impl NetworkGraph {
+    /**
+        It decodes a graph from its JSON form
+    */
+    pub fn from_json(value: &Value) -> io::Result<Self> {
+        let nodes_value = value.get("nodes").and_then(Value::as_object)
+            .ok_or_else(|| invalid_data("The graph must have a 'nodes' object"))?;
+        let edges_value = value.get("edges").and_then(Value::as_array)
+            .ok_or_else(|| invalid_data("The graph must have an 'edges' array"))?;
+
+        let mut nodes = BTreeMap::<String, GraphNode>::new();
+        for (name, node_value) in nodes_value {
+            nodes.insert(name.clone(), decode_node(name, node_value)?);
+        }
+
+        let mut edges = Vec::<(String, String)>::with_capacity(edges_value.len());
+        for edge in edges_value {
+            match edge.as_array().map(|pair| pair.as_slice()) {
+                Some([Value::String(source), Value::String(target)]) =>
+                    edges.push((source.clone(), target.clone())),
+                _ => return Err(invalid_data("Each edge must be a [source, target] pair of node names")),
+            }
+        }
+
+        Ok(Self { nodes, edges })
+    }
+
+    /**
+        It encodes the graph into its JSON form
+    */
+    pub fn to_json(&self) -> Value {
+        let mut nodes = Map::new();
+        for (name, node) in &self.nodes {
+            nodes.insert(name.clone(), encode_node(node));
+        }
+
+        let edges: Vec<Value> = self.edges.iter()
+            .map(|(source, target)| json!([source, target]))
+            .collect();
+
+        json!({ "nodes": nodes, "edges": edges })
+    }
+
+    /* (names of the nodes reached by an edge starting from *source*) */
+    fn successors(&self, source: &str) -> Vec<&str> {
+        self.edges.iter()
+            .filter(|(s, _)| s == source)
+            .map(|(_, target)| target.as_str())
+            .collect()
+    }
+
+    /* (names of the nodes with an edge ending in *target*) */
+    fn predecessors(&self, target: &str) -> Vec<&str> {
+        self.edges.iter()
+            .filter(|(_, t)| t == target)
+            .map(|(source, _)| source.as_str())
+            .collect()
+    }
+}
+
+/**
+    Trait for the Neuron models that can be mapped onto the nodes of a graph
+    - The time interval between two instants (dt) has no counterpart in the graph, so it must be given back when importing
+*/
+pub trait GraphNeuron: Neuron + Clone + Send + 'static {
+    /** It returns the node representing the neurons of a layer */
+    fn to_node(neurons: &[Self]) -> GraphNode;
+
+    /**
+        It creates the *num_neurons* neurons of a layer from their node (named *name*), together with the
+        factor of each neuron folded into its incoming weights (e.g. the LIF resistance r)
+        - It returns an InvalidData error if the node does not describe this model, or has the wrong sizes
+    */
+    fn from_node(name: &str, node: &GraphNode, num_neurons: usize, dt: f64) -> io::Result<(Vec<Self>, Vec<f64>)>;
+}
+
+/**
+    It maps a DynSNN onto a graph.
+    - The factors of the incoming weights (e.g. the LIF resistance r) are always 1, since the weighted sums
+      are directly added to the membrane potential (or to the synaptic currents)
+*/
+pub fn export<N: GraphNeuron>(snn: &DynSNN<N>) -> NetworkGraph {
+    let mut graph = NetworkGraph::default();
+    let layers = snn.get_layers();
+
+    let input_dimension = layers.first().map_or(0, |layer| layer.weight_matrix().cols());
+    let output_dimension = layers.last().map_or(0, |layer| layer.get_neurons_number());
+
+    graph.nodes.insert("input".to_string(), GraphNode::Input { shape: vec![input_dimension] });
+    graph.nodes.insert("output".to_string(), GraphNode::Output { shape: vec![output_dimension] });
+
+    let mut previous = "input".to_string();
+
+    for (l, layer) in layers.iter().enumerate() {
+        let neurons = layer.get_neurons();
+        let (fc, lif, rec) = (format!("fc{}", l), format!("lif{}", l), format!("rec{}", l));
+
+        /* reflexive links are ignored by the network, so they are exported as 0 */
+        let mut intra_weights = layer.get_intra_weights();
+        intra_weights.iter_mut().enumerate().for_each(|(i, row)| row[i] = 0.0);
+
+        graph.nodes.insert(fc.clone(), GraphNode::Affine {
+            weight: layer.get_weights(),
+            bias: vec![0.0; neurons.len()]
+        });
+        graph.nodes.insert(lif.clone(), N::to_node(&neurons));
+        graph.nodes.insert(rec.clone(), GraphNode::Linear { weight: intra_weights });
+
+        graph.edges.push((previous, fc.clone()));
+        graph.edges.push((fc, lif.clone()));
+        graph.edges.push((lif.clone(), rec.clone()));
+        graph.edges.push((rec, lif.clone()));
+
+        previous = lif;
+    }
+
+    graph.edges.push((previous, "output".to_string()));
+
+    graph
+}
+
+/**
+    It builds a DynSNN from a graph, whose neuron nodes must all describe the model N (see GraphNeuron).
+    The graph must be a chain input -> (Affine | Linear) -> LIF -> ... -> output, where each LIF node
+    can have a recurrent Linear node (LIF -> Linear -> LIF) providing the intra weights of the layer.
+    - dt: time interval between two consecutive instants, used for all the neurons
+    - The factors of the neuron nodes (e.g. the LIF resistance r) are folded into the incoming weights of each neuron
+    - Any other node type (or topology) is rejected with an error
+    - The weights must comply with the strict sign policy (see import_with_sign_policy())
+*/
+pub fn import<N: GraphNeuron>(graph: &NetworkGraph, dt: f64) -> io::Result<DynSNN<N>> {
+    import_with_sign_policy(graph, dt, SignPolicy::Strict)
+}
+
+/**
+    Same as import(), but the weights are checked according to the given sign policy (e.g. Unconstrained
+    for trained networks with mixed-sign weights), which is also the one of the imported layers
+*/
+pub fn import_with_sign_policy<N: GraphNeuron>(graph: &NetworkGraph, dt: f64, sign_policy: SignPolicy)
+    -> io::Result<DynSNN<N>> {
+    for (name, node) in &graph.nodes {
+        if let GraphNode::Unsupported { node_type } = node {
+            return Err(invalid_data(&format!("Unsupported node type '{}' (node '{}')", node_type, name)));
+        }
+    }
+    for (source, target) in &graph.edges {
+        for name in [source, target] {
+            if !graph.nodes.contains_key(name) {
+                return Err(invalid_data(&format!("The edge ({}, {}) refers to an unknown node", source, target)));
+            }
+        }
+    }
+
+    let input_names: Vec<&String> = graph.nodes.iter()
+        .filter(|(_, node)| matches!(node, GraphNode::Input { .. }))
+        .map(|(name, _)| name)
+        .collect();
+
+    let mut current: &str = match input_names.as_slice() {
+        [name] => name,
+        _ => return Err(invalid_data("The graph must have exactly one Input node")),
+    };
+
+    let mut input_dimension = match &graph.nodes[current] {
+        GraphNode::Input { shape } => shape.iter().product::<usize>(),
+        _ => unreachable!(),
+    };
+
+    let mut layers: Vec<Arc<Mutex<Layer<N>>>> = Vec::new();
+    let mut visited = BTreeSet::<&str>::new();
+    visit(graph, &mut visited, current)?;
+
+    loop {
+        /* * forward connection (Affine or Linear node) * */
+        let fc = single_successor(graph, current)?;
+        visit(graph, &mut visited, fc)?;
+
+        let weights = match &graph.nodes[fc] {
+            GraphNode::Output { shape } => {
+                if shape.iter().product::<usize>() != input_dimension {
+                    return Err(invalid_data("The Output node shape does not match the last layer dimension"));
+                }
+                break;
+            },
+            GraphNode::Affine { weight, bias } => {
+                if bias.iter().any(|b| *b != 0.0) {
+                    return Err(invalid_data(&format!("Affine node '{}': biases are not supported", fc)));
+                }
+                weight
+            },
+            GraphNode::Linear { weight } => weight,
+            node => return Err(invalid_data(&format!("Node '{}': expected an Affine or Linear node, found {}",
+                                                     fc, node.node_type()))),
+        };
+
+        check_matrix(fc, weights, input_dimension)?;
+        let num_neurons = weights.len();
+
+        /* * neurons (LIF or CubaLIF node) * */
+        let lif = single_successor(graph, fc)?;
+        visit(graph, &mut visited, lif)?;
+
+        let (neurons, r) = N::from_node(lif, &graph.nodes[lif], num_neurons, dt)?;
+
+        /* * recurrent connection (optional Linear node looping back to the LIF node) * */
+        let recurrent: Vec<&str> = graph.successors(lif).into_iter()
+            .filter(|next| graph.successors(next) == vec![lif])
+            .collect();
+
+        let intra_weights = match recurrent.as_slice() {
+            [] => vec![vec![0.0; num_neurons]; num_neurons],
+            [rec] => match &graph.nodes[*rec] {
+                GraphNode::Linear { weight } => {
+                    visit(graph, &mut visited, rec)?;
+                    check_matrix(rec, weight, num_neurons)?;
+                    weight.clone()
+                },
+                node => return Err(invalid_data(&format!("Node '{}': recurrent connections must be Linear nodes, \
+                                                          found {}", rec, node.node_type()))),
+            },
+            _ => return Err(invalid_data(&format!("LIF node '{}' has more than one recurrent connection", lif))),
+        };
+
+        /* fold r into the weights, then check the weights signs */
+        let weights = scale_rows(weights, &r);
+        let intra_weights = scale_rows(&intra_weights, &r);
+
+        if let Err(message) = sign_policy.validate(&weights, false) {
+            return Err(invalid_data(&format!("Node '{}': {}", fc, message)));
+        }
+        if let Err(message) = sign_policy.validate(&intra_weights, true) {
+            return Err(invalid_data(&format!("LIF node '{}': {}", lif, message)));
+        }
+        if let Some(previous_layer) = layers.last() {
+            let previous_intra_weights = previous_layer.lock().unwrap().get_intra_weights();
+            if let Err(message) = sign_policy.validate_across_layers(&previous_intra_weights, &weights) {
+                return Err(invalid_data(&format!("Node '{}': {}", fc, message)));
+            }
+        }
+
+        let mut layer = Layer::new(neurons, weights, intra_weights);
+        layer.set_sign_policy(sign_policy);
+        layers.push(Arc::new(Mutex::new(layer)));
+
+        input_dimension = num_neurons;
+        current = lif;
+    }
+
+    if layers.is_empty() {
+        return Err(invalid_data("The graph must have at least one layer"));
+    }
+
+    Ok(DynSNN::new(layers))
+}
+
+/**
+    It reads a graph from the file at *path*
+*/
+pub fn read_graph<P: AsRef<Path>>(path: P) -> io::Result<NetworkGraph> {
+    let reader = BufReader::new(File::open(path)?);
+    let value: Value = serde_json::from_reader(reader)?;
+    NetworkGraph::from_json(&value)
+}
+
+/**
+    It writes a graph into the file at *path*
+*/
+pub fn write_graph<P: AsRef<Path>>(graph: &NetworkGraph, path: P) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(&mut writer, &graph.to_json())?;
+    writer.flush()
+}
+
+/**
+    It exports the network into the JSON graph file at *path* (see export() and write_graph())
+*/
+pub fn save<N: GraphNeuron, P: AsRef<Path>>(snn: &DynSNN<N>, path: P) -> io::Result<()> {
+    write_graph(&export(snn), path)
+}
+
+/**
+    It builds a network from the JSON graph file at *path* (see import() and read_graph())
+*/
+pub fn load<N: GraphNeuron, P: AsRef<Path>>(path: P, dt: f64) -> io::Result<DynSNN<N>> {
+    import(&read_graph(path)?, dt)
+}
+
+/* private functions */
+
+fn decode_node(name: &str, value: &Value) -> io::Result<GraphNode> {
+    let node_type = value.get("type").and_then(Value::as_str)
+        .ok_or_else(|| invalid_data(&format!("Node '{}' has no 'type'", name)))?;
+
+    let node = match node_type {
+        "Input" => GraphNode::Input { shape: decode_shape(name, value)? },
+        "Output" => GraphNode::Output { shape: decode_shape(name, value)? },
+        "Affine" => GraphNode::Affine {
+            weight: decode_matrix(name, value, "weight")?,
+            bias: decode_vector(name, value, "bias")?
+        },
+        "Linear" => GraphNode::Linear { weight: decode_matrix(name, value, "weight")? },
+        "LIF" => {
+            let v_leak = decode_vector(name, value, "v_leak")?;
+            GraphNode::Lif {
+                tau: decode_vector(name, value, "tau")?,
+                r: decode_vector(name, value, "r")?,
+                v_threshold: decode_vector(name, value, "v_threshold")?,
+                /* (v_reset is optional, 0 by default) */
+                v_reset: decode_optional_vector(name, value, "v_reset", v_leak.len(), 0.0)?,
+                v_leak
+            }
+        },
+        "CubaLIF" => {
+            let v_leak = decode_vector(name, value, "v_leak")?;
+            let tau_syn = decode_vector(name, value, "tau_syn")?;
+            GraphNode::CubaLif {
+                /* (tau_syn_inh and kernel are optional: the inhibitory current has the same time constant
+                   of the excitatory one, and the kernel is exponential, by default) */
+                tau_syn_inh: match value.get("tau_syn_inh") {
+                    None => tau_syn.clone(),
+                    Some(_) => decode_vector(name, value, "tau_syn_inh")?,
+                },
+                kernel: decode_kernels(name, value, v_leak.len())?,
+                tau_syn,
+                tau_mem: decode_vector(name, value, "tau_mem")?,
+                r: decode_vector(name, value, "r")?,
+                v_threshold: decode_vector(name, value, "v_threshold")?,
+                v_reset: decode_optional_vector(name, value, "v_reset", v_leak.len(), 0.0)?,
+                w_in: decode_optional_vector(name, value, "w_in", v_leak.len(), 1.0)?,
+                v_leak
+            }
+        },
+        other => GraphNode::Unsupported { node_type: other.to_string() },
+    };
+
+    Ok(node)
+}
+
+fn encode_node(node: &GraphNode) -> Value {
+    let node_type = node.node_type();
+
+    match node {
+        GraphNode::Input { shape } | GraphNode::Output { shape } => json!({ "type": node_type, "shape": shape }),
+        GraphNode::Affine { weight, bias } => json!({ "type": node_type, "weight": weight, "bias": bias }),
+        GraphNode::Linear { weight } => json!({ "type": node_type, "weight": weight }),
+        GraphNode::Lif { tau, r, v_leak, v_threshold, v_reset } => json!({
+            "type": node_type, "tau": tau, "r": r, "v_leak": v_leak,
+            "v_threshold": v_threshold, "v_reset": v_reset
+        }),
+        GraphNode::CubaLif { tau_syn, tau_syn_inh, tau_mem, r, v_leak, v_threshold, v_reset, w_in, kernel } => {
+            let kernel: Vec<&str> = kernel.iter().map(|kernel| match kernel {
+                SynapseKernel::Exponential => "exponential",
+                SynapseKernel::Alpha => "alpha",
+            }).collect();
+            json!({
+                "type": node_type, "tau_syn": tau_syn, "tau_syn_inh": tau_syn_inh, "tau_mem": tau_mem, "r": r,
+                "v_leak": v_leak, "v_threshold": v_threshold, "v_reset": v_reset, "w_in": w_in, "kernel": kernel
+            })
+        },
+        GraphNode::Unsupported { .. } => json!({ "type": node_type }),
+    }
+}
+
+fn decode_shape(name: &str, value: &Value) -> io::Result<Vec<usize>> {
+    decode_vector(name, value, "shape")?.into_iter()
+        .map(|dim| if dim >= 0.0 && dim.fract() == 0.0 { Ok(dim as usize) } else {
+            Err(invalid_data(&format!("Node '{}': 'shape' must contain non-negative integers", name)))
+        })
+        .collect()
+}
+
+fn decode_vector(name: &str, value: &Value, param: &str) -> io::Result<Vec<f64>> {
+    let array = value.get(param).and_then(Value::as_array)
+        .ok_or_else(|| invalid_data(&format!("Node '{}': missing '{}' array", name, param)))?;
+
+    array.iter()
+        .map(|v| v.as_f64().ok_or_else(||
+            invalid_data(&format!("Node '{}': '{}' must contain numbers", name, param))))
+        .collect()
+}
+
+fn decode_optional_vector(name: &str, value: &Value, param: &str, len: usize, default: f64)
+    -> io::Result<Vec<f64>> {
+    match value.get(param) {
+        None => Ok(vec![default; len]),
+        Some(_) => decode_vector(name, value, param),
+    }
+}
+
+fn decode_kernels(name: &str, value: &Value, len: usize) -> io::Result<Vec<SynapseKernel>> {
+    let array = match value.get("kernel") {
+        None => return Ok(vec![SynapseKernel::Exponential; len]),
+        Some(kernel) => kernel.as_array()
+            .ok_or_else(|| invalid_data(&format!("Node '{}': 'kernel' must be an array", name)))?,
+    };
+
+    array.iter()
+        .map(|kernel| match kernel.as_str() {
+            Some("exponential") => Ok(SynapseKernel::Exponential),
+            Some("alpha") => Ok(SynapseKernel::Alpha),
+            _ => Err(invalid_data(&format!("Node '{}': 'kernel' must contain \"exponential\" or \"alpha\"", name))),
+        })
+        .collect()
+}
+
+fn decode_matrix(name: &str, value: &Value, param: &str) -> io::Result<Vec<Vec<f64>>> {
+    let rows = value.get(param).and_then(Value::as_array)
+        .ok_or_else(|| invalid_data(&format!("Node '{}': missing '{}' matrix", name, param)))?;
+
+    rows.iter()
+        .map(|row| decode_vector(name, &json!({ param: row }), param))
+        .collect()
+}
+
+fn single_successor<'a>(graph: &'a NetworkGraph, source: &'a str) -> io::Result<&'a str> {
+    let successors: Vec<&str> = graph.successors(source).into_iter()
+        /* (skip the recurrent connections, i.e. the nodes looping back to the source) */
+        .filter(|next| graph.successors(next) != vec![source])
+        .collect();
+
+    match successors.as_slice() {
+        [next] => Ok(next),
+        [] => Err(invalid_data(&format!("Node '{}' is not connected to any other node", source))),
+        _ => Err(invalid_data(&format!("Node '{}' has more than one forward connection \
+                                        (only feed-forward chains of layers are supported)", source))),
+    }
+}
+
+/* (it marks *name* as visited, rejecting the cycles and the nodes reached by more than one forward connection) */
+fn visit<'a>(graph: &NetworkGraph, visited: &mut BTreeSet<&'a str>, name: &'a str) -> io::Result<()> {
+    if !visited.insert(name) {
+        return Err(invalid_data(&format!("Node '{}' is reached twice (only acyclic chains of layers are supported)",
+                                         name)));
+    }
+
+    let forward_predecessors = graph.predecessors(name).into_iter()
+        /* (skip the recurrent connections, i.e. the nodes looping back to *name*) */
+        .filter(|previous| !(graph.successors(name).contains(previous) && graph.successors(previous) == vec![name]))
+        .count();
+
+    if forward_predecessors > 1 {
+        return Err(invalid_data(&format!("Node '{}' has more than one incoming forward connection \
+                                          (only feed-forward chains of layers are supported)", name)));
+    }
+    Ok(())
+}
+
+fn check_matrix(name: &str, matrix: &[Vec<f64>], cols: usize) -> io::Result<()> {
+    if matrix.iter().any(|row| row.len() != cols) {
+        return Err(invalid_data(&format!("Node '{}': each row of the weight matrix must have {} columns",
+                                         name, cols)));
+    }
+    Ok(())
+}
+
+fn scale_rows(matrix: &[Vec<f64>], factors: &[f64]) -> Vec<Vec<f64>> {
+    matrix.iter().zip(factors.iter())
+        .map(|(row, factor)| row.iter().map(|w| w * factor).collect())
+        .collect()
+}
+
+/* (for the GraphNeuron implementations) it checks that each parameter (name, number of values) has a value per neuron */
+pub(crate) fn check_params(name: &str, node: &GraphNode, params: &[(&str, usize)], num_neurons: usize) -> io::Result<()> {
+    match params.iter().find(|(_, len)| *len != num_neurons) {
+        Some((param, _)) => Err(invalid_data(&format!("{} node '{}': '{}' must have {} values",
+                                                      node.node_type(), name, param, num_neurons))),
+        None => Ok(()),
+    }
+}
+
+/* (for the GraphNeuron implementations) error of a node which does not describe the expected model */
+pub(crate) fn unexpected_node(name: &str, expected: &str, node: &GraphNode) -> io::Error {
+    invalid_data(&format!("Node '{}': expected a {} node, found {}", name, expected, node.node_type()))
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
@@ -0,0 +1,6 @@
+/* * Network file formats * */
+
+pub mod binary;
+pub mod graph;
+pub mod snapshot;
+/* (every format works on DynSNN networks, whose structure is known only at run-time, or on their snapshots) */
@@ -0,0 +1,187 @@
+/* * Snapshot file format submodule * */
+
+/*
+    Little-endian binary format for the snapshots of the dynamic state of a network
+    (see NetworkSnapshot), independent of the neuron model:
+
+    offset  size    field
+    ------  ----    -----
+    0       4       magic number ("PSNS")
+    4       2       format version (u16)
+    6       2       reserved (0)
+    8       8       number of instants processed since the last reset (u64)
+    16      8       number of layers (u64)
+    24      ...     layers, one after the other:
+                        - number of neurons (u64)
+                        - last processed instant (u64, u64::MAX if none)
+                        - number of output spikes of the last instant (u64, 0 or the number of neurons)
+                        - number of values of the dynamic synapses state (u64, u64::MAX without dynamic synapses)
+                        - state of each neuron: number of values (u64), then the values (f64)
+                        - output spikes of the last instant (u8)
+                        - state of the dynamic synapses (f64)
+*/
+
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+use crate::snn::snapshot::{LayerSnapshot, NetworkSnapshot};
+
+pub const MAGIC: [u8; 4] = *b"PSNS";
+pub const VERSION: u16 = 1;
+
+const NONE: u64 = u64::MAX;
+
+/**
+    It writes the snapshot into *writer*
+*/
+pub fn write<W: Write>(snapshot: &NetworkSnapshot, writer: &mut W) -> io::Result<()> {
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&[0u8; 2])?;
+    writer.write_all(&snapshot.elapsed.to_le_bytes())?;
+    writer.write_all(&(snapshot.layers.len() as u64).to_le_bytes())?;
+
+    for layer in &snapshot.layers {
+        writer.write_all(&(layer.neurons.len() as u64).to_le_bytes())?;
+        writer.write_all(&layer.last_instant.unwrap_or(NONE).to_le_bytes())?;
+        writer.write_all(&(layer.prev_output_spikes.len() as u64).to_le_bytes())?;
+        writer.write_all(&layer.synapses.as_ref().map_or(NONE, |state| state.len() as u64).to_le_bytes())?;
+
+        for state in &layer.neurons {
+            writer.write_all(&(state.len() as u64).to_le_bytes())?;
+            write_values(writer, state)?;
+        }
+        writer.write_all(&layer.prev_output_spikes)?;
+        if let Some(state) = &layer.synapses {
+            write_values(writer, state)?;
+        }
+    }
+
+    writer.flush()
+}
+
+/**
+    It saves the snapshot into the file at *path* (see write())
+*/
+pub fn save<P: AsRef<Path>>(snapshot: &NetworkSnapshot, path: P) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write(snapshot, &mut writer)
+}
+
+/**
+    It reads a snapshot from *reader*
+*/
+pub fn read<R: Read>(reader: &mut R) -> io::Result<NetworkSnapshot> {
+    let mut bytes = Vec::<u8>::new();
+    reader.read_to_end(&mut bytes)?;
+    let mut cursor = Cursor { bytes: &bytes, offset: 0 };
+
+    if cursor.take(4)? != MAGIC {
+        return Err(invalid_data("The file is not a snapshot (wrong magic number)"));
+    }
+    let version = u16::from_le_bytes(cursor.take(2)?.try_into().unwrap());
+    if version != VERSION {
+        return Err(invalid_data(&format!("Unsupported snapshot version {}", version)));
+    }
+    cursor.take(2)?;
+
+    let elapsed = cursor.u64()?;
+    let layers_number = cursor.count(24)?;
+    let mut layers = Vec::<LayerSnapshot>::with_capacity(layers_number);
+
+    for _ in 0..layers_number {
+        let neurons_number = cursor.count(8)?;
+        let last_instant = cursor.u64()?;
+        let spikes_number = cursor.count(1)?;
+        let synapses_values = cursor.u64()?;
+
+        if spikes_number != 0 && spikes_number != neurons_number {
+            return Err(invalid_data("The output spikes must be one for each neuron"));
+        }
+
+        let mut neurons = Vec::<Vec<f64>>::with_capacity(neurons_number);
+        for _ in 0..neurons_number {
+            let values_number = cursor.count(8)?;
+            neurons.push(cursor.values(values_number)?);
+        }
+        let prev_output_spikes = cursor.take(spikes_number)?.to_vec();
+        let synapses = match synapses_values {
+            NONE => None,
+            _ => {
+                let values_number = usize::try_from(synapses_values).map_err(|_| truncated())?;
+                Some(cursor.values(values_number)?)
+            },
+        };
+
+        layers.push(LayerSnapshot {
+            neurons,
+            prev_output_spikes,
+            last_instant: if last_instant == NONE { None } else { Some(last_instant) },
+            synapses,
+        });
+    }
+
+    if cursor.offset != bytes.len() {
+        return Err(invalid_data("Unexpected data after the end of the snapshot"));
+    }
+
+    Ok(NetworkSnapshot { elapsed, layers })
+}
+
+/**
+    It loads the snapshot stored in the file at *path*
+*/
+pub fn load<P: AsRef<Path>>(path: P) -> io::Result<NetworkSnapshot> {
+    let mut file = File::open(path)?;
+    read(&mut file)
+}
+
+/* private functions */
+
+fn write_values<W: Write>(writer: &mut W, values: &[f64]) -> io::Result<()> {
+    for value in values {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+/* Object reading the fields of a snapshot one after the other, checking the end of the bytes */
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, size: usize) -> io::Result<&'a [u8]> {
+        let end = self.offset.checked_add(size).filter(|end| *end <= self.bytes.len()).ok_or_else(truncated)?;
+        let bytes = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /* (a number of items of at least *item_size* bytes each, which must fit in the remaining bytes) */
+    fn count(&mut self, item_size: usize) -> io::Result<usize> {
+        let count = self.u64()?;
+        match usize::try_from(count) {
+            Ok(count) if count.saturating_mul(item_size) <= self.bytes.len() - self.offset => Ok(count),
+            _ => Err(truncated()),
+        }
+    }
+
+    fn values(&mut self, count: usize) -> io::Result<Vec<f64>> {
+        let bytes = self.take(count.checked_mul(8).ok_or_else(truncated)?)?;
+        Ok(bytes.chunks_exact(8).map(|value| f64::from_le_bytes(value.try_into().unwrap())).collect())
+    }
+}
+
+fn truncated() -> io::Error {
+    invalid_data("The snapshot file is truncated")
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
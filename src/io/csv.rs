@@ -0,0 +1,95 @@
+/* * CSV submodule * */
+
+/*
+    Dense CSV format: one line for each neuron, one (comma-separated) value for each time instant.
+    Empty lines and lines starting with '#' are ignored.
+*/
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/**
+    It reads a spikes matrix (of 0/1) from a dense CSV
+    - All the rows must have the same number of values
+*/
+pub fn read_spikes<R: BufRead>(reader: R) -> io::Result<Vec<Vec<u8>>> {
+    let rows = read_rows(reader)?;
+
+    rows.into_iter().enumerate().map(|(n, row)| {
+        row.into_iter().enumerate().map(|(t, value)| match value.as_str() {
+            "0" => Ok(0u8),
+            "1" => Ok(1u8),
+            _ => Err(invalid_data(&format!("Input spike must be 0 or 1 for neuron {} in t={}", n, t))),
+        }).collect()
+    }).collect()
+}
+
+/**
+    It reads a matrix of analog values from a dense CSV
+*/
+pub fn read_analog<R: BufRead>(reader: R) -> io::Result<Vec<Vec<f64>>> {
+    let rows = read_rows(reader)?;
+
+    rows.into_iter().enumerate().map(|(r, row)| {
+        row.into_iter().enumerate().map(|(c, value)| value.parse::<f64>()
+            .map_err(|_| invalid_data(&format!("Invalid analog value '{}' at row {}, column {}", value, r, c)))
+        ).collect()
+    }).collect()
+}
+
+/**
+    It writes a spikes matrix as a dense CSV
+*/
+pub fn write_spikes<W: Write>(writer: &mut W, spikes: &[Vec<u8>]) -> io::Result<()> {
+    for neuron_spikes in spikes {
+        let line: Vec<String> = neuron_spikes.iter().map(|spike| spike.to_string()).collect();
+        writeln!(writer, "{}", line.join(","))?;
+    }
+    writer.flush()
+}
+
+/** It reads a spikes matrix from the dense CSV file at *path* */
+pub fn load_spikes<P: AsRef<Path>>(path: P) -> io::Result<Vec<Vec<u8>>> {
+    read_spikes(BufReader::new(File::open(path)?))
+}
+
+/** It reads a matrix of analog values from the dense CSV file at *path* */
+pub fn load_analog<P: AsRef<Path>>(path: P) -> io::Result<Vec<Vec<f64>>> {
+    read_analog(BufReader::new(File::open(path)?))
+}
+
+/** It writes a spikes matrix into the dense CSV file at *path* */
+pub fn save_spikes<P: AsRef<Path>>(path: P, spikes: &[Vec<u8>]) -> io::Result<()> {
+    write_spikes(&mut BufWriter::new(File::create(path)?), spikes)
+}
+
+/* private functions */
+
+fn read_rows<R: BufRead>(reader: R) -> io::Result<Vec<Vec<String>>> {
+    let mut rows = Vec::<Vec<String>>::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let row: Vec<String> = line.split(',').map(|value| value.trim().to_string()).collect();
+
+        if let Some(first_row) = rows.first() {
+            if first_row.len() != row.len() {
+                return Err(invalid_data("All the rows of the CSV must have the same number of values"));
+            }
+        }
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
@@ -0,0 +1,125 @@
+/* * Events submodule * */
+
+/*
+    Sparse event format: one (index, time) pair for each spike, i.e. the index of the neuron
+    that fires and the time instant in which it fires, one pair for each line
+    (separated by a comma or by whitespaces). Empty lines and lines starting with '#' are ignored.
+*/
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/**
+    It reads a list of (index, time) events
+*/
+pub fn read_events<R: BufRead>(reader: R) -> io::Result<Vec<(usize, u64)>> {
+    let mut events = Vec::<(usize, u64)>::new();
+
+    for (l, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|field| !field.is_empty())
+            .collect();
+
+        match fields.as_slice() {
+            [index, t] => match (index.parse::<usize>(), t.parse::<u64>()) {
+                (Ok(index), Ok(t)) => events.push((index, t)),
+                _ => return Err(invalid_data(&format!("Line {}: index and time must be non-negative integers", l + 1))),
+            },
+            _ => return Err(invalid_data(&format!("Line {}: each event must be an (index, time) pair", l + 1))),
+        }
+    }
+
+    Ok(events)
+}
+
+/**
+    It writes a list of (index, time) events
+*/
+pub fn write_events<W: Write>(writer: &mut W, events: &[(usize, u64)]) -> io::Result<()> {
+    for (index, t) in events {
+        writeln!(writer, "{},{}", index, t)?;
+    }
+    writer.flush()
+}
+
+/** Maximum number of entries (neurons x time instants) of a spikes matrix built from an events list */
+pub const MAX_SPIKES_SIZE: usize = 1 << 30;
+
+/**
+    It converts a list of (index, time) events into a spikes matrix
+    - dimension: number of neurons (rows of the matrix)
+    - duration: number of time instants (columns of the matrix); if None, it is inferred from the last event
+    - It returns an error if an event does not fit the matrix, or if the matrix exceeds MAX_SPIKES_SIZE entries
+*/
+pub fn to_spikes(events: &[(usize, u64)], dimension: usize, duration: Option<usize>) -> io::Result<Vec<Vec<u8>>> {
+    let duration = match duration {
+        Some(duration) => duration as u64,
+        None => events.iter().map(|(_, t)| t.saturating_add(1)).max().unwrap_or(0),
+    };
+
+    /* (the matrix is checked before its allocation, since its size comes from the events) */
+    if let Some((index, t)) = events.iter().find(|(index, t)| *index >= dimension || *t >= duration) {
+        return Err(invalid_data(&format!("The event ({}, {}) does not fit a {}x{} spikes matrix",
+                                         index, t, dimension, duration)));
+    }
+    check_spikes_size(dimension, duration)?;
+
+    let mut spikes = vec![vec![0u8; duration as usize]; dimension];
+
+    for (index, t) in events {
+        spikes[*index][*t as usize] = 1;
+    }
+
+    Ok(spikes)
+}
+
+/**
+    It converts a spikes matrix into a list of (index, time) events, sorted by time
+*/
+pub fn from_spikes(spikes: &[Vec<u8>]) -> Vec<(usize, u64)> {
+    let duration = spikes.iter().map(|neuron_spikes| neuron_spikes.len()).max().unwrap_or(0);
+    let mut events = Vec::<(usize, u64)>::new();
+
+    for t in 0..duration {
+        for (index, neuron_spikes) in spikes.iter().enumerate() {
+            if neuron_spikes.get(t).is_some_and(|spike| *spike != 0) {
+                events.push((index, t as u64));
+            }
+        }
+    }
+
+    events
+}
+
+/** It reads a list of (index, time) events from the file at *path* */
+pub fn load_events<P: AsRef<Path>>(path: P) -> io::Result<Vec<(usize, u64)>> {
+    read_events(BufReader::new(File::open(path)?))
+}
+
+/** It writes a list of (index, time) events into the file at *path* */
+pub fn save_events<P: AsRef<Path>>(path: P, events: &[(usize, u64)]) -> io::Result<()> {
+    write_events(&mut BufWriter::new(File::create(path)?), events)
+}
+
+/**
+    It checks that a spikes matrix of *dimension* x *duration* entries does not exceed MAX_SPIKES_SIZE
+*/
+pub(crate) fn check_spikes_size(dimension: usize, duration: u64) -> io::Result<()> {
+    match (dimension as u64).checked_mul(duration) {
+        Some(size) if size <= MAX_SPIKES_SIZE as u64 => Ok(()),
+        _ => Err(invalid_data(&format!("A {}x{} spikes matrix exceeds the maximum size of {} entries",
+                                       dimension, duration, MAX_SPIKES_SIZE))),
+    }
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
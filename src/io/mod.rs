@@ -0,0 +1,114 @@
+/* * Spikes I/O * */
+
+pub mod csv;
+pub mod events;
+pub mod npy;
+/* (all the readers/writers work on the spikes matrix accepted by DynSNN::process()) */
+
+use std::io;
+use std::path::Path;
+use crate::snn::SpikeEvent;
+
+/**
+    Supported formats of the spikes files
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpikesFormat {
+    Csv,        /* dense CSV, one row for each neuron */
+    Npy,        /* 2-D uint8/bool NumPy array, one row for each neuron */
+    Events,     /* sparse (index, time) events list */
+}
+
+impl SpikesFormat {
+    /**
+        It infers the format from the extension of the file
+        (.csv, .npy, otherwise the events list)
+    */
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some("csv") => SpikesFormat::Csv,
+            Some("npy") => SpikesFormat::Npy,
+            _ => SpikesFormat::Events,
+        }
+    }
+}
+
+/**
+    It reads a spikes matrix from the file at *path*
+    - dimension: number of neurons, needed by the events lists only (the other formats are dense)
+*/
+pub fn load_spikes<P: AsRef<Path>>(path: P, format: SpikesFormat, dimension: usize) -> io::Result<Vec<Vec<u8>>> {
+    match format {
+        SpikesFormat::Csv => csv::load_spikes(path),
+        SpikesFormat::Npy => npy::load_spikes(path),
+        SpikesFormat::Events => events::to_spikes(&events::load_events(path)?, dimension, None),
+    }
+}
+
+/**
+    It writes a spikes matrix into the file at *path*
+*/
+pub fn save_spikes<P: AsRef<Path>>(path: P, format: SpikesFormat, spikes: &[Vec<u8>]) -> io::Result<()> {
+    match format {
+        SpikesFormat::Csv => csv::save_spikes(path, spikes),
+        SpikesFormat::Npy => npy::save_spikes(path, spikes),
+        SpikesFormat::Events => events::save_events(path, &events::from_spikes(spikes)),
+    }
+}
+
+/**
+    It reads a Vec of SpikeEvents from the file at *path* (see load_spikes())
+*/
+pub fn load_spike_events<P: AsRef<Path>>(path: P, format: SpikesFormat, dimension: usize)
+    -> io::Result<Vec<SpikeEvent>> {
+    Ok(to_spike_events(&load_spikes(path, format, dimension)?))
+}
+
+/**
+    It writes a Vec of SpikeEvents into the file at *path*
+*/
+pub fn save_spike_events<P: AsRef<Path>>(path: P, format: SpikesFormat, spike_events: &[SpikeEvent])
+    -> io::Result<()> {
+    save_spikes(path, format, &from_spike_events(spike_events, None)?)
+}
+
+/**
+    It converts a spikes matrix into a Vec of SpikeEvents, one for each instant in which
+    at least one neuron fires (as the ones flowing through the network)
+*/
+pub fn to_spike_events(spikes: &[Vec<u8>]) -> Vec<SpikeEvent> {
+    let duration = spikes.first().map_or(0, |neuron_spikes| neuron_spikes.len());
+
+    (0..duration)
+        .map(|t| SpikeEvent::new(t as u64, spikes.iter().map(|neuron_spikes| neuron_spikes[t]).collect()))
+        .filter(|spike_event| spike_event.get_spikes().iter().any(|spike| *spike != 0))
+        .collect()
+}
+
+/**
+    It converts a Vec of SpikeEvents into a spikes matrix
+    - duration: number of time instants; if None, it is inferred from the last SpikeEvent
+    - All the SpikeEvents must have the same number of spikes
+*/
+pub fn from_spike_events(spike_events: &[SpikeEvent], duration: Option<usize>) -> io::Result<Vec<Vec<u8>>> {
+    let dimension = spike_events.first().map_or(0, |spike_event| spike_event.get_spikes().len());
+    let duration = duration.unwrap_or_else(||
+        spike_events.iter().map(|spike_event| spike_event.get_ts() as usize + 1).max().unwrap_or(0));
+
+    let mut spikes = vec![vec![0u8; duration]; dimension];
+
+    for spike_event in spike_events {
+        let t = spike_event.get_ts() as usize;
+
+        if spike_event.get_spikes().len() != dimension || t >= duration {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      format!("The SpikeEvent t={} does not fit a {}x{} spikes matrix",
+                                              t, dimension, duration)));
+        }
+        for (n, spike) in spike_event.get_spikes().iter().enumerate() {
+            spikes[n][t] = *spike;
+        }
+    }
+
+    Ok(spikes)
+}
@@ -0,0 +1,298 @@
+/* * NPY submodule * */
+
+/*
+    NumPy .npy format (version 1.0, 2.0 and 3.0): a magic string, a header describing the array
+    (a Python dict literal with 'descr', 'fortran_order' and 'shape') and the raw data.
+    - Spikes matrices are 2-D arrays of uint8 (or bool) values, shape (neurons, instants)
+    - Analog matrices are 2-D arrays of floats or integers (signed or unsigned, of 1, 2, 4 or 8 bytes),
+      e.g. one row for each sample; a 1-D array is read as a single row
+*/
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/* Scalar types supported by the reader */
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Descr {
+    U8,
+    Bool,
+    I8,
+    Int { size: usize, signed: bool, little_endian: bool },   /* (integers of 2, 4 or 8 bytes) */
+    F32 { little_endian: bool },
+    F64 { little_endian: bool },
+}
+
+impl Descr {
+    fn parse(descr: &str) -> io::Result<Self> {
+        match descr {
+            "|u1" | "<u1" | ">u1" | "u1" => Ok(Descr::U8),
+            "|b1" | "b1" | "?" => Ok(Descr::Bool),
+            "|i1" | "<i1" | ">i1" | "i1" => Ok(Descr::I8),
+            "<f4" => Ok(Descr::F32 { little_endian: true }),
+            ">f4" => Ok(Descr::F32 { little_endian: false }),
+            "<f8" => Ok(Descr::F64 { little_endian: true }),
+            ">f8" => Ok(Descr::F64 { little_endian: false }),
+            other => {
+                let unsupported = || invalid_data(&format!("Unsupported NumPy dtype '{}'", other));
+                let (little_endian, kind) = match (other.strip_prefix('<'), other.strip_prefix('>')) {
+                    (Some(kind), _) => (true, kind),
+                    (_, Some(kind)) => (false, kind),
+                    _ => return Err(unsupported()),
+                };
+                let (signed, size) = match kind {
+                    "i2" => (true, 2),
+                    "i4" => (true, 4),
+                    "i8" => (true, 8),
+                    "u2" => (false, 2),
+                    "u4" => (false, 4),
+                    "u8" => (false, 8),
+                    _ => return Err(unsupported()),
+                };
+                Ok(Descr::Int { size, signed, little_endian })
+            },
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Descr::U8 | Descr::Bool | Descr::I8 => 1,
+            Descr::Int { size, .. } => *size,
+            Descr::F32 { .. } => 4,
+            Descr::F64 { .. } => 8,
+        }
+    }
+
+    fn value(&self, bytes: &[u8]) -> f64 {
+        match *self {
+            Descr::U8 | Descr::Bool => bytes[0] as f64,
+            Descr::I8 => bytes[0] as i8 as f64,
+            Descr::Int { size, signed, little_endian } => {
+                let fold = |raw: u64, byte: &u8| (raw << 8) | *byte as u64;
+                let raw = if little_endian { bytes.iter().rev().fold(0, fold) } else { bytes.iter().fold(0, fold) };
+                let unused_bits = 64 - 8 * size as u32;
+                if signed { ((raw << unused_bits) as i64 >> unused_bits) as f64 } else { raw as f64 }
+            },
+            Descr::F32 { little_endian: true } => f32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            Descr::F32 { little_endian: false } => f32::from_be_bytes(bytes.try_into().unwrap()) as f64,
+            Descr::F64 { little_endian: true } => f64::from_le_bytes(bytes.try_into().unwrap()),
+            Descr::F64 { little_endian: false } => f64::from_be_bytes(bytes.try_into().unwrap()),
+        }
+    }
+}
+
+/**
+    It reads a spikes matrix from a 2-D uint8/bool .npy array
+*/
+pub fn read_spikes<R: Read>(reader: &mut R) -> io::Result<Vec<Vec<u8>>> {
+    let (descr, rows, cols, data) = read_array(reader)?;
+
+    if !matches!(descr, Descr::U8 | Descr::Bool) {
+        return Err(invalid_data("Spikes arrays must have uint8 or bool dtype"));
+    }
+
+    (0..rows).map(|n| (0..cols).map(|t| match data[n * cols + t] {
+        spike @ (0 | 1) => Ok(spike),
+        _ => Err(invalid_data(&format!("Input spike must be 0 or 1 for neuron {} in t={}", n, t))),
+    }).collect()).collect()
+}
+
+/**
+    It reads a matrix of analog values from a (1-D or 2-D) numeric .npy array
+*/
+pub fn read_analog<R: Read>(reader: &mut R) -> io::Result<Vec<Vec<f64>>> {
+    let (descr, rows, cols, data) = read_array(reader)?;
+    let size = descr.size();
+
+    Ok((0..rows).map(|r| (0..cols).map(|c| {
+        let start = (r * cols + c) * size;
+        descr.value(&data[start..start + size])
+    }).collect()).collect())
+}
+
+/**
+    It writes a spikes matrix as a 2-D uint8 .npy array
+    - All the rows must have the same length
+*/
+pub fn write_spikes<W: Write>(writer: &mut W, spikes: &[Vec<u8>]) -> io::Result<()> {
+    let cols = check_rows(spikes)?;
+
+    write_header(writer, "|u1", spikes.len(), cols)?;
+    for neuron_spikes in spikes {
+        writer.write_all(neuron_spikes)?;
+    }
+    writer.flush()
+}
+
+/**
+    It writes a matrix of analog values as a 2-D float64 .npy array
+    - All the rows must have the same length
+*/
+pub fn write_analog<W: Write>(writer: &mut W, values: &[Vec<f64>]) -> io::Result<()> {
+    let cols = check_rows(values)?;
+
+    write_header(writer, "<f8", values.len(), cols)?;
+    for value in values.iter().flatten() {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    writer.flush()
+}
+
+/** It reads a spikes matrix from the .npy file at *path* */
+pub fn load_spikes<P: AsRef<Path>>(path: P) -> io::Result<Vec<Vec<u8>>> {
+    read_spikes(&mut BufReader::new(File::open(path)?))
+}
+
+/** It reads a matrix of analog values from the .npy file at *path* */
+pub fn load_analog<P: AsRef<Path>>(path: P) -> io::Result<Vec<Vec<f64>>> {
+    read_analog(&mut BufReader::new(File::open(path)?))
+}
+
+/** It writes a spikes matrix into the .npy file at *path* */
+pub fn save_spikes<P: AsRef<Path>>(path: P, spikes: &[Vec<u8>]) -> io::Result<()> {
+    write_spikes(&mut BufWriter::new(File::create(path)?), spikes)
+}
+
+/** It writes a matrix of analog values into the .npy file at *path* */
+pub fn save_analog<P: AsRef<Path>>(path: P, values: &[Vec<f64>]) -> io::Result<()> {
+    write_analog(&mut BufWriter::new(File::create(path)?), values)
+}
+
+/* private functions */
+
+/**
+    It reads a whole .npy array, returning its dtype, its (rows, cols) dimensions
+    and its data in C (row-major) order
+*/
+fn read_array<R: Read>(reader: &mut R) -> io::Result<(Descr, usize, usize, Vec<u8>)> {
+    let mut preamble = [0u8; 8];
+    reader.read_exact(&mut preamble)?;
+
+    if &preamble[0..6] != MAGIC {
+        return Err(invalid_data("Wrong magic string: the file is not a .npy file"));
+    }
+
+    let header_len = match preamble[6] {
+        1 => {
+            let mut len = [0u8; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        },
+        2 | 3 => {
+            let mut len = [0u8; 4];
+            reader.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        },
+        version => return Err(invalid_data(&format!("Unsupported .npy version {}", version))),
+    };
+
+    let header = read_bytes(reader, header_len, "header")?;
+    let header = String::from_utf8_lossy(&header);
+
+    let descr = Descr::parse(header_value(&header, "descr")?.trim_matches(|c| c == '\'' || c == '"'))?;
+    let fortran_order = match header_value(&header, "fortran_order")?.as_str() {
+        "True" => true,
+        "False" => false,
+        other => return Err(invalid_data(&format!("Invalid fortran_order '{}'", other))),
+    };
+
+    let shape: Vec<usize> = header_value(&header, "shape")?
+        .trim_matches(|c| c == '(' || c == ')')
+        .split(',')
+        .map(str::trim)
+        .filter(|dim| !dim.is_empty())
+        .map(|dim| dim.parse::<usize>().map_err(|_| invalid_data("Invalid array shape")))
+        .collect::<io::Result<_>>()?;
+
+    let (rows, cols) = match shape.as_slice() {
+        [cols] => (1, *cols),
+        [rows, cols] => (*rows, *cols),
+        _ => return Err(invalid_data("Only 1-D and 2-D arrays are supported")),
+    };
+
+    let size = descr.size();
+    let data_len = rows.checked_mul(cols).and_then(|n| n.checked_mul(size))
+        .ok_or_else(|| invalid_data("Invalid array shape"))?;
+
+    let mut data = read_bytes(reader, data_len, "data")?;
+
+    /* bring a Fortran (column-major) array into C order */
+    if fortran_order && rows > 1 {
+        let mut c_data = vec![0u8; data_len];
+        for r in 0..rows {
+            for c in 0..cols {
+                let (src, dst) = ((c * rows + r) * size, (r * cols + c) * size);
+                c_data[dst..dst + size].copy_from_slice(&data[src..src + size]);
+            }
+        }
+        data = c_data;
+    }
+
+    Ok((descr, rows, cols, data))
+}
+
+/**
+    It reads *len* bytes in bounded chunks, so that the lengths declared by a corrupted header
+    cannot allocate more than the file actually contains
+*/
+fn read_bytes<R: Read>(reader: &mut R, len: usize, what: &str) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::<u8>::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+
+    if bytes.len() < len {
+        return Err(invalid_data(&format!("The file ends after {} of the {} bytes of the array {}",
+                                         bytes.len(), len, what)));
+    }
+
+    Ok(bytes)
+}
+
+/**
+    It extracts the (raw) value of a key from the header dict literal,
+    e.g. "'<f8'" for 'descr' or "(3, 4)" for 'shape'
+*/
+fn header_value(header: &str, key: &str) -> io::Result<String> {
+    let missing = || invalid_data(&format!("The .npy header has no '{}' key", key));
+
+    let key_start = header.find(&format!("'{}'", key)).ok_or_else(missing)?;
+    let rest = &header[key_start + key.len() + 2..];
+    let rest = rest.trim_start().strip_prefix(':').ok_or_else(missing)?.trim_start();
+
+    let value = if rest.starts_with('(') {
+        &rest[..rest.find(')').ok_or_else(missing)? + 1]
+    } else {
+        rest.split([',', '}']).next().unwrap_or("")
+    };
+
+    Ok(value.trim().to_string())
+}
+
+fn write_header<W: Write>(writer: &mut W, descr: &str, rows: usize, cols: usize) -> io::Result<()> {
+    let mut header = format!("{{'descr': '{}', 'fortran_order': False, 'shape': ({}, {}), }}", descr, rows, cols);
+
+    /* the whole preamble (magic + version + length + header) must be aligned to 64 bytes */
+    let preamble_len = MAGIC.len() + 2 + 2;
+    let padding = (64 - (preamble_len + header.len() + 1) % 64) % 64;
+    header.push_str(&" ".repeat(padding));
+    header.push('\n');
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[1u8, 0u8])?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())
+}
+
+fn check_rows<T>(matrix: &[Vec<T>]) -> io::Result<usize> {
+    let cols = matrix.first().map_or(0, |row| row.len());
+
+    if matrix.iter().any(|row| row.len() != cols) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "All the rows must have the same length"));
+    }
+    Ok(cols)
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
@@ -1,6 +1,25 @@
 pub use self::snn::builders;
+pub use self::snn::connectivity;
+pub use self::snn::layer;
 pub use self::snn::neuron;
+pub use self::snn::readout;
+pub use self::snn::snapshot;
+pub use self::snn::stream;
+pub use self::snn::synapses;
 pub use self::snn::SpikeEvent;
+pub use self::snn::DynSNN;
+pub use self::snn::SpikingNetwork;
+pub use self::snn::weights;
+pub use self::snn::wta;
 
+pub mod coding;
+pub mod compression;
+pub mod datasets;
+pub mod evaluation;
+pub mod fault;
+pub mod format;
+pub mod io;
 pub mod models;
+pub mod reservoir;
+pub mod utils;
     mod snn;
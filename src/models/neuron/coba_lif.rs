@@ -0,0 +1,223 @@
+use crate::format::binary::SerializableNeuron;
+use crate::snn::neuron::{self, Membrane, Neuron, SynapticInput};
+
+/* * COBA LIF submodule * */
+
+/**
+    Parameters of a population of synapses (excitatory or inhibitory) of the COBA model
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Conductance {
+    pub e_rev: f64,     /* reversal potential */
+    pub tau:   f64,     /* time constant of the conductance */
+}
+
+impl Conductance {
+    pub fn new(e_rev: f64, tau: f64) -> Self {
+        Self { e_rev, tau }
+    }
+}
+
+/**
+    Object representing a Neuron in the COBA (conductance-based) LIF model: the input spikes
+    increase an excitatory and an inhibitory conductance (relative to the leak one), which decay
+    exponentially, and the synaptic current depends on the distance of the membrane potential from
+    the reversal potentials. In each instant:
+        g_exc = a_exc * g_exc + excitatory input   (same for g_inh, with the inhibitory input magnitude)
+        tau_mem * dv/dt = (v_rest - v_mem) + g_exc * (e_exc - v_mem) + g_inh * (e_inh - v_mem)
+    where the membrane equation is integrated exactly over the instant (the conductances are constant in it).
+    - The membrane potential never crosses the reversal potentials, and an inhibitory reversal potential
+      equal to v_rest gives a pure shunting inhibition (it does not move the resting neuron, but divides
+      the effect of the excitation)
+    - The inputs are the positive and the negative weighted sums (see SynapticInput); if the neuron is
+      invoked by compute_v_mem(), they are the extra and the intra weighted sums
+    - While the conductances are not negligible the neuron is active (see Neuron::is_active()), so it
+      can fire also in the instants without input spikes
+*/
+#[derive(Debug, Clone)]
+pub struct CobaLifNeuron {
+    /* const fields */
+    v_th:       f64,            /* threshold potential */
+    v_rest:     f64,            /* resting potential */
+    v_reset:    f64,            /* reset potential */
+    tau_mem:    f64,            /* membrane time constant */
+    excitatory: Conductance,
+    inhibitory: Conductance,
+    dt:         f64,            /* time interval between two consecutive instants */
+    /* mutable fields */
+    v_mem:      f64,            /* membrane potential */
+    g_exc:      f64,            /* excitatory conductance */
+    g_inh:      f64,            /* inhibitory conductance */
+    ts:         Option<u64>,    /* last instant in which the neuron has been updated */
+}
+
+impl CobaLifNeuron {
+    pub fn new(v_th: f64, v_rest: f64, v_reset: f64, tau_mem: f64, excitatory: Conductance,
+               inhibitory: Conductance, dt: f64) -> Self {
+        Self {
+            v_th,
+            v_rest,
+            v_reset,
+            tau_mem,
+            excitatory,
+            inhibitory,
+            dt,
+            v_mem: v_rest,
+            g_exc: 0.0,
+            g_inh: 0.0,
+            ts: None,
+        }
+    }
+
+    /* Getters of the neuron object parameters */
+    pub fn get_v_th(&self) -> f64 {
+        self.v_th
+    }
+
+    pub fn get_v_rest(&self) -> f64 {
+        self.v_rest
+    }
+
+    pub fn get_v_reset(&self) -> f64 {
+        self.v_reset
+    }
+
+    pub fn get_tau_mem(&self) -> f64 {
+        self.tau_mem
+    }
+
+    pub fn get_excitatory(&self) -> Conductance {
+        self.excitatory
+    }
+
+    pub fn get_inhibitory(&self) -> Conductance {
+        self.inhibitory
+    }
+
+    pub fn get_dt(&self) -> f64 { self.dt }
+
+    pub fn get_v_mem(&self) -> f64 {
+        self.v_mem
+    }
+
+    pub fn get_g_exc(&self) -> f64 {
+        self.g_exc
+    }
+
+    pub fn get_g_inh(&self) -> f64 {
+        self.g_inh
+    }
+}
+
+impl CobaLifNeuron {
+    /* It advances the neuron by one instant, in which the conductances receive the given increments */
+    fn step(&mut self, excitatory_input: f64, inhibitory_input: f64) {
+        let decay = |tau: f64| if tau > 0.0 { (-self.dt / tau).exp() } else { 0.0 };
+
+        self.g_exc = decay(self.excitatory.tau) * self.g_exc + excitatory_input;
+        self.g_inh = decay(self.inhibitory.tau) * self.g_inh + inhibitory_input;
+
+        /* (the potential relaxes towards v_inf, with the effective time constant tau_mem / g_total) */
+        let g_total = 1.0 + self.g_exc + self.g_inh;
+        let v_inf = (self.v_rest + self.g_exc * self.excitatory.e_rev + self.g_inh * self.inhibitory.e_rev) / g_total;
+        self.v_mem = v_inf + (self.v_mem - v_inf) * (-g_total * self.dt / self.tau_mem).exp();
+    }
+
+    /* It returns true if the conductances are negligible (so that only the membrane decays) */
+    fn is_quiet(&self) -> bool {
+        self.g_exc.abs() < 1e-12 && self.g_inh.abs() < 1e-12
+    }
+
+    fn update(&mut self, t: u64, excitatory_input: f64, inhibitory_input: f64) -> u8 {
+        self.advance(t, excitatory_input, inhibitory_input);
+
+        if self.v_mem > self.v_th {
+            /* reset membrane potential (the conductances keep decaying) */
+            self.v_mem = self.v_reset;
+            1   /* fire */
+        } else {
+            0   /* not fire */
+        }
+    }
+
+    /* It brings the neuron to the instant t, in which it receives the given inputs (without firing) */
+    fn advance(&mut self, t: u64, excitatory_input: f64, inhibitory_input: f64) {
+        /* instants without input spikes since the last update */
+        let mut silent_instants = self.ts.map_or(0, |ts| t.saturating_sub(ts + 1));
+
+        while silent_instants > 0 && !self.is_quiet() {
+            self.step(0.0, 0.0);
+            silent_instants -= 1;
+        }
+        if silent_instants > 0 {
+            /* (closed form of the remaining decay of the membrane) */
+            let exponent = -(silent_instants as f64) * self.dt / self.tau_mem;
+            self.v_mem = self.v_rest + (self.v_mem - self.v_rest) * exponent.exp();
+            self.g_exc = 0.0;
+            self.g_inh = 0.0;
+        }
+
+        self.step(excitatory_input, inhibitory_input);
+        self.ts = Some(t);
+    }
+}
+
+impl Neuron for CobaLifNeuron {
+    /* (the extra weighted sum drives the excitatory conductance, the intra one the inhibitory conductance) */
+    fn compute_v_mem(&mut self, t: u64, extra_weighted_sum: f64, intra_weighted_sum: f64) -> u8 {
+        self.update(t, extra_weighted_sum, intra_weighted_sum.abs())
+    }
+
+    fn compute_v_mem_with_input(&mut self, t: u64, input: &SynapticInput) -> u8 {
+        self.update(t, input.excitatory, input.inhibitory.abs())
+    }
+
+    fn integrate(&mut self, t: u64, input: &SynapticInput) -> Option<Membrane> {
+        self.advance(t, input.excitatory, input.inhibitory.abs());
+        Some(Membrane { v_mem: self.v_mem, v_th: self.v_th, v_reset: self.v_reset })
+    }
+
+    fn set_v_mem(&mut self, v_mem: f64) {
+        self.v_mem = v_mem;
+    }
+
+    fn is_active(&self) -> bool {
+        !self.is_quiet()
+    }
+
+    /* (membrane potential, conductances and last update instant, -1 if none) */
+    fn get_state(&self) -> Option<Vec<f64>> {
+        Some(vec![self.v_mem, self.g_exc, self.g_inh, self.ts.map_or(-1.0, |ts| ts as f64)])
+    }
+
+    fn set_state(&mut self, state: &[f64]) {
+        neuron::check_state(state, 4);
+        self.v_mem = state[0];
+        self.g_exc = state[1];
+        self.g_inh = state[2];
+        self.ts = if state[3] < 0.0 { None } else { Some(state[3] as u64) };
+    }
+
+    fn initialize(&mut self) {
+        self.v_mem = self.v_rest;
+        self.g_exc = 0.0;
+        self.g_inh = 0.0;
+        self.ts = None;
+    }
+}
+
+impl SerializableNeuron for CobaLifNeuron {
+    const MODEL_ID: u32 = 3;
+    const PARAMS_NUMBER: usize = 9;
+
+    /* (only the const fields are stored: the mutable ones are initialized when the network runs) */
+    fn to_params(&self) -> Vec<f64> {
+        vec![self.v_th, self.v_rest, self.v_reset, self.tau_mem, self.excitatory.e_rev, self.excitatory.tau,
+             self.inhibitory.e_rev, self.inhibitory.tau, self.dt]
+    }
+
+    fn from_params(params: &[f64]) -> Self {
+        Self::new(params[0], params[1], params[2], params[3], Conductance::new(params[4], params[5]),
+                  Conductance::new(params[6], params[7]), params[8])
+    }
+}
@@ -0,0 +1,290 @@
+use crate::format::binary::SerializableNeuron;
+use crate::format::graph::{self, GraphNeuron, GraphNode};
+use std::io;
+use crate::snn::neuron::{self, Membrane, Neuron, SynapticInput};
+
+/* * CUBA LIF submodule * */
+
+/**
+    Kernel of the synaptic currents, i.e. their response to a single input spike of weight w
+    (k instants after the spike, where a = exp(-dt / tau_syn)):
+    - Exponential: w * a^k (instantaneous rise, exponential decay)
+    - Alpha: w * (1 - a) * (k + 1) * a^k (delayed peak, with the same total charge of the exponential kernel)
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SynapseKernel {
+    Exponential,
+    Alpha,
+}
+
+/* State of the synaptic current of a population of inputs (excitatory or inhibitory) */
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Synapse {
+    current: f64,
+    rise: f64,      /* (auxiliary variable of the alpha kernel) */
+}
+
+/**
+    Object representing a Neuron in the CUBA (current-based) LIF model: the input spikes
+    do not change the membrane potential directly, but drive an excitatory and an inhibitory
+    synaptic current (with their own time constants), which decay between the events and are
+    integrated into the membrane. In each instant:
+        i_exc = a_exc * i_exc + excitatory input  (same for i_inh, and for the rise of the alpha kernel)
+        v_mem = v_rest + b * (v_mem - v_rest) + i_exc + i_inh,   where b = exp(-dt / tau_mem)
+    - The excitatory and the inhibitory inputs are the positive and the negative weighted sums
+      (see SynapticInput); if the neuron is invoked by compute_v_mem(), they are the extra and the
+      intra weighted sums
+    - While the synaptic currents are not negligible the neuron is active (see Neuron::is_active()),
+      so the layer processes it also in the instants without input spikes, in which it can fire
+      because of the residual currents (e.g. at the delayed peak of the alpha kernel)
+*/
+#[derive(Debug, Clone)]
+pub struct CubaLifNeuron {
+    /* const fields */
+    v_th:      f64,     /* threshold potential */
+    v_rest:    f64,     /* resting potential */
+    v_reset:   f64,     /* reset potential */
+    tau_mem:   f64,     /* membrane time constant */
+    tau_exc:   f64,     /* time constant of the excitatory synaptic current */
+    tau_inh:   f64,     /* time constant of the inhibitory synaptic current */
+    kernel:    SynapseKernel,
+    dt:        f64,     /* time interval between two consecutive instants */
+    /* mutable fields */
+    v_mem:     f64,     /* membrane potential */
+    excitatory: Synapse,
+    inhibitory: Synapse,
+    ts:        Option<u64>,  /* last instant in which the neuron has been updated */
+}
+
+impl CubaLifNeuron {
+    /** (the synapses have an exponential kernel, see with_kernel()) */
+    pub fn new(v_th: f64, v_rest: f64, v_reset: f64, tau_mem: f64, tau_exc: f64, tau_inh: f64, dt: f64) -> Self {
+        Self {
+            v_th,
+            v_rest,
+            v_reset,
+            tau_mem,
+            tau_exc,
+            tau_inh,
+            kernel: SynapseKernel::Exponential,
+            dt,
+            v_mem: v_rest,
+            excitatory: Synapse::default(),
+            inhibitory: Synapse::default(),
+            ts: None,
+        }
+    }
+
+    pub fn with_kernel(mut self, kernel: SynapseKernel) -> Self {
+        self.kernel = kernel;
+        self
+    }
+
+    /* Getters of the neuron object parameters */
+    pub fn get_v_th(&self) -> f64 {
+        self.v_th
+    }
+
+    pub fn get_v_rest(&self) -> f64 {
+        self.v_rest
+    }
+
+    pub fn get_v_reset(&self) -> f64 {
+        self.v_reset
+    }
+
+    pub fn get_tau_mem(&self) -> f64 {
+        self.tau_mem
+    }
+
+    pub fn get_tau_exc(&self) -> f64 {
+        self.tau_exc
+    }
+
+    pub fn get_tau_inh(&self) -> f64 {
+        self.tau_inh
+    }
+
+    pub fn get_kernel(&self) -> SynapseKernel {
+        self.kernel
+    }
+
+    pub fn get_dt(&self) -> f64 { self.dt }
+
+    pub fn get_v_mem(&self) -> f64 {
+        self.v_mem
+    }
+
+    /** Excitatory synaptic current */
+    pub fn get_i_exc(&self) -> f64 {
+        self.excitatory.current
+    }
+
+    /** Inhibitory synaptic current */
+    pub fn get_i_inh(&self) -> f64 {
+        self.inhibitory.current
+    }
+}
+
+impl CubaLifNeuron {
+    /* It advances the neuron by one instant, in which the synapses receive the given inputs */
+    fn step(&mut self, excitatory_input: f64, inhibitory_input: f64) {
+        let decay = |tau: f64| if tau > 0.0 { (-self.dt / tau).exp() } else { 0.0 };
+        let kernel = self.kernel;
+
+        let advance = |synapse: &mut Synapse, a: f64, input: f64| match kernel {
+            SynapseKernel::Exponential => synapse.current = a * synapse.current + input,
+            SynapseKernel::Alpha => {
+                synapse.rise = a * synapse.rise + (1.0 - a) * input;
+                synapse.current = a * synapse.current + synapse.rise;
+            },
+        };
+        advance(&mut self.excitatory, decay(self.tau_exc), excitatory_input);
+        advance(&mut self.inhibitory, decay(self.tau_inh), inhibitory_input);
+
+        self.v_mem = self.v_rest + decay(self.tau_mem) * (self.v_mem - self.v_rest)
+            + self.excitatory.current + self.inhibitory.current;
+    }
+
+    /* It returns true if the synaptic currents are negligible (so that only the membrane decays) */
+    fn is_quiet(&self) -> bool {
+        [self.excitatory, self.inhibitory].iter()
+            .all(|synapse| synapse.current.abs() < 1e-12 && synapse.rise.abs() < 1e-12)
+    }
+
+    fn update(&mut self, t: u64, excitatory_input: f64, inhibitory_input: f64) -> u8 {
+        self.advance(t, excitatory_input, inhibitory_input);
+
+        if self.v_mem > self.v_th {
+            /* reset membrane potential (the synaptic currents keep flowing) */
+            self.v_mem = self.v_reset;
+            1   /* fire */
+        } else {
+            0   /* not fire */
+        }
+    }
+
+    /* It brings the neuron to the instant t, in which it receives the given inputs (without firing) */
+    fn advance(&mut self, t: u64, excitatory_input: f64, inhibitory_input: f64) {
+        /* instants without input spikes since the last update */
+        let mut silent_instants = self.ts.map_or(0, |ts| t.saturating_sub(ts + 1));
+
+        while silent_instants > 0 && !self.is_quiet() {
+            self.step(0.0, 0.0);
+            silent_instants -= 1;
+        }
+        if silent_instants > 0 {
+            /* (closed form of the remaining decay of the membrane) */
+            let exponent = -(silent_instants as f64) * self.dt / self.tau_mem;
+            self.v_mem = self.v_rest + (self.v_mem - self.v_rest) * exponent.exp();
+            self.excitatory = Synapse::default();
+            self.inhibitory = Synapse::default();
+        }
+
+        self.step(excitatory_input, inhibitory_input);
+        self.ts = Some(t);
+    }
+}
+
+impl Neuron for CubaLifNeuron {
+    /* (the extra weighted sum drives the excitatory current, the intra one the inhibitory current) */
+    fn compute_v_mem(&mut self, t: u64, extra_weighted_sum: f64, intra_weighted_sum: f64) -> u8 {
+        self.update(t, extra_weighted_sum, intra_weighted_sum)
+    }
+
+    fn compute_v_mem_with_input(&mut self, t: u64, input: &SynapticInput) -> u8 {
+        self.update(t, input.excitatory, input.inhibitory)
+    }
+
+    fn integrate(&mut self, t: u64, input: &SynapticInput) -> Option<Membrane> {
+        self.advance(t, input.excitatory, input.inhibitory);
+        Some(Membrane { v_mem: self.v_mem, v_th: self.v_th, v_reset: self.v_reset })
+    }
+
+    fn set_v_mem(&mut self, v_mem: f64) {
+        self.v_mem = v_mem;
+    }
+
+    fn is_active(&self) -> bool {
+        !self.is_quiet()
+    }
+
+    /* (membrane potential, synaptic currents with their rise, and last update instant, -1 if none) */
+    fn get_state(&self) -> Option<Vec<f64>> {
+        Some(vec![self.v_mem, self.excitatory.current, self.excitatory.rise, self.inhibitory.current,
+                  self.inhibitory.rise, self.ts.map_or(-1.0, |ts| ts as f64)])
+    }
+
+    fn set_state(&mut self, state: &[f64]) {
+        neuron::check_state(state, 6);
+        self.v_mem = state[0];
+        self.excitatory = Synapse { current: state[1], rise: state[2] };
+        self.inhibitory = Synapse { current: state[3], rise: state[4] };
+        self.ts = if state[5] < 0.0 { None } else { Some(state[5] as u64) };
+    }
+
+    fn initialize(&mut self) {
+        self.v_mem = self.v_rest;
+        self.excitatory = Synapse::default();
+        self.inhibitory = Synapse::default();
+        self.ts = None;
+    }
+}
+
+impl GraphNeuron for CubaLifNeuron {
+    /*
+        (CubaLIF node: tau_syn and tau_syn_inh are the time constants of the excitatory and of the inhibitory
+        currents, while r and w_in are 1, since the weighted sums are directly added to the synaptic currents)
+    */
+    fn to_node(neurons: &[Self]) -> GraphNode {
+        GraphNode::CubaLif {
+            tau_syn: neurons.iter().map(|n| n.tau_exc).collect(),
+            tau_syn_inh: neurons.iter().map(|n| n.tau_inh).collect(),
+            tau_mem: neurons.iter().map(|n| n.tau_mem).collect(),
+            r: vec![1.0; neurons.len()],
+            v_leak: neurons.iter().map(|n| n.v_rest).collect(),
+            v_threshold: neurons.iter().map(|n| n.v_th).collect(),
+            v_reset: neurons.iter().map(|n| n.v_reset).collect(),
+            w_in: vec![1.0; neurons.len()],
+            kernel: neurons.iter().map(|n| n.kernel).collect()
+        }
+    }
+
+    /* (both r and w_in are folded into the incoming weights) */
+    fn from_node(name: &str, node: &GraphNode, num_neurons: usize, dt: f64) -> io::Result<(Vec<Self>, Vec<f64>)> {
+        match node {
+            GraphNode::CubaLif { tau_syn, tau_syn_inh, tau_mem, r, v_leak, v_threshold, v_reset, w_in, kernel } => {
+                graph::check_params(name, node, &[("tau_syn", tau_syn.len()), ("tau_syn_inh", tau_syn_inh.len()),
+                                                  ("tau_mem", tau_mem.len()), ("r", r.len()), ("v_leak", v_leak.len()),
+                                                  ("v_threshold", v_threshold.len()), ("v_reset", v_reset.len()),
+                                                  ("w_in", w_in.len()), ("kernel", kernel.len())],
+                                    num_neurons)?;
+                let neurons = (0..num_neurons)
+                    .map(|n| Self::new(v_threshold[n], v_leak[n], v_reset[n], tau_mem[n], tau_syn[n], tau_syn_inh[n], dt)
+                        .with_kernel(kernel[n]))
+                    .collect();
+                Ok((neurons, r.iter().zip(w_in.iter()).map(|(r, w_in)| r * w_in).collect()))
+            },
+            node => Err(graph::unexpected_node(name, "CubaLIF", node)),
+        }
+    }
+}
+
+impl SerializableNeuron for CubaLifNeuron {
+    const MODEL_ID: u32 = 2;
+    const PARAMS_NUMBER: usize = 8;
+
+    /* (only the const fields are stored: the mutable ones are initialized when the network runs) */
+    fn to_params(&self) -> Vec<f64> {
+        let kernel = match self.kernel {
+            SynapseKernel::Exponential => 0.0,
+            SynapseKernel::Alpha => 1.0,
+        };
+        vec![self.v_th, self.v_rest, self.v_reset, self.tau_mem, self.tau_exc, self.tau_inh, kernel, self.dt]
+    }
+
+    fn from_params(params: &[f64]) -> Self {
+        let kernel = if params[6] == 0.0 { SynapseKernel::Exponential } else { SynapseKernel::Alpha };
+        Self::new(params[0], params[1], params[2], params[3], params[4], params[5], params[7]).with_kernel(kernel)
+    }
+}
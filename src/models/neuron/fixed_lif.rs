@@ -0,0 +1,217 @@
+use crate::fault::model::{FaultInjector, NeuronSite};
+use crate::snn::neuron::{self, Membrane, Neuron, SynapticInput};
+use crate::utils::fixed::FixedFormat;
+
+/* * Fixed-point LIF submodule * */
+
+/**
+    Leak of the membrane potential towards the resting potential, in fixed-point arithmetic
+    (replacing the exponential of the LIF formula)
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum Leak {
+    /**
+        At each instant, (v_mem - v_rest) is reduced by (v_mem - v_rest) >> k,
+        i.e. it is multiplied by (1 - 2^-k), which approximates exp(-dt / tau) with tau = dt * 2^k
+    */
+    Shift(u32),
+    /**
+        Lookup table of the (raw) decay factors: after n instants without spikes, (v_mem - v_rest)
+        is multiplied by table[n]; after table.len() or more instants it is completely decayed
+    */
+    Table(Vec<i64>),
+}
+
+impl Leak {
+    /**
+        It builds the lookup table of exp(-n * dt / tau), n = 0..len, quantized in the given format
+    */
+    pub fn table(tau: f64, dt: f64, len: usize, format: &FixedFormat) -> Self {
+        Leak::Table((0..len).map(|n| format.quantize((-(n as f64) * dt / tau).exp())).collect())
+    }
+
+    /*
+        It returns the raw value of *v* after *instants* instants of leak, where the leak parameter
+        (the shift or the factor of the table) is read through *read*
+    */
+    fn apply<F: Fn(i64) -> i64>(&self, v: i64, instants: u64, format: &FixedFormat, read: F) -> i64 {
+        match self {
+            Leak::Shift(k) => {
+                let k = read(*k as i64).clamp(0, 127) as u32;
+                let mut v = v;
+                for _ in 0..instants {
+                    let leaked = format.sub(v, format.shift_right(v, k));
+                    if leaked == v {
+                        break;  /* (the potential cannot decay anymore) */
+                    }
+                    v = leaked;
+                }
+                v
+            },
+            Leak::Table(factors) => match factors.get(instants as usize) {
+                Some(factor) => format.mul(v, read(*factor)),
+                None => 0,
+            },
+        }
+    }
+}
+
+/**
+Object representing a Neuron in the LIF model computed in fixed-point arithmetic, bit-accurate
+with respect to a hardware implementation using the same format, leak and rounding.
+ - The layer accumulates the weighted sums in the neuron format (see Neuron::get_fixed_format()): the weights
+   are quantized in the format (exactly, if they are representable in it, see FixedFormat::round_to_grid()),
+   and their raw values are added with the overflow mode of the format, as the hardware accumulator does
+ */
+#[derive(Debug, Clone)]
+pub struct FixedLifNeuron {
+    format: FixedFormat,
+    /* const fields (raw values) */
+    v_th:    i64,       /* threshold potential */
+    v_rest:  i64,       /* resting potential */
+    v_reset: i64,       /* reset potential */
+    leak:    Leak,
+    /* mutable fields */
+    v_mem:   i64,       /* membrane potential (raw value) */
+    ts:      u64,       /* last instant in which has been received at least one spike */
+}
+
+impl FixedLifNeuron {
+    pub fn new(format: FixedFormat, v_th: f64, v_rest: f64, v_reset: f64, leak: Leak) -> Self {
+        let v_rest = format.quantize(v_rest);
+
+        Self {
+            format,
+            v_th: format.quantize(v_th),
+            v_rest,
+            v_reset: format.quantize(v_reset),
+            leak,
+            v_mem: v_rest,
+            ts: 0u64,
+        }
+    }
+
+    /* Getters of the neuron object parameters */
+    pub fn get_format(&self) -> FixedFormat {
+        self.format
+    }
+
+    pub fn get_leak(&self) -> &Leak {
+        &self.leak
+    }
+
+    pub fn get_v_th(&self) -> f64 {
+        self.format.to_f64(self.v_th)
+    }
+
+    pub fn get_v_rest(&self) -> f64 {
+        self.format.to_f64(self.v_rest)
+    }
+
+    pub fn get_v_reset(&self) -> f64 {
+        self.format.to_f64(self.v_reset)
+    }
+
+    pub fn get_v_mem(&self) -> f64 {
+        self.format.to_f64(self.v_mem)
+    }
+
+    /** Raw value of the membrane potential, as stored by the hardware (e.g. to compare test vectors) */
+    pub fn get_v_mem_raw(&self) -> i64 {
+        self.v_mem
+    }
+
+    pub fn get_ts(&self) -> u64 {
+        self.ts
+    }
+}
+
+impl FixedLifNeuron {
+    /*
+        Fixed-point LIF formula, where each parameter, the state and the output of each operation
+        are read (as raw values) through *read* (the identity, unless some faults are injected)
+    */
+    fn update_v_mem<F: Fn(NeuronSite, i64) -> i64>(&mut self, t: u64, extra_weighted_sum: f64,
+                                                   intra_weighted_sum: f64, read: F) -> u8 {
+        self.integrate_v_mem(t, extra_weighted_sum, intra_weighted_sum, &read);
+
+        let fire = read(NeuronSite::Comparator, if self.v_mem > read(NeuronSite::VTh, self.v_th) { 1 } else { 0 });
+
+        if fire != 0 {
+            /* reset membrane potential */
+            self.v_mem = read(NeuronSite::VReset, self.v_reset);
+            1   /* fire */
+        } else {
+            0   /* not fire */
+        }
+    }
+
+    /* (leak and integration of the input, without firing) */
+    fn integrate_v_mem<F: Fn(NeuronSite, i64) -> i64>(&mut self, t: u64, extra_weighted_sum: f64,
+                                                      intra_weighted_sum: f64, read: &F) {
+        let format = &self.format;
+        let weighted_sum = read(NeuronSite::Adder,
+                                format.add(format.quantize(extra_weighted_sum), format.quantize(intra_weighted_sum)));
+
+        /* leak towards the resting potential (the Tau site is the leak parameter), then integrate the input */
+        let decayed = self.leak.apply(format.sub(read(NeuronSite::VMem, self.v_mem), self.v_rest), t - self.ts, format,
+                                      |param| read(NeuronSite::Tau, param));
+        let decayed = read(NeuronSite::Multiplier, decayed);
+        self.v_mem = format.add(format.add(self.v_rest, decayed), weighted_sum);
+
+        /* update ts - last instant in which at least one positive spike (1) is received */
+        self.ts = t;
+    }
+}
+
+impl Neuron for FixedLifNeuron {
+    /*
+        This function updates the membrane potential of the neuron when it receives at least one spike
+    */
+    fn compute_v_mem(&mut self, t: u64, extra_weighted_sum: f64, intra_weighted_sum: f64) -> u8 {
+        self.update_v_mem(t, extra_weighted_sum, intra_weighted_sum, |_, raw| raw)
+    }
+
+    /* (the faults affect the bits of the raw values, i.e. of the registers of the hardware) */
+    fn compute_v_mem_with_faults(&mut self, t: u64, input: &SynapticInput, injector: &dyn FaultInjector) -> u8 {
+        let width = self.format.get_width();
+        self.update_v_mem(t, input.extra_weighted_sum, input.intra_weighted_sum,
+                          |site, raw| injector.inject_raw(site, raw, width))
+    }
+
+    fn get_fault_width(&self) -> Option<u32> {
+        Some(self.format.get_width())
+    }
+
+    fn get_fixed_format(&self) -> Option<FixedFormat> {
+        Some(self.format)
+    }
+
+    fn integrate(&mut self, t: u64, input: &SynapticInput) -> Option<Membrane> {
+        self.integrate_v_mem(t, input.extra_weighted_sum, input.intra_weighted_sum, &|_, raw| raw);
+
+        let format = &self.format;
+        Some(Membrane { v_mem: format.to_f64(self.v_mem), v_th: format.to_f64(self.v_th), v_reset: format.to_f64(self.v_reset) })
+    }
+
+    /* (the potential is quantized in the format of the neuron) */
+    fn set_v_mem(&mut self, v_mem: f64) {
+        self.v_mem = self.format.quantize(v_mem);
+    }
+
+    /* (raw membrane potential and last update instant) */
+    fn get_state(&self) -> Option<Vec<f64>> {
+        Some(vec![self.v_mem as f64, self.ts as f64])
+    }
+
+    fn set_state(&mut self, state: &[f64]) {
+        neuron::check_state(state, 2);
+        self.v_mem = state[0] as i64;
+        self.ts = state[1] as u64;
+    }
+
+    fn initialize(&mut self) {
+        self.v_mem = self.v_rest;
+        self.ts = 0u64;
+    }
+}
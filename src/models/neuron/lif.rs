@@ -1,4 +1,8 @@
-use crate::snn::neuron::Neuron;
+use crate::fault::model::{FaultInjector, NeuronSite};
+use crate::format::binary::SerializableNeuron;
+use crate::format::graph::{self, GraphNeuron, GraphNode};
+use std::io;
+use crate::snn::neuron::{self, Membrane, Neuron, SynapticInput};
 
 /* * LIF submodule * */
 
@@ -60,37 +64,128 @@ impl LifNeuron {
 
 }
 
-impl Neuron for LifNeuron {
+impl LifNeuron {
     /*
-        This function updates the membrane potential of the neuron when it receives at least one spike
+        LIF formula, where each parameter, the state and the output of each operation
+        are read through *read* (the identity, unless some faults are injected)
     */
-    fn compute_v_mem(&mut self, t: u64, extra_weighted_sum: f64, intra_weighted_sum: f64) -> u8 {
-        let weighted_sum = extra_weighted_sum +    /* positive contribute */
-            intra_weighted_sum      /* negative contribute */;
+    fn update_v_mem<F: Fn(NeuronSite, f64) -> f64>(&mut self, t: u64, extra_weighted_sum: f64,
+                                                   intra_weighted_sum: f64, read: F) -> u8 {
+        self.integrate_v_mem(t, extra_weighted_sum, intra_weighted_sum, &read);
 
-        /* compute the neuron membrane potential with the LIF formula */
-
-        let exponent = -(((t - self.ts) as f64) * self.dt / self.tau);
-        self.v_mem = self.v_rest + (self.v_mem - self.v_rest) * exponent.exp() + weighted_sum;
+        let fire = read(NeuronSite::Comparator, if self.v_mem > read(NeuronSite::VTh, self.v_th) { 1.0 } else { 0.0 });
 
-        /* update ts - last instant in which at least one positive spike (1) is received */
-        self.ts = t;
-
-        return if self.v_mem > self.v_th {
+        return if fire != 0.0 {
             /* reset membrane potential */
-            self.v_mem = self.v_reset;
+            self.v_mem = read(NeuronSite::VReset, self.v_reset);
             1   /* fire */
         } else {
             0   /* not fire */
         };
     }
 
+    /* (leak and integration of the input, without firing) */
+    fn integrate_v_mem<F: Fn(NeuronSite, f64) -> f64>(&mut self, t: u64, extra_weighted_sum: f64,
+                                                      intra_weighted_sum: f64, read: &F) {
+        let weighted_sum = read(NeuronSite::Adder,
+                                extra_weighted_sum +    /* positive contribute */
+                                intra_weighted_sum      /* negative contribute */);
+
+        /* compute the neuron membrane potential with the LIF formula */
+
+        let exponent = -(((t - self.ts) as f64) * self.dt / read(NeuronSite::Tau, self.tau));
+        let decay = read(NeuronSite::Multiplier, (read(NeuronSite::VMem, self.v_mem) - self.v_rest) * exponent.exp());
+        self.v_mem = self.v_rest + decay + weighted_sum;
+
+        /* update ts - last instant in which at least one positive spike (1) is received */
+        self.ts = t;
+    }
+}
+
+impl Neuron for LifNeuron {
+    /*
+        This function updates the membrane potential of the neuron when it receives at least one spike
+    */
+    fn compute_v_mem(&mut self, t: u64, extra_weighted_sum: f64, intra_weighted_sum: f64) -> u8 {
+        self.update_v_mem(t, extra_weighted_sum, intra_weighted_sum, |_, value| value)
+    }
+
+    fn compute_v_mem_with_faults(&mut self, t: u64, input: &SynapticInput, injector: &dyn FaultInjector) -> u8 {
+        self.update_v_mem(t, input.extra_weighted_sum, input.intra_weighted_sum, |site, value| injector.inject(site, value))
+    }
+
+    fn get_fault_width(&self) -> Option<u32> {
+        Some(64)
+    }
+
+    fn integrate(&mut self, t: u64, input: &SynapticInput) -> Option<Membrane> {
+        self.integrate_v_mem(t, input.extra_weighted_sum, input.intra_weighted_sum, &|_, value| value);
+        Some(Membrane { v_mem: self.v_mem, v_th: self.v_th, v_reset: self.v_reset })
+    }
+
+    fn set_v_mem(&mut self, v_mem: f64) {
+        self.v_mem = v_mem;
+    }
+
+    /* (membrane potential and last update instant) */
+    fn get_state(&self) -> Option<Vec<f64>> {
+        Some(vec![self.v_mem, self.ts as f64])
+    }
+
+    fn set_state(&mut self, state: &[f64]) {
+        neuron::check_state(state, 2);
+        self.v_mem = state[0];
+        self.ts = state[1] as u64;
+    }
+
     fn initialize(&mut self) {
         self.v_mem = self.v_rest;
         self.ts = 0u64;
     }
 }
 
+impl SerializableNeuron for LifNeuron {
+    const MODEL_ID: u32 = 1;
+    const PARAMS_NUMBER: usize = 5;
+
+    /* (only the const fields are stored: the mutable ones are initialized when the network runs) */
+    fn to_params(&self) -> Vec<f64> {
+        vec![self.v_th, self.v_rest, self.v_reset, self.tau, self.dt]
+    }
+
+    fn from_params(params: &[f64]) -> Self {
+        Self::new(params[0], params[1], params[2], params[3], params[4])
+    }
+}
+
+impl GraphNeuron for LifNeuron {
+    /* (LIF node: the resistance r is 1, since the weighted sums are directly added to the membrane potential) */
+    fn to_node(neurons: &[Self]) -> GraphNode {
+        GraphNode::Lif {
+            tau: neurons.iter().map(|n| n.tau).collect(),
+            r: vec![1.0; neurons.len()],
+            v_leak: neurons.iter().map(|n| n.v_rest).collect(),
+            v_threshold: neurons.iter().map(|n| n.v_th).collect(),
+            v_reset: neurons.iter().map(|n| n.v_reset).collect()
+        }
+    }
+
+    fn from_node(name: &str, node: &GraphNode, num_neurons: usize, dt: f64) -> io::Result<(Vec<Self>, Vec<f64>)> {
+        match node {
+            GraphNode::Lif { tau, r, v_leak, v_threshold, v_reset } => {
+                graph::check_params(name, node, &[("tau", tau.len()), ("r", r.len()), ("v_leak", v_leak.len()),
+                                                  ("v_threshold", v_threshold.len()), ("v_reset", v_reset.len())],
+                                    num_neurons)?;
+                let neurons = (0..num_neurons)
+                    .map(|n| Self::new(v_threshold[n], v_leak[n], v_reset[n], tau[n], dt))
+                    .collect();
+                Ok((neurons, r.clone()))
+            },
+            node => Err(graph::unexpected_node(name, "LIF", node)),
+        }
+    }
+}
+
 /*
     Traits implementation for the LifNeuron object
 */
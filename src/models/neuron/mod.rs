@@ -1,4 +1,7 @@
 /* * SNN Neuron models * */
 
+pub mod coba_lif;
+pub mod cuba_lif;
+pub mod fixed_lif;
 pub mod lif;
-/* (only LIF model supported at the moment) */
+/* (LIF model, in floating-point or fixed-point arithmetic, and with current-based or conductance-based synapses) */
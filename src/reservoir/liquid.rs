@@ -0,0 +1,230 @@
+/* * Liquid submodule * */
+
+use crate::DynSNN;
+use crate::builders::DynSnnBuilder;
+use crate::models::neuron::lif::LifNeuron;
+use crate::neuron::Neuron;
+use crate::utils::random::Rng;
+use crate::weights::SignPolicy;
+
+/**
+    Object describing a Liquid State Machine reservoir: a single layer of randomly and recurrently
+    connected LifNeurons, generated from a few hyperparameters.
+    - A fraction *excitatory_ratio* of the neurons is excitatory (its outgoing recurrent weights are
+      positive), the others are inhibitory (negative weights), following Dale's law
+    - Each recurrent link exists with probability *connection_probability*, multiplied by
+      exp(-(d / lambda)^2) if the neurons are placed on a 3D grid at distance d (distance-dependent connectivity)
+    - The magnitudes of the recurrent weights are uniform in [0, 1), then the whole matrix is scaled
+      so that its spectral radius is *spectral_radius*
+    - Each reservoir neuron receives each input with probability *input_probability*, with weight *input_weight*
+    - The recurrent weights are both positive and negative, so the layer has the Dale sign policy
+*/
+#[derive(Debug, Clone)]
+pub struct Reservoir {
+    input_dimension: usize,
+    size: usize,
+    excitatory_ratio: f64,
+    connection_probability: f64,
+    lambda: Option<f64>,
+    spectral_radius: f64,
+    input_probability: f64,
+    input_weight: f64,
+    neuron: LifNeuron,
+    seed: u64,
+}
+
+impl Reservoir {
+    pub fn new(input_dimension: usize, size: usize) -> Self {
+        if size == 0 {
+            panic!("The reservoir must have at least one neuron");
+        }
+
+        Self {
+            input_dimension,
+            size,
+            excitatory_ratio: 0.8,
+            connection_probability: 0.1,
+            lambda: None,
+            spectral_radius: 0.9,
+            input_probability: 0.3,
+            input_weight: 1.0,
+            neuron: LifNeuron::new(1.0, 0.0, 0.0, 20.0, 1.0),
+            seed: 0,
+        }
+    }
+
+    /* Setters of the hyperparameters */
+
+    /** - It panics if the ratio is not in the range [0, 1] */
+    pub fn with_excitatory_ratio(mut self, excitatory_ratio: f64) -> Self {
+        check_probability("excitatory ratio", excitatory_ratio);
+        self.excitatory_ratio = excitatory_ratio;
+        self
+    }
+
+    /** - It panics if the probability is not in the range [0, 1] */
+    pub fn with_connection_probability(mut self, connection_probability: f64) -> Self {
+        check_probability("connection probability", connection_probability);
+        self.connection_probability = connection_probability;
+        self
+    }
+
+    /** It places the neurons on a 3D grid, making the connection probability decay with the distance */
+    pub fn with_distance_dependence(mut self, lambda: f64) -> Self {
+        if lambda <= 0.0 {
+            panic!("lambda must be positive");
+        }
+        self.lambda = Some(lambda);
+        self
+    }
+
+    /** - It panics if the spectral radius is negative */
+    pub fn with_spectral_radius(mut self, spectral_radius: f64) -> Self {
+        if spectral_radius < 0.0 {
+            panic!("The spectral radius must not be negative");
+        }
+        self.spectral_radius = spectral_radius;
+        self
+    }
+
+    /** - It panics if the probability is not in the range [0, 1] or the weight is negative */
+    pub fn with_input(mut self, input_probability: f64, input_weight: f64) -> Self {
+        check_probability("input probability", input_probability);
+        if input_weight < 0.0 {
+            panic!("The weights must be positive");
+        }
+        self.input_probability = input_probability;
+        self.input_weight = input_weight;
+        self
+    }
+
+    /** Neuron of the reservoir (all the neurons are copies of it) */
+    pub fn with_neuron(mut self, neuron: LifNeuron) -> Self {
+        self.neuron = neuron;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /**
+        It generates the reservoir: the same hyperparameters and seed always generate the same network
+    */
+    pub fn build(&self) -> DynSNN<LifNeuron> {
+        let mut rng = Rng::new(self.seed);
+        let excitatory_number = (self.excitatory_ratio * self.size as f64).round() as usize;
+
+        /* input weights */
+        let weights: Vec<Vec<f64>> = (0..self.size).map(|_|
+            (0..self.input_dimension)
+                .map(|_| if rng.bernoulli(self.input_probability) { self.input_weight } else { 0.0 })
+                .collect()
+        ).collect();
+
+        /* recurrent weights (intra_weights[i][j] is the weight of the link from j to i) */
+        let side = (self.size as f64).cbrt().ceil() as usize;
+        let position = |n: usize| [(n % side) as f64, ((n / side) % side) as f64, (n / (side * side)) as f64];
+
+        let mut intra_weights = vec![vec![0f64; self.size]; self.size];
+        for (i, row) in intra_weights.iter_mut().enumerate() {
+            for (j, weight) in row.iter_mut().enumerate() {
+                if i == j {
+                    continue;
+                }
+
+                let probability = match self.lambda {
+                    Some(lambda) => {
+                        let (a, b) = (position(i), position(j));
+                        let squared_distance: f64 = a.iter().zip(b.iter()).map(|(x, y)| (x - y) * (x - y)).sum();
+                        self.connection_probability * (-squared_distance / (lambda * lambda)).exp()
+                    },
+                    None => self.connection_probability,
+                };
+
+                if rng.bernoulli(probability) {
+                    let sign = if j < excitatory_number { 1.0 } else { -1.0 };
+                    *weight = sign * rng.next_f64();
+                }
+            }
+        }
+
+        /* spectral scaling */
+        let radius = spectral_radius(&intra_weights, &mut rng);
+        if radius > 0.0 {
+            let scale = self.spectral_radius / radius;
+            intra_weights.iter_mut().flatten().for_each(|weight| *weight *= scale);
+        }
+
+        DynSnnBuilder::new(self.input_dimension)
+            .with_sign_policy(SignPolicy::Dale)
+            .add_layer_with_same_neurons(self.neuron.clone(), self.size, weights, intra_weights)
+            .build()
+    }
+}
+
+/**
+    It estimates the spectral radius of a square matrix with Gelfand's formula,
+    rho = lim ||W^k x||^(1/k), which (unlike the plain power iteration) converges
+    also when the dominant eigenvalues are complex
+*/
+pub fn spectral_radius(matrix: &[Vec<f64>], rng: &mut Rng) -> f64 {
+    const WARMUP: usize = 50;
+    const ITERATIONS: usize = 100;
+
+    let mut x: Vec<f64> = (0..matrix.len()).map(|_| rng.next_f64() - 0.5).collect();
+    let mut log_growth = 0f64;
+
+    for iteration in 0..WARMUP + ITERATIONS {
+        x = matrix.iter().map(|row| row.iter().zip(x.iter()).map(|(w, v)| w * v).sum()).collect();
+
+        let norm = x.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if norm == 0.0 {
+            return 0.0;     /* (nilpotent matrix, e.g. without links) */
+        }
+        x.iter_mut().for_each(|v| *v /= norm);
+
+        /* (the first iterations only align x to the dominant eigenvectors) */
+        if iteration >= WARMUP {
+            log_growth += norm.ln();
+        }
+    }
+
+    (log_growth / ITERATIONS as f64).exp()
+}
+
+/**
+    It filters the spike trains of a network output with an exponential kernel of time constant *tau*
+    (in instants): it returns the filtered state of each neuron at each instant, one row for each instant
+*/
+pub fn filter_spikes(spikes: &[Vec<u8>], tau: f64) -> Vec<Vec<f64>> {
+    let duration = spikes.first().map_or(0, |row| row.len());
+    let decay = (-1.0 / tau).exp();
+    let mut state = vec![0f64; spikes.len()];
+
+    (0..duration).map(|t| {
+        for (value, neuron_spikes) in state.iter_mut().zip(spikes.iter()) {
+            *value = *value * decay + neuron_spikes[t] as f64;
+        }
+        state.clone()
+    }).collect()
+}
+
+/**
+    It runs the reservoir on each input (in parallel, on *num_threads* replicas) and returns the
+    final filtered state of its neurons for each input (see filter_spikes()), i.e. the features
+    used to train the readout
+*/
+pub fn collect_states<N: Neuron + Clone>(reservoir: &DynSNN<N>, inputs: &[Vec<Vec<u8>>], tau: f64,
+                                         num_threads: usize) -> Vec<Vec<f64>> {
+    reservoir.process_batch(inputs, num_threads).iter()
+        .map(|output| filter_spikes(output, tau).pop().unwrap_or_else(|| vec![0.0; output.len()]))
+        .collect()
+}
+
+fn check_probability(name: &str, value: f64) {
+    if !(0.0..=1.0).contains(&value) {
+        panic!("The {} must be in the range [0, 1]", name);
+    }
+}
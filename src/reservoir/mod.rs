@@ -0,0 +1,5 @@
+/* * Reservoir computing * */
+
+pub mod liquid;
+pub mod ridge;
+/* (the recurrent network is generated randomly and stays fixed: only the linear readout is trained) */
@@ -0,0 +1,175 @@
+/* * Ridge submodule * */
+
+/**
+    Linear readout of a reservoir, trained with ridge regression: each output is
+    y = w · x + b, where x is the state of the reservoir (see liquid::collect_states())
+*/
+#[derive(Debug, Clone)]
+pub struct RidgeReadout {
+    weights: Vec<Vec<f64>>,     /* one row for each output */
+    biases: Vec<f64>,
+}
+
+impl RidgeReadout {
+    /**
+        It trains the readout minimizing ||X W + b - Y||^2 + alpha ||W||^2 (the biases are not
+        regularized), solving the normal equations with the Cholesky decomposition
+        - states: one row for each sample (the features)
+        - targets: one row for each sample (the desired outputs)
+        - It panics if there are no samples, if the rows have different lengths or if alpha is not positive
+    */
+    pub fn fit(states: &[Vec<f64>], targets: &[Vec<f64>], alpha: f64) -> Self {
+        if states.is_empty() || states.len() != targets.len() {
+            panic!("The number of states must be equal to the number of targets (and greater than 0)");
+        }
+        if alpha <= 0.0 {
+            panic!("alpha must be positive");
+        }
+
+        let features = states[0].len();
+        let outputs = targets[0].len();
+        if states.iter().any(|state| state.len() != features) || targets.iter().any(|target| target.len() != outputs) {
+            panic!("All the states (and all the targets) must have the same length");
+        }
+
+        /* normal equations (X'X + alpha I) W = X'Y, where the last column of X is the bias (always 1) */
+        let n = features + 1;
+        let mut gram = vec![vec![0f64; n]; n];
+        let mut rhs = vec![vec![0f64; outputs]; n];
+
+        for (state, target) in states.iter().zip(targets.iter()) {
+            let x = |i: usize| if i < features { state[i] } else { 1.0 };
+
+            for i in 0..n {
+                let xi = x(i);
+                if xi == 0.0 {
+                    continue;   /* (the states of a reservoir are sparse) */
+                }
+                for (j, value) in gram[i].iter_mut().enumerate().take(i + 1) {
+                    *value += xi * x(j);
+                }
+                for (value, y) in rhs[i].iter_mut().zip(target.iter()) {
+                    *value += xi * y;
+                }
+            }
+        }
+        for (i, row) in gram.iter_mut().enumerate().take(features) {
+            row[i] += alpha;
+        }
+
+        let solution = cholesky_solve(gram, rhs);
+
+        Self {
+            weights: (0..outputs).map(|o| (0..features).map(|i| solution[i][o]).collect()).collect(),
+            biases: solution[features].clone(),
+        }
+    }
+
+    /* Getters */
+    pub fn get_weights(&self) -> &Vec<Vec<f64>> {
+        &self.weights
+    }
+
+    pub fn get_biases(&self) -> &Vec<f64> {
+        &self.biases
+    }
+
+    pub fn get_input_dimension(&self) -> usize {
+        self.weights.first().map_or(0, |row| row.len())
+    }
+
+    pub fn get_output_dimension(&self) -> usize {
+        self.biases.len()
+    }
+
+    /** - It panics if the state length differs from the input dimension */
+    pub fn predict(&self, state: &[f64]) -> Vec<f64> {
+        if state.len() != self.get_input_dimension() {
+            panic!("The state length must be equal to the readout input dimension");
+        }
+
+        self.weights.iter().zip(self.biases.iter())
+            .map(|(row, bias)| row.iter().zip(state.iter()).map(|(w, x)| w * x).sum::<f64>() + bias)
+            .collect()
+    }
+
+    /** It returns the index of the greatest output (None if the readout has no outputs) */
+    pub fn predict_class(&self, state: &[f64]) -> Option<usize> {
+        self.predict(state).iter().enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(class, _)| class)
+    }
+
+    /** Mean squared error of the predictions */
+    pub fn score(&self, states: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+        let (error, count) = states.iter().zip(targets.iter())
+            .flat_map(|(state, target)| self.predict(state).into_iter().zip(target.iter())
+                .map(|(y, t)| (y - t) * (y - t)).collect::<Vec<_>>())
+            .fold((0f64, 0usize), |(error, count), e| (error + e, count + 1));
+
+        if count == 0 { 0.0 } else { error / count as f64 }
+    }
+}
+
+/**
+    It converts class labels into one-hot targets (1 for the label class, 0 otherwise), to train
+    a classification readout
+    - It panics if a label is not less than *num_classes*
+*/
+pub fn one_hot(labels: &[usize], num_classes: usize) -> Vec<Vec<f64>> {
+    labels.iter().map(|&label| {
+        if label >= num_classes {
+            panic!("The label {} is not less than the number of classes ({})", label, num_classes);
+        }
+        (0..num_classes).map(|class| if class == label { 1.0 } else { 0.0 }).collect()
+    }).collect()
+}
+
+/*
+    It solves A X = B for a symmetric positive definite A, of which only the lower triangle is used
+*/
+fn cholesky_solve(mut a: Vec<Vec<f64>>, mut b: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+    let n = a.len();
+
+    /* A = L L' (L overwrites the lower triangle of A) */
+    for j in 0..n {
+        let diagonal = a[j][j] - a[j][..j].iter().map(|l| l * l).sum::<f64>();
+        if diagonal <= 0.0 {
+            panic!("The normal equations are not positive definite");
+        }
+        a[j][j] = diagonal.sqrt();
+
+        for i in j + 1..n {
+            let dot: f64 = a[i][..j].iter().zip(a[j][..j].iter()).map(|(x, y)| x * y).sum();
+            a[i][j] = (a[i][j] - dot) / a[j][j];
+        }
+    }
+
+    /* forward substitution: L Z = B */
+    for i in 0..n {
+        for k in 0..i {
+            let l = a[i][k];
+            if l != 0.0 {
+                let (done, rest) = b.split_at_mut(i);
+                rest[0].iter_mut().zip(done[k].iter()).for_each(|(value, z)| *value -= l * z);
+            }
+        }
+        let diagonal = a[i][i];
+        b[i].iter_mut().for_each(|value| *value /= diagonal);
+    }
+
+    /* backward substitution: L' X = Z */
+    for i in (0..n).rev() {
+        let (head, tail) = b.split_at_mut(i + 1);
+        for (row, x) in a[i + 1..].iter().zip(tail.iter()) {
+            let l = row[i];
+            if l != 0.0 {
+                head[i].iter_mut().zip(x.iter()).for_each(|(value, x)| *value -= l * x);
+            }
+        }
+        let diagonal = a[i][i];
+        b[i].iter_mut().for_each(|value| *value /= diagonal);
+    }
+
+    b
+}
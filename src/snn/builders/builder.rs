@@ -4,18 +4,22 @@ use std::sync::{Arc, Mutex};
 use crate::snn::layer::Layer;
 use crate::snn::neuron::Neuron;
 use crate::snn::snn::SNN;
+use crate::snn::weights::{self, SignPolicy};
 
 /**
     Object containing the configuration parameters describing the SNN architecture
     - *neurons*: has a Vec of Neurons for each layer
     - *extra_weights*: has a matrix of weights for each layer (each matrix has a Vec for each layer's Neuron)
     - *intra_weights*: has a matrix of weights for each layer (each matrix has a Vec for each layer's Neuron)
+    - *sign_policy* and *weight_limit*: the checks on the weights values (see SignPolicy)
  */
 #[derive(Debug, Clone)]
 pub struct SnnParams<N: Neuron + Clone + Send + 'static> {
     pub neurons: Vec<Vec<N>>,               /* neurons per each layer */
-    pub extra_weights: Vec<Vec<Vec<f64>>>,  /* (positive) weights between layers */
-    pub intra_weights: Vec<Vec<Vec<f64>>>,  /* (negative) weights inside the same layer */
+    pub extra_weights: Vec<Vec<Vec<f64>>>,  /* weights between layers (positive, with the strict sign policy) */
+    pub intra_weights: Vec<Vec<Vec<f64>>>,  /* weights inside the same layer (negative, with the strict sign policy) */
+    pub sign_policy: SignPolicy,            /* policy on the signs of the weights of all the layers */
+    pub weight_limit: Option<f64>,          /* maximum absolute value of the weights (if any) */
 }
 
 /**
@@ -36,7 +40,9 @@ impl<N: Neuron + Clone + Send + 'static> SnnBuilder<N> {
             params: SnnParams {
                 neurons: vec![],
                 extra_weights: vec![],
-                intra_weights: vec![]
+                intra_weights: vec![],
+                sign_policy: SignPolicy::Strict,
+                weight_limit: None
             }
         }
     }
@@ -45,6 +51,25 @@ impl<N: Neuron + Clone + Send + 'static> SnnBuilder<N> {
         self.params.clone()
     }
 
+    /**
+        It sets the policy on the signs of the weights (Strict by default, see SignPolicy),
+        which is checked while adding the layers and by the setters of the built network
+    */
+    pub fn with_sign_policy(mut self, sign_policy: SignPolicy) -> Self {
+        self.params.sign_policy = sign_policy;
+        self
+    }
+
+    /**
+        It sets the maximum absolute value of the weights and of the intra weights (no limit by default),
+        e.g. Some(1.0) to accept only the weights in the range [-1, 1].
+        The limit is checked while adding the layers and by the setters of the built network
+    */
+    pub fn with_weight_limit(mut self, weight_limit: Option<f64>) -> Self {
+        self.params.weight_limit = weight_limit;
+        self
+    }
+
     /* (the *input dimension* of the network can be automatically inferred by the compiler) */
     pub fn add_layer<const INPUT_DIM: usize>(self) -> WeightsBuilder<N, INPUT_DIM, INPUT_DIM> {
         WeightsBuilder::<N, INPUT_DIM, INPUT_DIM>::new(self.params)
@@ -84,12 +109,16 @@ impl<N: Neuron + Clone + Send + 'static, const INPUT_DIM: usize, const NET_INPUT
 
         /* convert the array-like parameter into a Vec */
         for neuron_weights in &weights {
-            neuron_weights.iter().for_each(|w|
-                if w < &0.0
-            { panic!("The weights must be positive"); });
             weights_vec.push(Vec::from(neuron_weights.as_slice()));
         }
 
+        /* check the weights values (and, with Dale's law, their coherence with the previous layer) */
+        let previous_intra_weights = self.params.intra_weights.last();
+        check_values(self.params.sign_policy.validate(&weights_vec, false)
+            .and_then(|_| weights::validate_limit(&weights_vec, self.params.weight_limit))
+            .and_then(|_| previous_intra_weights.map_or(Ok(()), |intra_weights|
+                self.params.sign_policy.validate_across_layers(intra_weights, &weights_vec))));
+
         /* save layer weights */
         self.params.extra_weights.push(weights_vec);
         NeuronsBuilder::<N, NUM_NEURONS, NET_INPUT_DIM>::new(self.params)
@@ -154,7 +183,8 @@ impl<N: Neuron + Clone + Send + 'static, const NUM_NEURONS: usize, const NET_INP
     }
 
     /**
-        It specifies the (negative) weights of the connections between neurons in the **same layer**
+        It specifies the weights of the connections between neurons in the **same layer**
+        (negative, with the strict sign policy)
         It receives a matrix-like argument with **an array for each neuron**. Each array contains
         the ordered weights of the links **from** its siblings
 
@@ -174,11 +204,12 @@ impl<N: Neuron + Clone + Send + 'static, const NUM_NEURONS: usize, const NET_INP
 
         /* convert array-like intra weights parameter into a Vec */
         for neuron_intra_weights in &intra_weights {
-            neuron_intra_weights.iter().for_each(|w| if w>&0f64
-            { panic!("The intra weights must be negative"); });
             intra_weights_vec.push(Vec::from(neuron_intra_weights.as_slice()));
         }
 
+        check_values(self.params.sign_policy.validate(&intra_weights_vec, true)
+            .and_then(|_| weights::validate_limit(&intra_weights_vec, self.params.weight_limit)));
+
         /* save layer intra weights */
         self.params.intra_weights.push(intra_weights_vec);
         LayerBuilder::<N, NUM_NEURONS, NET_INPUT_DIM>::new(self.params)
@@ -233,10 +264,19 @@ impl<N: Neuron + Clone + Send + 'static, const OUTPUT_DIM: usize, const NET_INPU
             let layer_intra_weights = intra_weights_iter.next().unwrap();
 
             /* create and save the new layer */
-            let new_layer = Layer::new(layer_neurons, layer_extra_weights, layer_intra_weights);
+            let mut new_layer = Layer::new(layer_neurons, layer_extra_weights, layer_intra_weights);
+            new_layer.set_sign_policy(self.params.sign_policy);
+            new_layer.set_weight_limit(self.params.weight_limit);
             layers.push(Arc::new(Mutex::new(new_layer)));
         }
 
         SNN::<N, NET_INPUT_DIM, OUTPUT_DIM>::new(layers)
     }
 }
+
+/* It panics with the description of the first invalid weight, if any */
+fn check_values(result: Result<(), String>) {
+    if let Err(message) = result {
+        panic!("{}", message);
+    }
+}
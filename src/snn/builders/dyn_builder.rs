@@ -2,8 +2,10 @@
 
 use std::sync::{Arc, Mutex};
 use crate::neuron::Neuron;
+use crate::snn::connectivity::Connectivity;
 use crate::snn::dyn_snn::DynSNN;
 use crate::snn::layer::Layer;
+use crate::snn::weights::{self, SignPolicy};
 
 /**
     Object containing the configuration parameters describing the DynSNN architecture
@@ -12,9 +14,11 @@ use crate::snn::layer::Layer;
 pub struct DynSnnParams<N: Neuron> {
     pub input_dimensions: usize,            /* dimension of the network input layer */
     pub neurons: Vec<Vec<N>>,               /* neurons per each layer */
-    pub extra_weights: Vec<Vec<Vec<f64>>>,  /* (positive) weights between layers */
-    pub intra_weights: Vec<Vec<Vec<f64>>>,  /* (negative) weights inside the same layer */
+    pub extra_weights: Vec<Vec<Vec<f64>>>,  /* weights between layers (positive, with the strict sign policy) */
+    pub intra_weights: Vec<Vec<Vec<f64>>>,  /* weights inside the same layer (negative, with the strict sign policy) */
     pub num_layers: usize,                  /* number of layers */
+    pub sign_policy: SignPolicy,            /* policy on the signs of the weights of all the layers */
+    pub weight_limit: Option<f64>,          /* maximum absolute value of the weights (if any) */
 }
 
 /**
@@ -36,7 +40,9 @@ impl<N: Neuron + Clone> DynSnnBuilder<N> {
                 neurons: vec![],
                 extra_weights: vec![],
                 intra_weights: vec![],
-                num_layers: 0
+                num_layers: 0,
+                sign_policy: SignPolicy::Strict,
+                weight_limit: None
             }
         }
     }
@@ -45,11 +51,35 @@ impl<N: Neuron + Clone> DynSnnBuilder<N> {
         self.params.clone()
     }
 
+    /**
+        It sets the policy on the signs of the weights (Strict by default, see SignPolicy),
+        which is checked while adding the layers and by the setters of the built network
+        - It panics if some layers have already been added
+    */
+    pub fn with_sign_policy(mut self, sign_policy: SignPolicy) -> Self {
+        if self.params.num_layers != 0 {
+            panic!("The sign policy must be set before adding the layers");
+        }
+        self.params.sign_policy = sign_policy;
+        self
+    }
+
+    /**
+        It sets the maximum absolute value of the weights and of the intra weights (no limit by default),
+        e.g. Some(1.0) to accept only the weights in the range [-1, 1].
+        The limit is checked while adding the layers and by the setters of the built network
+    */
+    pub fn with_weight_limit(mut self, weight_limit: Option<f64>) -> Self {
+        self.params.weight_limit = weight_limit;
+        self
+    }
+
     /**
         It does all the checks related to the network's intra weights.
         - It checks that the number of neurons is equal to the number of rows of the intra weights matrix
         - It checks that the number of neurons is equal to the number of columns of the intra weights matrix
-        - It checks that the intra weights' values are all negative and in the range [-1, 0]
+        - It checks the intra weights' signs according to the sign policy (negative, with the strict one)
+          and their absolute values according to the weight limit (if any)
     */
     fn check_intra_weights(&self, num_neurons: usize, weights: &Vec<Vec<f64>>)  {
         if num_neurons != weights.len() {
@@ -59,12 +89,8 @@ impl<N: Neuron + Clone> DynSnnBuilder<N> {
             if num_neurons != row.len() {
                 panic!("The number of neurons must be equal to the number of columns of the intra weights matrix");
             }
-            for weight in row {
-                if *weight > 0.0 {
-                    panic!("The intra weights must be negative");
-                }
-            }
         }
+        self.check_values(weights, true);
     }
 
     /**
@@ -72,7 +98,9 @@ impl<N: Neuron + Clone> DynSnnBuilder<N> {
         - It checks that the number of neurons is equal to the number of rows of the extra weights matrix
         - It checks that the number of neurons is equal to the number of columns of the extra weights matrix
         - It checks that the number of columns of the extra weights matrix is equal to the number of neurons of the previous layer
-        - It checks that the extra weights' values are all positive and in the range [0, 1]
+        - It checks the extra weights' signs according to the sign policy (positive, with the strict one,
+          and with Dale's law also coherent with the intra weights of the previous layer) and their absolute
+          values according to the weight limit (if any)
     */
 
     fn check_weights(&self, num_neurons: usize, weights: &Vec<Vec<f64>>) {
@@ -91,14 +119,26 @@ impl<N: Neuron + Clone> DynSnnBuilder<N> {
                     panic!("The number of columns in the weights matrix must be equal to the number of neurons of the previous layer");
                 }
             }
-            for weight in row {
-                if *weight < 0.0 {
-                    panic!("The weights must be positive");
-                }
+        }
+        self.check_values(weights, false);
+
+        if let Some(previous_intra_weights) = self.params.intra_weights.last() {
+            if let Err(message) = self.params.sign_policy.validate_across_layers(previous_intra_weights, weights) {
+                panic!("{}", message);
             }
         }
     }
 
+    /* It checks the signs and the absolute values of the weights */
+    fn check_values(&self, weights: &[Vec<f64>], intra: bool) {
+        let result = self.params.sign_policy.validate(weights, intra)
+            .and_then(|_| weights::validate_limit(weights, self.params.weight_limit));
+
+        if let Err(message) = result {
+            panic!("{}", message);
+        }
+    }
+
     /**
         It adds a new layer to the network specifying all the parameters requested.
     */
@@ -140,6 +180,23 @@ impl<N: Neuron + Clone> DynSnnBuilder<N> {
         Self { params }
     }
 
+    /**
+        It adds a new layer of *num_neurons* neurons with the same parameters, whose weights and intra
+        weights are generated by the given connectivities (see Connectivity::weights() and
+        Connectivity::intra_weights()), without literal matrices
+    */
+    pub fn add_connected_layer(self, neuron: N, num_neurons: usize, extra: &Connectivity, intra: &Connectivity) -> Self {
+        let inputs_number = match self.params.num_layers {
+            0 => self.params.input_dimensions,
+            num_layers => self.params.neurons[num_layers - 1].len(),
+        };
+
+        let extra_weights = extra.weights(inputs_number, num_neurons);
+        let intra_weights = intra.intra_weights(num_neurons);
+
+        self.add_layer_with_same_neurons(neuron, num_neurons, extra_weights, intra_weights)
+    }
+
     /**
         Create and initialize the whole dynamic Spiking Neural Network with the characteristics defined so far
         - If the network has no layers, the process panics
@@ -167,7 +224,9 @@ impl<N: Neuron + Clone> DynSnnBuilder<N> {
             let layer_intra_weights = intra_weights_iter.next().unwrap();
 
             /* create and save the new layer */
-            let new_layer = Layer::new(layer_neurons, layer_extra_weights, layer_intra_weights);
+            let mut new_layer = Layer::new(layer_neurons, layer_extra_weights, layer_intra_weights);
+            new_layer.set_sign_policy(self.params.sign_policy);
+            new_layer.set_weight_limit(self.params.weight_limit);
             layers.push(Arc::new(Mutex::new(new_layer)));
         }
 
@@ -0,0 +1,206 @@
+/* * Connectivity submodule * */
+
+use crate::utils::random::Rng;
+
+/**
+    Distribution of the magnitudes of the generated weights
+    (negative samples, e.g. from a normal distribution, are rectified to 0)
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WeightDistribution {
+    Constant(f64),
+    Uniform { low: f64, high: f64 },
+    Normal { mean: f64, std: f64 },
+    LogNormal { mu: f64, sigma: f64 },     /* (parameters of the underlying normal distribution) */
+}
+
+impl WeightDistribution {
+    pub fn sample(&self, rng: &mut Rng) -> f64 {
+        let value = match *self {
+            WeightDistribution::Constant(value) => value,
+            WeightDistribution::Uniform { low, high } => low + (high - low) * rng.next_f64(),
+            WeightDistribution::Normal { mean, std } => mean + std * rng.standard_normal(),
+            WeightDistribution::LogNormal { mu, sigma } => (mu + sigma * rng.standard_normal()).exp(),
+        };
+
+        value.max(0.0)
+    }
+}
+
+/**
+    Pattern of the links between the neurons of two layers (pre-synaptic, i.e. the inputs,
+    and post-synaptic, i.e. the neurons of the new layer)
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Connector {
+    /** every input is linked to every neuron */
+    AllToAll,
+    /** the input i is linked only to the neuron i (the two layers must have the same dimension) */
+    OneToOne,
+    /** each link exists with the given probability */
+    FixedProbability(f64),
+    /** each neuron is linked to exactly the given number of (distinct) random inputs */
+    FixedInDegree(usize),
+    /**
+        The two layers are 2D grids of the given (rows, cols) shapes, overlapped on the same area:
+        each link exists with probability *max_probability* * exp(-d^2 / (2 sigma^2)), where d is
+        the distance between the two neurons, in cells of the post-synaptic grid
+    */
+    Gaussian2D { pre_shape: (usize, usize), post_shape: (usize, usize), sigma: f64, max_probability: f64 },
+}
+
+/**
+    Object generating the weights matrix of a layer from a Connector and a WeightDistribution,
+    instead of a literal matrix: the same seed always generates the same weights
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Connectivity {
+    connector: Connector,
+    distribution: WeightDistribution,
+    seed: u64,
+}
+
+impl Connectivity {
+    pub fn new(connector: Connector, distribution: WeightDistribution) -> Self {
+        Self { connector, distribution, seed: 0 }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /** Preset for the intra weights: each neuron inhibits all the others by *strength* */
+    pub fn lateral_inhibition(strength: f64) -> Self {
+        Self::new(Connector::AllToAll, WeightDistribution::Constant(strength))
+    }
+
+    /** Preset without links (e.g. for a layer without intra weights) */
+    pub fn none() -> Self {
+        Self::new(Connector::FixedProbability(0.0), WeightDistribution::Constant(0.0))
+    }
+
+    /* Getters */
+    pub fn get_connector(&self) -> Connector {
+        self.connector
+    }
+
+    pub fn get_distribution(&self) -> WeightDistribution {
+        self.distribution
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /**
+        It generates the (positive) weights between *pre* inputs and *post* neurons,
+        one row for each neuron, as expected by the builders
+        - It panics if the connector does not fit the dimensions of the two layers
+    */
+    pub fn weights(&self, pre: usize, post: usize) -> Vec<Vec<f64>> {
+        self.generate(pre, post, false)
+    }
+
+    /**
+        It generates the (negative) intra weights of a layer of *num_neurons* neurons, as expected
+        by the builders: the magnitudes are negated and the reflexive links are always 0
+        - It panics if the connector does not fit the dimension of the layer
+    */
+    pub fn intra_weights(&self, num_neurons: usize) -> Vec<Vec<f64>> {
+        let mut intra_weights = self.generate(num_neurons, num_neurons, true);
+        intra_weights.iter_mut().flatten().filter(|weight| **weight != 0.0).for_each(|weight| *weight = -*weight);
+        intra_weights
+    }
+
+    fn generate(&self, pre: usize, post: usize, intra: bool) -> Vec<Vec<f64>> {
+        let mut rng = Rng::new(self.seed);
+        let mut weights = vec![vec![0f64; pre]; post];
+
+        match self.connector {
+            Connector::AllToAll => {
+                for (i, row) in weights.iter_mut().enumerate() {
+                    for (j, weight) in row.iter_mut().enumerate() {
+                        if !(intra && i == j) {
+                            *weight = self.distribution.sample(&mut rng);
+                        }
+                    }
+                }
+            },
+            Connector::OneToOne => {
+                if pre != post {
+                    panic!("The one-to-one connector requires layers of the same dimension");
+                }
+                /* (in a layer the only links would be the reflexive ones, which are ignored) */
+                if !intra {
+                    for (i, row) in weights.iter_mut().enumerate() {
+                        row[i] = self.distribution.sample(&mut rng);
+                    }
+                }
+            },
+            Connector::FixedProbability(probability) => {
+                check_probability(probability);
+                for (i, row) in weights.iter_mut().enumerate() {
+                    for (j, weight) in row.iter_mut().enumerate() {
+                        if !(intra && i == j) && rng.bernoulli(probability) {
+                            *weight = self.distribution.sample(&mut rng);
+                        }
+                    }
+                }
+            },
+            Connector::FixedInDegree(in_degree) => {
+                let candidates_number = if intra { pre.saturating_sub(1) } else { pre };
+                if in_degree > candidates_number {
+                    panic!("The in-degree {} is greater than the number of available inputs ({})", in_degree, candidates_number);
+                }
+
+                for (i, row) in weights.iter_mut().enumerate() {
+                    let mut candidates: Vec<usize> = (0..pre).filter(|&j| !(intra && i == j)).collect();
+
+                    /* partial Fisher-Yates shuffle: the first in_degree candidates are the chosen ones */
+                    for k in 0..in_degree {
+                        let chosen = k + rng.below(candidates.len() - k);
+                        candidates.swap(k, chosen);
+                        row[candidates[k]] = self.distribution.sample(&mut rng);
+                    }
+                }
+            },
+            Connector::Gaussian2D { pre_shape, post_shape, sigma, max_probability } => {
+                check_probability(max_probability);
+                if pre_shape.0 * pre_shape.1 != pre || post_shape.0 * post_shape.1 != post {
+                    panic!("The grid shapes must match the dimensions of the layers");
+                }
+                if sigma <= 0.0 {
+                    panic!("sigma must be positive");
+                }
+
+                /* (position of the pre-synaptic neuron j on the post-synaptic grid) */
+                let scale = (post_shape.0 as f64 / pre_shape.0 as f64, post_shape.1 as f64 / pre_shape.1 as f64);
+                let pre_position = |j: usize| (((j / pre_shape.1) as f64 + 0.5) * scale.0 - 0.5,
+                                               ((j % pre_shape.1) as f64 + 0.5) * scale.1 - 0.5);
+
+                for (i, row) in weights.iter_mut().enumerate() {
+                    let (y, x) = ((i / post_shape.1) as f64, (i % post_shape.1) as f64);
+
+                    for (j, weight) in row.iter_mut().enumerate() {
+                        let (pre_y, pre_x) = pre_position(j);
+                        let squared_distance = (y - pre_y).powi(2) + (x - pre_x).powi(2);
+                        let probability = max_probability * (-squared_distance / (2.0 * sigma * sigma)).exp();
+
+                        if !(intra && i == j) && rng.bernoulli(probability) {
+                            *weight = self.distribution.sample(&mut rng);
+                        }
+                    }
+                }
+            },
+        }
+
+        weights
+    }
+}
+
+fn check_probability(probability: f64) {
+    if !(0.0..=1.0).contains(&probability) {
+        panic!("The connection probability must be in the range [0, 1]");
+    }
+}
@@ -1,9 +1,16 @@
 use std::slice::IterMut;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use crate::fault::model::Fault;
 use crate::neuron::Neuron;
-use crate::snn::layer::Layer;
+use crate::snn::layer::{Layer, RecurrenceMode, SpikingLayer};
 use crate::snn::processor::Processor;
-use crate::SpikeEvent;
+use crate::snn::readout::{ReadoutLayer, ReadoutMode};
+use crate::snn::snapshot::NetworkSnapshot;
+use crate::snn::stream::SpikeStream;
+use crate::snn::synapses::DynamicSynapses;
+use crate::snn::weights::SignPolicy;
+use crate::snn::wta::WtaMode;
 
 /* * Dynamic Spiking Neural Network structure * */
 
@@ -16,12 +23,13 @@ use crate::SpikeEvent;
 */
 #[derive(Debug, Clone)]
 pub struct DynSNN<N: Neuron + Clone + 'static> {
-    layers: Vec<Arc<Mutex<Layer<N>>>>
+    layers: Vec<Arc<Mutex<Layer<N>>>>,
+    elapsed: u64    /* number of instants processed since the last reset */
 }
 
 impl<N: Neuron + Clone> DynSNN<N> {
     pub fn new(layers: Vec<Arc<Mutex<Layer<N>>>>) -> Self {
-        Self { layers }
+        Self { layers, elapsed: 0 }
     }
 
     /* Getters */
@@ -29,14 +37,14 @@ impl<N: Neuron + Clone> DynSNN<N> {
         self.layers.len()
     }
 
-    fn get_input_layer_dimension(&self) -> usize {
+    pub fn get_input_layer_dimension(&self) -> usize {
         let first_layer = self.layers[0].lock().unwrap();
-        let input_layer_dimension = first_layer.get_weights().first().unwrap().len();
+        let input_layer_dimension = first_layer.weight_matrix().cols();
 
         input_layer_dimension
     }
 
-    fn get_output_layer_dimension(&self) -> usize {
+    pub fn get_output_layer_dimension(&self) -> usize {
         let last_layer = self.layers.last().unwrap().lock().unwrap();
         let output_dimension = last_layer.get_neurons_number();
 
@@ -47,96 +55,316 @@ impl<N: Neuron + Clone> DynSNN<N> {
         self.layers.iter().map(|layer| layer.lock().unwrap().clone()).collect()
     }
 
+    /*
+        Access to the weights and the neurons of the built network, by (layer, neuron, input) index.
+        The setters do the same checks of the builders (including the ones across two consecutive layers,
+        with Dale's law), and panic if an index is out of bounds
+    */
+    pub fn get_neuron(&self, layer: usize, index: usize) -> N {
+        self.layer(layer).get_neuron(index).clone()
+    }
+
+    pub fn set_neuron(&mut self, layer: usize, index: usize, neuron: N) {
+        self.layer(layer).set_neuron(index, neuron);
+    }
+
+    pub fn get_weight(&self, layer: usize, neuron: usize, input: usize) -> f64 {
+        self.layer(layer).get_weight(neuron, input)
+    }
+
+    pub fn set_weight(&mut self, layer: usize, neuron: usize, input: usize, weight: f64) {
+        if layer > 0 {
+            let next = self.layer(layer);
+            self.layer(layer - 1).check_outgoing_weights(&next, input, None, Some((neuron, weight)));
+        }
+        self.layer(layer).set_weight(neuron, input, weight);
+    }
+
+    pub fn get_intra_weight(&self, layer: usize, neuron: usize, from: usize) -> f64 {
+        self.layer(layer).get_intra_weight(neuron, from)
+    }
+
+    pub fn set_intra_weight(&mut self, layer: usize, neuron: usize, from: usize, weight: f64) {
+        if layer + 1 < self.layers.len() {
+            let next = self.layer(layer + 1);
+            self.layer(layer).check_outgoing_weights(&next, from, Some((neuron, weight)), None);
+        }
+        self.layer(layer).set_intra_weight(neuron, from, weight);
+    }
+
+    /** It replaces the weights of a layer (the dimensions of the matrix cannot change) */
+    pub fn set_weights(&mut self, layer: usize, weights: Vec<Vec<f64>>) {
+        if layer > 0 {
+            let next = self.layer(layer);
+            self.layer(layer - 1).check_across_layers(&next, None, Some(&weights));
+        }
+        self.layer(layer).set_weights(weights);
+    }
+
+    /** It replaces the intra weights of a layer (the dimensions of the matrix cannot change) */
+    pub fn set_intra_weights(&mut self, layer: usize, intra_weights: Vec<Vec<f64>>) {
+        if layer + 1 < self.layers.len() {
+            let next = self.layer(layer + 1);
+            self.layer(layer).check_across_layers(&next, Some(&intra_weights), None);
+        }
+        self.layer(layer).set_intra_weights(intra_weights);
+    }
+
+    /**
+        It sets the policy on the signs of the weights of all the layers (see Layer::set_sign_policy()),
+        e.g. for a network loaded from a binary file
+    */
+    pub fn set_sign_policy(&mut self, sign_policy: SignPolicy) {
+        for index in 0..self.layers.len() {
+            self.layer(index).set_sign_policy(sign_policy);
+        }
+        for index in 1..self.layers.len() {
+            let next = self.layer(index);
+            self.layer(index - 1).check_across_layers(&next, None, None);
+        }
+    }
+
+    /**
+        It sets the maximum absolute value of the weights of all the layers (see Layer::set_weight_limit()),
+        e.g. for a network loaded from a binary file
+    */
+    pub fn set_weight_limit(&mut self, weight_limit: Option<f64>) {
+        for index in 0..self.layers.len() {
+            self.layer(index).set_weight_limit(weight_limit);
+        }
+    }
+
+    /** It makes the extra-layer connections of a layer dynamic, or static again with None (see Layer::set_dynamic_synapses()) */
+    pub fn set_dynamic_synapses(&mut self, layer: usize, synapses: Option<DynamicSynapses>) {
+        self.layer(layer).set_dynamic_synapses(synapses);
+    }
+
+    /** It sets the winner-take-all competition among the neurons of a layer (see Layer::set_wta_mode()) */
+    pub fn set_wta_mode(&mut self, layer: usize, wta: WtaMode) {
+        self.layer(layer).set_wta_mode(wta);
+    }
+
+    /** It sets the semantics of the intra weights of all the layers (see RecurrenceMode) */
+    pub fn set_recurrence_mode(&mut self, recurrence: RecurrenceMode) {
+        for index in 0..self.layers.len() {
+            self.layer(index).set_recurrence_mode(recurrence);
+        }
+    }
+
+    /* It locks the given layer, panicking if it does not exist */
+    fn layer(&self, index: usize) -> MutexGuard<'_, Layer<N>> {
+        match self.layers.get(index) {
+            Some(layer) => layer.lock().unwrap(),
+            None => panic!("The layer {} does not exist (the network has {} layers)", index, self.layers.len()),
+        }
+    }
+
+    /* (access to the layers without cloning them, e.g. to serialize the network) */
+    pub(crate) fn layers(&self) -> &Vec<Arc<Mutex<Layer<N>>>> {
+        &self.layers
+    }
+
     /**
         Actually process input spikes by means of the Spiking Neural Network and produce corresponding output spikes.
         'spikes' contains an array for each input layer's neuron, and each array has the same
         number of spikes, equal to the duration of the input
         (spikes is a matrix, one row for each input neuron, and one column for each time instant)
         This method check user input at *run-time*
+        - The network starts from the initial state (see reset())
     */
     pub fn process(&mut self, spikes: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+        self.reset();
+        self.process_continue(spikes)
+    }
+
+    /**
+        Same as process(), but the network starts from the state reached at the end of the previous
+        input, so that a long input can be processed in consecutive chunks: the instants of each
+        chunk follow the ones already processed (see get_elapsed_instants()), and the output of
+        the chunks is the same of the whole input
+    */
+    pub fn process_continue(&mut self, spikes: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
         // * check and compute the spikes duration *
-        let spikes_duration = self.compute_spikes_duration(spikes);
+        let spikes_duration = Processor::compute_spikes_duration(spikes);
 
         let input_layer_dimension = self.get_input_layer_dimension();
         let output_layer_dimension = self.get_output_layer_dimension();
 
         // * encode spikes into SpikeEvent(s) *
         let input_spike_events =
-            DynSNN::<N>::encode_spikes(input_layer_dimension, spikes, spikes_duration);
+            Processor::encode_spikes(input_layer_dimension, spikes, spikes_duration);
 
         // * process input *
         let processor = Processor{};
-        let output_spike_events = processor.process_events(self,input_spike_events);
+        let output_spike_events = processor.process_events(self.layers.iter_mut(), input_spike_events,
+                                                           self.elapsed, spikes_duration as u64);
+        self.elapsed += spikes_duration as u64;
 
         // * decode output into array shape *
-        let decoded_output =  DynSNN::<N>::decode_spikes(output_layer_dimension,
+        let decoded_output =  Processor::decode_spikes(output_layer_dimension,
                                                          output_spike_events, spikes_duration);
 
         decoded_output
     }
 
+    /** Bring all the layers to the initial state, so that the next process_continue() starts from the instant 0 */
+    pub fn reset(&mut self) {
+        self.layers.iter().for_each(|layer| layer.lock().unwrap().reset());
+        self.elapsed = 0;
+    }
+
+    /** Number of instants processed since the last reset (the first instant of the next process_continue()) */
+    pub fn get_elapsed_instants(&self) -> u64 {
+        self.elapsed
+    }
+
     /**
-        This function checks if each vector passed in 'spikes' has the same number of spikes.
-        If yes, it returns the duration, otherwise it triggers an error
-     */
-    fn compute_spikes_duration(&self, spikes: &Vec<Vec<u8>>) -> usize {
-        // compute length of the first Vec (0 if it does not exist)
-        let spikes_duration = spikes.get(0)
-                                            .unwrap_or(&Vec::new())
-                                            .len();
-
-        for neuron_spikes in spikes {
-            if neuron_spikes.len() != spikes_duration {
-                panic!("The number of spikes duration must be equal for each neuron");
-            }
+        It captures the dynamic state of the network (see NetworkSnapshot), e.g. to resume a long
+        simulation later, or to start several experiments from the same state
+        - It panics if the neuron model does not support the snapshots (see Neuron::get_state())
+    */
+    pub fn snapshot(&self) -> NetworkSnapshot {
+        NetworkSnapshot {
+            elapsed: self.elapsed,
+            layers: self.layers.iter().map(|layer| layer.lock().unwrap().snapshot()).collect(),
         }
-        spikes_duration
     }
 
     /**
-        This function encodes the received input spikes in a Vec of **SpikeEvent** to process them.
-     */
-    fn encode_spikes(input_layer_dimension: usize, spikes: &Vec<Vec<u8>>, spikes_duration: usize) -> Vec<SpikeEvent> {
-        let mut spike_events = Vec::<SpikeEvent>::new();
-
-        if spikes.len() != input_layer_dimension {
-            panic!("The number of input spikes is not coherent with the input layer dimension: \
-                    'spikes' must have a Vec for each neuron");
+        It restores a state captured by snapshot() on this network or on a network with the same
+        structure (e.g. a replica), so that process_continue() goes on from it
+        - It panics if the snapshot does not fit the network
+    */
+    pub fn restore(&mut self, snapshot: &NetworkSnapshot) {
+        if snapshot.layers.len() != self.layers.len() {
+            panic!("The snapshot has {} layers, but the network has {}", snapshot.layers.len(), self.layers.len());
         }
 
-        for t in 0..spikes_duration {
-            let mut t_spikes = Vec::<u8>::new();
+        for (index, layer_snapshot) in snapshot.layers.iter().enumerate() {
+            self.layer(index).restore(layer_snapshot);
+        }
+        self.elapsed = snapshot.elapsed;
+    }
 
-            /* retrieve the input spikes for each neuron */
-            for in_neuron_index in 0..spikes.len(){
-                /* check for 0 or 1 only */
-                if spikes[in_neuron_index][t] != 0 && spikes[in_neuron_index][t] != 1 {
-                    panic!("Error: input spike must be 0 or 1 at for N={} at t={}", in_neuron_index, t);
-                }
-                t_spikes.push(spikes[in_neuron_index][t]);
-            }
+    /**
+        It opens a streaming inference on the network, whose input SpikeEvents are pushed one at a time
+        and whose output SpikeEvents are received as soon as the last layer emits them (see SpikeStream)
+        - The network starts from the initial state, which then persists until the stream is reset or closed
+          (and after it, see process_continue())
+    */
+    pub fn stream(&mut self) -> SpikeStream<'_> {
+        let input_layer_dimension = self.get_input_layer_dimension();
+        self.reset();
+        let (input, output, threads) = Processor{}.open_stream(self.layers.iter_mut());
+
+        SpikeStream::new(input, output, threads, input_layer_dimension, &mut self.elapsed)
+    }
 
-            let t_spike_event = SpikeEvent::new(t as u64, t_spikes);
-            spike_events.push(t_spike_event);
+    /**
+        Process input spikes (as in process()) and feed the output spikes to a non-spiking readout layer,
+        returning its continuous-valued outputs (one row for each readout neuron, see ReadoutLayer::integrate())
+        - It panics if the readout input dimension differs from the output layer dimension
+    */
+    pub fn process_readout(&mut self, spikes: &Vec<Vec<u8>>, readout: &ReadoutLayer, mode: ReadoutMode) -> Vec<Vec<f64>> {
+        if readout.get_input_dimension() != self.get_output_layer_dimension() {
+            panic!("The readout input dimension must be equal to the output layer dimension");
         }
 
-        spike_events
+        readout.integrate(&self.process(spikes), mode)
     }
 
     /**
-        This function decodes a Vec of SpikeEvents and returns an output spikes matrix of 0/1
-     */
-    fn decode_spikes(output_layer_dimension: usize, spikes: Vec<SpikeEvent>, spikes_duration: usize) -> Vec<Vec<u8>> {
-        let mut raw_spikes  = vec![vec![0; spikes_duration]; output_layer_dimension];
-
-        for spike_event in spikes {
-            for (out_neuron_index, spike) in spike_event.spikes.into_iter().enumerate() {
-                raw_spikes[out_neuron_index][spike_event.ts as usize] = spike;
-            }
+        It creates an independent copy of the network: unlike clone(), which shares the layers
+        among the copies, each replica owns its layers, so that more replicas can run in parallel
+        (memory-mapped weights are still shared, since they are read-only)
+    */
+    pub fn replicate(&self) -> Self {
+        let layers = self.get_layers().into_iter()
+            .map(|layer| Arc::new(Mutex::new(layer)))
+            .collect();
+
+        Self { layers, elapsed: self.elapsed }
+    }
+
+    /**
+        It creates a new network with the same neurons, whose weights and intra weights are
+        the ones returned by *map* (called with the weights of each layer, false and the weight limit
+        of the layer, then with its intra weights, true and the weight limit)
+        - It panics if the new weights do not comply with the sign policy or the weight limit of their layer
+    */
+    pub(crate) fn map_weights<F: FnMut(Vec<Vec<f64>>, bool, Option<f64>) -> Vec<Vec<f64>>>(&self, mut map: F) -> Self {
+        let layers = self.layers.iter().map(|layer| {
+            let layer = layer.lock().unwrap();
+            let weight_limit = layer.get_weight_limit();
+            let weights = map(layer.get_weights(), false, weight_limit);
+            let intra_weights = map(layer.get_intra_weights(), true, weight_limit);
+
+            let mut new_layer = Layer::new(layer.get_neurons(), weights, intra_weights);
+            new_layer.set_dynamic_synapses(layer.get_dynamic_synapses().cloned());
+            new_layer.set_sign_policy(layer.get_sign_policy());
+            new_layer.set_weight_limit(weight_limit);
+            new_layer.set_wta_mode(layer.get_wta_mode());
+            new_layer.set_recurrence_mode(layer.get_recurrence_mode());
+
+            Arc::new(Mutex::new(new_layer))
+        }).collect();
+
+        Self { layers, elapsed: 0 }
+    }
+
+    /**
+        Process a batch of inputs (each one as in process()), returning an output for each of them.
+        The inputs are split among *num_threads* replicas of the network, running in parallel
+        - The state of the network itself is not modified
+    */
+    pub fn process_batch(&self, inputs: &[Vec<Vec<u8>>], num_threads: usize) -> Vec<Vec<Vec<u8>>> {
+        let num_threads = num_threads.clamp(1, inputs.len().max(1));
+        let chunk_size = inputs.len().div_ceil(num_threads).max(1);
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = inputs.chunks(chunk_size).map(|chunk| {
+                let mut replica = self.replicate();
+                scope.spawn(move || chunk.iter().map(|spikes| replica.process(spikes)).collect::<Vec<_>>())
+            }).collect();
+
+            handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+        })
+    }
+
+    /**
+        Process input spikes (as in process()) injecting the given faults.
+        The faults are injected into a replica of the network, so the network itself is never
+        affected by them (and its state is not modified).
+        - It panics if a fault targets a layer, a neuron or a weight which does not exist
+    */
+    pub fn process_with_faults(&self, spikes: &Vec<Vec<u8>>, faults: &[Fault]) -> Vec<Vec<u8>> {
+        self.faulty_replica(faults).process(spikes)
+    }
+
+    /**
+        Same as process_with_faults(), but the replica starts from the current state of the network
+        (see process_continue()), e.g. to inject the faults into a network warmed up by a previous input,
+        or restored from a snapshot
+        - The time windows of the faults refer to the instants processed since the last reset
+    */
+    pub fn process_continue_with_faults(&self, spikes: &Vec<Vec<u8>>, faults: &[Fault]) -> Vec<Vec<u8>> {
+        self.faulty_replica(faults).process_continue(spikes)
+    }
+
+    /* It creates a replica of the network (with its state) injecting the given faults */
+    fn faulty_replica(&self, faults: &[Fault]) -> Self {
+        let replica = self.replicate();
+
+        if let Some(fault) = faults.iter().find(|fault| fault.layer >= self.layers.len()) {
+            panic!("The fault layer {} does not exist (the network has {} layers)", fault.layer, self.layers.len());
+        }
+
+        for (index, layer) in replica.layers.iter().enumerate() {
+            let layer_faults = faults.iter().filter(|fault| fault.layer == index).copied().collect();
+            layer.lock().unwrap().set_faults(layer_faults);
         }
 
-        raw_spikes
+        replica
     }
 }
 
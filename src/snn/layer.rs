@@ -1,16 +1,86 @@
-/* * private Layer submodule * */
+/* * Layer submodule * */
 
-use crate::snn::neuron::Neuron;
-use std::sync::mpsc::{Receiver, Sender};
+use crate::snn::neuron::{Membrane, Neuron, SynapticInput};
+use crate::snn::snapshot::LayerSnapshot;
 use crate::snn::SpikeEvent;
+use crate::snn::synapses::DynamicSynapses;
+use crate::snn::weights::{self, SignPolicy, WeightMatrix};
+use crate::snn::wta::WtaMode;
+use crate::utils::fixed::FixedFormat;
+use crate::fault::model::{self, Fault, FaultTarget, NeuronFaults, NeuronSite};
 
-/* Object representing a Layer of the Spiking Neural Network */
+/**
+    Trait for the implementation of all the layer kinds.
+    A layer receives the SpikeEvents of the previous layer (or of the network input), one for each
+    instant in which at least one of its inputs fires, in increasing time order; the Processor
+    runs each layer in its own thread, forwarding the events it returns to the next layer.
+*/
+pub trait SpikingLayer: Send {
+    /** Number of inputs of the layer, i.e. the number of spikes of each input SpikeEvent */
+    fn get_input_dimension(&self) -> usize;
+
+    /** Number of outputs of the layer, i.e. the number of spikes of each output SpikeEvent */
+    fn get_output_dimension(&self) -> usize;
+
+    /**
+        It processes the input spikes of an instant, returning the output spikes of the same instant
+        (None if no output fires, so that the next layer is not activated)
+    */
+    fn process_event(&mut self, input_spike_event: &SpikeEvent) -> Option<SpikeEvent>;
+
+    /**
+        It brings the layer up to the given instant (excluded), returning the output spikes of the
+        instants in which it fires without receiving any input (e.g. because of its recurrent activity,
+        or of the synaptic currents of its neurons):
+        the Processor invokes it before each input SpikeEvent and at the end of the input
+        - The default implementation returns no SpikeEvent (the layer fires only when it receives some input)
+    */
+    fn advance_to(&mut self, instant: u64) -> Vec<SpikeEvent> {
+        let _ = instant;
+        vec![]
+    }
+
+    /**
+        Bring the layer to the initial state: it is invoked before each processed input,
+        so that the network can be reused
+    */
+    fn reset(&mut self);
+}
+
+/**
+    Semantics of the intra weights, i.e. of the recurrent links among the neurons of a layer:
+    - Legacy: the neurons receive the output spikes of the last instant in which the layer has been
+      processed, which may be many instants before (the silent instants are skipped), regardless of
+      the elapsed time
+    - Delayed: the output spikes of the instant t reach the neurons in the instant t + 1, as in the
+      clock-driven simulators: if the layer receives no input in t + 1, it is processed anyway with
+      the recurrent input only (so its recurrent activity can go on without input, until the end of it)
+    - Settling(max_iterations): the output spikes reach the neurons in the same instant, which is
+      processed again (from the same initial state) with the new recurrent input, until the output
+      spikes do not change or the maximum number of iterations is reached
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum RecurrenceMode {
+    #[default]
+    Legacy,
+    Delayed,
+    Settling(usize),
+}
+
+/* Object representing a Layer of the Spiking Neural Network (the default SpikingLayer) */
 #[derive(Debug)]
 pub struct Layer<N: Neuron + Clone + Send + 'static> {
     neurons: Vec<N>,                /* neurons of the layer */
-    weights: Vec<Vec<f64>>,         /* weights between the neurons of this layer and the previous one */
-    intra_weights: Vec<Vec<f64>>,   /* weights between the neurons of this layer */
-    prev_output_spikes: Vec<u8>     /* output spikes of the previous instant */
+    weights: WeightMatrix,          /* weights between the neurons of this layer and the previous one */
+    intra_weights: WeightMatrix,    /* weights between the neurons of this layer */
+    prev_output_spikes: Vec<u8>,    /* output spikes of the previous instant */
+    last_instant: Option<u64>,      /* last instant in which the layer has been processed */
+    recurrence: RecurrenceMode,     /* semantics of the intra weights */
+    sign_policy: SignPolicy,        /* policy on the signs of the weights, checked by the setters */
+    weight_limit: Option<f64>,      /* maximum absolute value of the weights (if any), checked by the setters */
+    faults: Vec<Fault>,             /* faults injected into the layer (empty for a healthy layer) */
+    synapses: Option<DynamicSynapses>,  /* dynamic synapses of the extra weights (None for static ones) */
+    wta: WtaMode                        /* winner-take-all competition among the neurons */
 }
 
 impl<N: Neuron + Clone + Send + 'static> Layer<N> {
@@ -18,13 +88,29 @@ impl<N: Neuron + Clone + Send + 'static> Layer<N> {
         neurons: Vec<N>,
         weights: Vec<Vec<f64>>,
         intra_weights: Vec<Vec<f64>>
+    ) -> Self {
+        Self::from_weight_matrices(neurons, WeightMatrix::from(weights), WeightMatrix::from(intra_weights))
+    }
+
+    /** (same as new(), but the weights can be also memory-mapped from a network file) */
+    pub(crate) fn from_weight_matrices(
+        neurons: Vec<N>,
+        weights: WeightMatrix,
+        intra_weights: WeightMatrix
     ) -> Self {
         let num_neurons = neurons.len();
         Self {
             neurons,
             weights,
             intra_weights,
-            prev_output_spikes: vec![0; num_neurons]
+            prev_output_spikes: vec![0; num_neurons],
+            last_instant: None,
+            recurrence: RecurrenceMode::Legacy,
+            sign_policy: SignPolicy::Strict,
+            weight_limit: None,
+            faults: vec![],
+            synapses: None,
+            wta: WtaMode::None
         }
     }
 
@@ -36,92 +122,589 @@ impl<N: Neuron + Clone + Send + 'static> Layer<N> {
     pub fn get_neurons(&self) -> Vec<N> { self.neurons.clone() }
 
     pub fn get_weights(&self) -> Vec<Vec<f64>> {
-        self.weights.clone()
+        self.weights.to_vec()
     }
 
     pub fn get_intra_weights(&self) -> Vec<Vec<f64>> {
-        self.intra_weights.clone()
-    }
-
-    /** It processes the output SpikeEvent(s) coming from the previous layer,
-        according to the model of the Neurons in the network, and sends
-        the resulting spikes to the next layer.
-        - layer_input_rc: is a channel *receiver* from the previous Layer
-        - layer_output_tx: is a channel *sender* to the next Network Layer
-            (or to the SNN itself, if this is the output layer) */
-    pub fn process(&mut self, layer_input_rc: Receiver<SpikeEvent>, layer_output_tx: Sender<SpikeEvent>) {
-        /* initialize data structures, so that the SNN can be reused */
-        self.initialize();
-
-        /* listen to SpikeEvent(s) coming from the previous layer and process them */
-        while let Ok(input_spike_event) = layer_input_rc.recv() {
-            let instant = input_spike_event.ts;    /* time instant of the input spike */
-            let mut output_spikes = Vec::<u8>::with_capacity(self.neurons.len());
-            let mut at_least_one_spike = false;
-
-            /*
-                for each neuron compute the intra and the extra weighted sums,
-                then retrieve the output spike
-            */
-            for (index, neuron) in self.neurons.iter_mut().enumerate() {
-                let mut extra_weighted_sum = 0f64;
-                let mut intra_weighted_sum = 0f64;
-
-                /* compute extra weighted sum */
-                let extra_weights_pairs =
-                    self.weights[index].iter().zip(input_spike_event.spikes.iter());
-
-                for (weight, spike) in extra_weights_pairs {
-                    if *spike != 0 {
-                        extra_weighted_sum += *weight;
-                    }
-                }
+        self.intra_weights.to_vec()
+    }
 
-                /* compute intra weighted sum
-                   (intra_weights[index] contains the weights of the links to the current neuron) */
-                let intra_weights_pairs =
-                    self.intra_weights[index].iter().zip(self.prev_output_spikes.iter());
+    pub fn get_neuron(&self, index: usize) -> &N {
+        self.check_neuron_index(index);
+        &self.neurons[index]
+    }
 
-                for (i, (weight, spike)) in intra_weights_pairs.enumerate() {
-                    /* ignore the reflexive link */
-                    if i != index && *spike != 0 {
-                        intra_weighted_sum += *weight;
-                    }
-                }
+    pub fn get_weight(&self, neuron: usize, input: usize) -> f64 {
+        self.check_weight_index(neuron, input);
+        self.weights.get(neuron, input)
+    }
+
+    pub fn get_intra_weight(&self, neuron: usize, from: usize) -> f64 {
+        self.check_intra_weight_index(neuron, from);
+        self.intra_weights.get(neuron, from)
+    }
+
+    pub fn get_sign_policy(&self) -> SignPolicy {
+        self.sign_policy
+    }
+
+    pub fn get_weight_limit(&self) -> Option<f64> {
+        self.weight_limit
+    }
+
+    /* Setters (with the same checks of the builders, according to the sign policy and the weight limit of the layer) */
+
+    /**
+        It sets the policy on the signs of the weights (Strict for the layers created by Layer::new())
+        - It panics if the current weights do not comply with it
+    */
+    pub fn set_sign_policy(&mut self, sign_policy: SignPolicy) {
+        Self::check_values(sign_policy.validate(&self.weights.to_vec(), false));
+        Self::check_values(sign_policy.validate(&self.intra_weights.to_vec(), true));
+        self.sign_policy = sign_policy;
+    }
+
+    /**
+        It sets the maximum absolute value of the weights and of the intra weights (no limit for the layers
+        created by Layer::new())
+        - It panics if the current weights exceed it
+    */
+    pub fn set_weight_limit(&mut self, weight_limit: Option<f64>) {
+        Self::check_values(weights::validate_limit(&self.weights.to_vec(), weight_limit));
+        Self::check_values(weights::validate_limit(&self.intra_weights.to_vec(), weight_limit));
+        self.weight_limit = weight_limit;
+    }
+
+    /**
+        It replaces a neuron of the layer (its state is kept until the next process)
+        - In a winner-take-all layer, it panics if the neuron model does not support it (see set_wta_mode())
+    */
+    pub fn set_neuron(&mut self, index: usize, neuron: N) {
+        self.check_neuron_index(index);
+        if self.wta != WtaMode::None && !Self::supports_wta(&neuron) {
+            panic!("The neuron model does not support the winner-take-all layers");
+        }
+        self.neurons[index] = neuron;
+    }
+
+    /** It sets the weight of the link from the input *input* to the neuron *neuron* */
+    pub fn set_weight(&mut self, neuron: usize, input: usize, weight: f64) {
+        self.check_weight_index(neuron, input);
+        let column: Vec<f64> = (0..self.neurons.len())
+            .map(|i| if i == neuron { weight } else { self.weights.get(i, input) })
+            .collect();
+        Self::check_values(self.sign_policy.validate_column(&column, input, false));
+        Self::check_values(weights::validate_limit(&[vec![weight]], self.weight_limit));
+        self.weights.set(neuron, input, weight);
+    }
+
+    /** It sets the weight of the link from the neuron *from* to the neuron *neuron* of the layer */
+    pub fn set_intra_weight(&mut self, neuron: usize, from: usize, weight: f64) {
+        self.check_intra_weight_index(neuron, from);
+        let column: Vec<f64> = (0..self.neurons.len())
+            .map(|i| if i == from { 0.0 } else if i == neuron { weight } else { self.intra_weights.get(i, from) })
+            .collect();
+        Self::check_values(self.sign_policy.validate_column(&column, from, true));
+        Self::check_values(weights::validate_limit(&[vec![weight]], self.weight_limit));
+        self.intra_weights.set(neuron, from, weight);
+    }
+
+    /** It replaces the weights matrix, which must have the same dimensions of the current one */
+    pub fn set_weights(&mut self, weights: Vec<Vec<f64>>) {
+        if weights.len() != self.neurons.len() {
+            panic!("The number of neurons must be equal to the number of rows of the weights matrix");
+        }
+        for row in &weights {
+            if row.len() != self.weights.cols() {
+                panic!("The number of columns in the weights matrix must be equal to the number of neurons of the previous layer");
+            }
+        }
+        Self::check_values(self.sign_policy.validate(&weights, false));
+        Self::check_values(weights::validate_limit(&weights, self.weight_limit));
+        self.weights = WeightMatrix::from(weights);
+    }
+
+    /** It replaces the intra weights matrix, which must be (neurons x neurons) */
+    pub fn set_intra_weights(&mut self, intra_weights: Vec<Vec<f64>>) {
+        if intra_weights.len() != self.neurons.len() {
+            panic!("The number of neurons must be equal to the number of rows of the intra weights matrix");
+        }
+        for row in &intra_weights {
+            if row.len() != self.neurons.len() {
+                panic!("The number of neurons must be equal to the number of columns of the intra weights matrix");
+            }
+        }
+        Self::check_values(self.sign_policy.validate(&intra_weights, true));
+        Self::check_values(weights::validate_limit(&intra_weights, self.weight_limit));
+        self.intra_weights = WeightMatrix::from(intra_weights);
+    }
+
+    /*
+        With Dale's law, it checks that the outgoing weights of the neurons of this layer have the same sign
+        in the layer and towards the *next* one, replacing the intra weights of this layer and/or the weights
+        of the next one with the given ones (done by the setters of the network, before replacing them)
+    */
+    pub(crate) fn check_across_layers(&self, next: &Layer<N>, intra_weights: Option<&[Vec<f64>]>,
+                                      next_weights: Option<&[Vec<f64>]>) {
+        if self.sign_policy != SignPolicy::Dale {
+            return;
+        }
+
+        let intra_weights = intra_weights.map_or_else(|| self.intra_weights.to_vec(), |weights| weights.to_vec());
+        let next_weights = next_weights.map_or_else(|| next.weights.to_vec(), |weights| weights.to_vec());
+        Self::check_values(self.sign_policy.validate_across_layers(&intra_weights, &next_weights));
+    }
+
+    /*
+        (same as check_across_layers(), for the outgoing weights of the neuron *from* only, replacing its
+        intra weight towards the neuron *intra.0* with *intra.1*, or its weight towards the neuron *next.0*
+        of the next layer with *next.1*)
+    */
+    pub(crate) fn check_outgoing_weights(&self, next_layer: &Layer<N>, from: usize, intra: Option<(usize, f64)>,
+                                         next: Option<(usize, f64)>) {
+        if self.sign_policy != SignPolicy::Dale {
+            return;
+        }
+
+        if let Some((neuron, _)) = intra {
+            self.check_intra_weight_index(neuron, from);
+        }
+        if let Some((neuron, _)) = next {
+            next_layer.check_weight_index(neuron, from);
+        }
+
+        let replace = |replaced: Option<(usize, f64)>, i: usize, weight: f64| match replaced {
+            Some((neuron, new_weight)) if neuron == i => new_weight,
+            _ => weight,
+        };
+        let outgoing: Vec<f64> = (0..self.neurons.len())
+            .map(|i| if i == from { 0.0 } else { replace(intra, i, self.intra_weights.get(i, from)) })
+            .chain((0..next_layer.neurons.len()).map(|i| replace(next, i, next_layer.weights.get(i, from))))
+            .collect();
+        Self::check_values(self.sign_policy.validate_outgoing_weights(&outgoing));
+    }
+
+    pub fn get_dynamic_synapses(&self) -> Option<&DynamicSynapses> {
+        self.synapses.as_ref()
+    }
+
+    /**
+        It makes the extra-layer connections dynamic (see DynamicSynapses), or static again with None
+        - It panics if the synapses do not have the dimensions of the weights matrix
+    */
+    pub fn set_dynamic_synapses(&mut self, synapses: Option<DynamicSynapses>) {
+        if let Some(synapses) = &synapses {
+            if synapses.get_neurons_number() != self.neurons.len() || synapses.get_inputs_number() != self.weights.cols() {
+                panic!("The dynamic synapses must have the dimensions of the weights matrix");
+            }
+        }
+        self.synapses = synapses;
+    }
+
+    pub fn get_wta_mode(&self) -> WtaMode {
+        self.wta
+    }
+
+    /**
+        It sets the winner-take-all competition among the neurons of the layer (WtaMode::None by default)
+        - The faults of the neurons cannot be injected into a winner-take-all layer (the ones of the weights can)
+        - It panics if the mode has an invalid parameter, if the neuron model does not support it
+          (see Neuron::integrate()) or if some faults of the neurons are injected into the layer
+    */
+    pub fn set_wta_mode(&mut self, wta: WtaMode) {
+        wta.validate();
+        Self::check_values(self.validate_wta_mode(wta));
+        self.wta = wta;
+    }
+
+    /*
+        It checks that the neurons of the layer can compete with the given mode (see supports_wta())
+        and that no fault of the neurons is injected into the layer
+    */
+    pub(crate) fn validate_wta_mode(&self, wta: WtaMode) -> Result<(), String> {
+        if wta == WtaMode::None {
+            return Ok(());
+        }
+
+        if let Some(fault) = self.faults.iter().find(|fault| matches!(fault.target, FaultTarget::Neuron { .. })) {
+            return Err(format!("The layer {} is a winner-take-all layer, which does not support the neuron faults",
+                               fault.layer));
+        }
+
+        if !self.neurons.iter().all(Self::supports_wta) {
+            return Err("The neuron model does not support the winner-take-all layers".to_string());
+        }
+        Ok(())
+    }
+
+    /* It probes Neuron::integrate() on a copy of the neuron in its initial state */
+    fn supports_wta(neuron: &N) -> bool {
+        let mut probe = neuron.clone();
+        probe.initialize();
+        probe.integrate(0, &SynapticInput::default()).is_some()
+    }
+
+    pub fn get_recurrence_mode(&self) -> RecurrenceMode {
+        self.recurrence
+    }
+
+    /**
+        It sets the semantics of the intra weights (RecurrenceMode::Legacy by default)
+        - It panics if the maximum number of iterations of the settling mode is 0
+    */
+    pub fn set_recurrence_mode(&mut self, recurrence: RecurrenceMode) {
+        if recurrence == RecurrenceMode::Settling(0) {
+            panic!("The maximum number of iterations must be positive");
+        }
+        self.recurrence = recurrence;
+    }
+
+    /**
+        It captures the dynamic state of the layer (see LayerSnapshot)
+        - It panics if the neuron model does not support the snapshots (see Neuron::get_state())
+    */
+    pub fn snapshot(&self) -> LayerSnapshot {
+        let neurons = self.neurons.iter().map(|neuron| match neuron.get_state() {
+            Some(state) => state,
+            None => panic!("The neuron model does not support the snapshots"),
+        }).collect();
 
-                /* compute membrane potential and determine if the Neuron fires or not */
-                let neuron_spike = neuron.compute_v_mem(instant, extra_weighted_sum, intra_weighted_sum);
-                output_spikes.push(neuron_spike);
+        LayerSnapshot {
+            neurons,
+            prev_output_spikes: self.prev_output_spikes.clone(),
+            last_instant: self.last_instant,
+            synapses: self.synapses.as_ref().map(|synapses| synapses.get_state()),
+        }
+    }
+
+    /**
+        It restores a state captured by snapshot(), on this layer or on a layer with the same structure
+        - It panics if the snapshot does not fit the layer (number of neurons, dynamic synapses)
+    */
+    pub fn restore(&mut self, snapshot: &LayerSnapshot) {
+        if snapshot.neurons.len() != self.neurons.len() {
+            panic!("The snapshot has {} neurons, but the layer has {}", snapshot.neurons.len(), self.neurons.len());
+        }
+        if !snapshot.prev_output_spikes.is_empty() && snapshot.prev_output_spikes.len() != self.neurons.len() {
+            panic!("The output spikes of the snapshot must be one for each neuron");
+        }
+        match (self.synapses.as_mut(), &snapshot.synapses) {
+            (Some(synapses), Some(state)) => synapses.set_state(state),
+            (None, None) => {},
+            _ => panic!("The snapshot and the layer must both have (or not have) the dynamic synapses"),
+        }
+
+        for (neuron, state) in self.neurons.iter_mut().zip(&snapshot.neurons) {
+            neuron.set_state(state);
+        }
+        self.prev_output_spikes = snapshot.prev_output_spikes.clone();
+        self.last_instant = snapshot.last_instant;
+    }
+
+    pub(crate) fn neurons(&self) -> &Vec<N> { &self.neurons }
 
-                if !at_least_one_spike && neuron_spike == 1u8 {
-                    at_least_one_spike = true;
+    pub(crate) fn weight_matrix(&self) -> &WeightMatrix { &self.weights }
+
+    pub(crate) fn intra_weight_matrix(&self) -> &WeightMatrix { &self.intra_weights }
+
+    /**
+        It sets the faults injected into the layer (the layer index of the faults is not checked).
+        - It panics if a fault targets a neuron or a weight which does not exist, or a neuron whose model
+          does not support the neuron faults (see Neuron::get_fault_width()) or of a winner-take-all layer
+    */
+    pub(crate) fn set_faults(&mut self, faults: Vec<Fault>) {
+        for fault in &faults {
+            let (neuron, col, cols) = match fault.target {
+                FaultTarget::Weight { neuron, input } => (neuron, input, self.weights.cols()),
+                FaultTarget::IntraWeight { neuron, from } => (neuron, from, self.neurons.len()),
+                FaultTarget::Neuron { neuron, .. } => (neuron, 0, 1),
+            };
+
+            if neuron >= self.neurons.len() || col >= cols {
+                panic!("The fault target {:?} does not exist in layer {}", fault.target, fault.layer);
+            }
+
+            if let FaultTarget::Neuron { neuron, site } = fault.target {
+                if self.wta != WtaMode::None {
+                    panic!("The layer {} is a winner-take-all layer, which does not support the neuron faults", fault.layer);
+                }
+                match self.neurons[neuron].get_fault_width() {
+                    None => panic!("The neuron model of layer {} does not support the neuron faults", fault.layer),
+                    Some(width) if site != NeuronSite::Comparator && fault.bit as u32 >= width =>
+                        panic!("The bit {} does not exist in the neuron {} of layer {} ({} bits)", fault.bit, neuron, fault.layer, width),
+                    _ => {},
                 }
             }
+        }
+
+        self.faults = faults;
+    }
 
-            /* save output spikes for later */
-            self.prev_output_spikes = output_spikes.clone();
+    /*
+        It computes the extra weighted sum of the input spikes and the intra weighted sum of the
+        recurrent spikes (see RecurrenceMode) for the neuron *index*, also split into their
+        positive and negative contributes
+        - The errors of the faulty weights (if any) are added to the extra and the intra weighted sums,
+          and to the positive or the negative contributes according to their sign
+        - For the fixed-point neurons (see Neuron::get_fixed_format()) the extra and the intra weighted sums
+          are accumulated in their format, with the faulty weights in place of the correct ones
+    */
+    fn synaptic_input(&self, index: usize, instant: u64, spikes: &[u8], recurrent: &[u8]) -> SynapticInput {
+        /* compute extra weighted sum (split into its positive and negative contributes) */
+        let (mut extra_excitatory, mut extra_inhibitory) = match &self.synapses {
+            Some(synapses) => synapses.signed_weighted_sums(&self.weights, index, spikes),
+            None => self.weights.signed_weighted_sums(index, spikes, None),
+        };
 
-            /* check if at least one neuron fired - if not, not send any spike */
-            if !at_least_one_spike {
-                continue;
+        /* compute intra weighted sum
+           (intra_weights[index] contains the weights of the links to the current neuron),
+           ignoring the reflexive link */
+        let (mut intra_excitatory, mut intra_inhibitory) =
+            self.intra_weights.signed_weighted_sums(index, recurrent, Some(index));
+
+        if !self.faults.is_empty() {
+            /* (the faulty weights replace the correct ones inside the weighted sums) */
+            let add_error = |error: f64, excitatory: &mut f64, inhibitory: &mut f64| {
+                if error < 0.0 { *inhibitory += error } else { *excitatory += error }
+            };
+            add_error(model::weighted_sum_error(&self.faults, &self.weights, false, index, spikes, None, instant),
+                      &mut extra_excitatory, &mut extra_inhibitory);
+            add_error(model::weighted_sum_error(&self.faults, &self.intra_weights, true, index,
+                                                recurrent, Some(index), instant),
+                      &mut intra_excitatory, &mut intra_inhibitory);
+        }
+
+        let (extra_weighted_sum, intra_weighted_sum) = match self.neurons[index].get_fixed_format() {
+            /* (the raw sums are exact in f64, see FixedFormat::MAX_WIDTH) */
+            Some(format) => (format.to_f64(self.fixed_weighted_sum(&format, false, index, instant, spikes)),
+                             format.to_f64(self.fixed_weighted_sum(&format, true, index, instant, recurrent))),
+            None => (extra_excitatory + extra_inhibitory, intra_excitatory + intra_inhibitory),
+        };
+
+        SynapticInput {
+            extra_weighted_sum,
+            intra_weighted_sum,
+            excitatory: extra_excitatory + intra_excitatory,
+            inhibitory: extra_inhibitory + intra_inhibitory,
+        }
+    }
+
+    /*
+        It accumulates the extra (or the intra) weighted sum of the neuron *index* in the fixed-point *format*
+        (see WeightMatrix::fixed_weighted_sum()), using the faulty weights (if any) and the efficacy of the
+        dynamic synapses (if any)
+    */
+    fn fixed_weighted_sum(&self, format: &FixedFormat, intra: bool, index: usize, instant: u64, spikes: &[u8]) -> i64 {
+        let weight = |col: usize, weight: f64| {
+            let weight = if self.faults.is_empty() {
+                weight
+            } else {
+                model::faulty_weight(&self.faults, intra, index, col, weight, instant)
+            };
+            match &self.synapses {
+                Some(synapses) if !intra => weight * synapses.get_efficacy(index, col),
+                _ => weight,
             }
-            /* at least one neuron fired -> send output spikes to the next layer */
+        };
 
-            let output_spike_event = SpikeEvent::new(instant, output_spikes);
+        if intra {
+            self.intra_weights.fixed_weighted_sum(index, spikes, Some(index), format, weight)
+        } else {
+            self.weights.fixed_weighted_sum(index, spikes, None, format, weight)
+        }
+    }
 
-            layer_output_tx.send(output_spike_event)
-                .expect(&format!("Unexpected error sending input spike event t={}", instant));
+    /*
+        It updates all the neurons with the input spikes and the recurrent spikes of an instant,
+        returning their output spikes
+    */
+    fn step(&mut self, instant: u64, spikes: &[u8], recurrent: &[u8]) -> Vec<u8> {
+        if self.wta != WtaMode::None {
+            return self.compete(instant, spikes, recurrent);
         }
 
         /*
-            we don't need to drop the sender, because it will be
-            automatically dropped when the layer goes out of scope
+            for each neuron compute the intra and the extra weighted sums,
+            then retrieve the output spike
         */
+        (0..self.neurons.len()).map(|index| {
+            /* compute membrane potential and determine if the Neuron fires or not */
+            let input = self.synaptic_input(index, instant, spikes, recurrent);
+            if self.faults.is_empty() {
+                self.neurons[index].compute_v_mem_with_input(instant, &input)
+            } else {
+                self.neurons[index].compute_v_mem_with_faults(instant, &input, &NeuronFaults::new(&self.faults, index, instant))
+            }
+        }).collect()
+    }
+
+    /*
+        Same as step(), for a winner-take-all layer: all the neurons integrate their input,
+        then the competition decides which ones fire (see WtaMode)
+    */
+    fn compete(&mut self, instant: u64, spikes: &[u8], recurrent: &[u8]) -> Vec<u8> {
+        let mut membranes = Vec::<Membrane>::with_capacity(self.neurons.len());
+
+        for index in 0..self.neurons.len() {
+            let input = self.synaptic_input(index, instant, spikes, recurrent);
+            /* (probed by set_wta_mode() and set_neuron()) */
+            match self.neurons[index].integrate(instant, &input) {
+                Some(membrane) => membranes.push(membrane),
+                None => panic!("The neuron model does not support the winner-take-all layers"),
+            }
+        }
+
+        let (output_spikes, potentials) = self.wta.resolve(&membranes);
+        for (neuron, potential) in self.neurons.iter_mut().zip(potentials) {
+            neuron.set_v_mem(potential);
+        }
+
+        output_spikes
+    }
+
+    /* Same as step(), with the recurrence of the previous instant (see RecurrenceMode::Delayed) */
+    fn step_delayed(&mut self, instant: u64, spikes: &[u8]) -> Vec<u8> {
+        /* (the spikes of the last processed instant are recurrent only if it is the previous one) */
+        let recurrent = if self.last_instant.is_some_and(|last| last + 1 == instant) {
+            std::mem::take(&mut self.prev_output_spikes)
+        } else {
+            vec![]
+        };
+        self.step(instant, spikes, &recurrent)
+    }
+
+    /* Same as step(), with the same-instant recurrence (see RecurrenceMode::Settling) */
+    fn settle(&mut self, instant: u64, spikes: &[u8], max_iterations: usize) -> Vec<u8> {
+        let initial_neurons = self.neurons.clone();
+        let mut recurrent = vec![0u8; self.neurons.len()];
+
+        for iteration in 1..=max_iterations {
+            let output_spikes = self.step(instant, spikes, &recurrent);
+
+            if output_spikes == recurrent || iteration == max_iterations {
+                return output_spikes;
+            }
+
+            /* (the instant is processed again from the same initial state) */
+            self.neurons.clone_from(&initial_neurons);
+            recurrent = output_spikes;
+        }
+
+        recurrent
+    }
+
+    /* It saves the output spikes of the instant, returning them as a SpikeEvent if at least one neuron fired */
+    fn emit(&mut self, instant: u64, output_spikes: Vec<u8>) -> Option<SpikeEvent> {
+        /* save output spikes for later */
+        self.prev_output_spikes = output_spikes.clone();
+        self.last_instant = Some(instant);
+
+        /* check if at least one neuron fired - if not, not send any spike */
+        if output_spikes.iter().all(|spike| *spike == 0) {
+            return None;
+        }
+
+        Some(SpikeEvent::new(instant, output_spikes))
+    }
+
+    /* It panics with the description of the first invalid weight, if any (see SignPolicy) */
+    fn check_values(result: Result<(), String>) {
+        if let Err(message) = result {
+            panic!("{}", message);
+        }
+    }
+
+    fn check_neuron_index(&self, index: usize) {
+        if index >= self.neurons.len() {
+            panic!("The neuron {} does not exist (the layer has {} neurons)", index, self.neurons.len());
+        }
     }
 
-    fn initialize(&mut self) {
+    fn check_weight_index(&self, neuron: usize, input: usize) {
+        self.check_neuron_index(neuron);
+        if input >= self.weights.cols() {
+            panic!("The input {} does not exist (the layer has {} inputs)", input, self.weights.cols());
+        }
+    }
+
+    fn check_intra_weight_index(&self, neuron: usize, from: usize) {
+        self.check_neuron_index(neuron);
+        self.check_neuron_index(from);
+    }
+
+}
+
+impl<N: Neuron + Clone + Send + 'static> SpikingLayer for Layer<N> {
+    fn get_input_dimension(&self) -> usize {
+        self.weights.cols()
+    }
+
+    fn get_output_dimension(&self) -> usize {
+        self.neurons.len()
+    }
+
+    /** It processes the SpikeEvent coming from the previous layer, according to the model of the
+        Neurons in the network, and returns the resulting spikes (if at least one neuron fires) */
+    fn process_event(&mut self, input_spike_event: &SpikeEvent) -> Option<SpikeEvent> {
+        let instant = input_spike_event.ts;    /* time instant of the input spike */
+
+        /* update the dynamic synapses (if any) with the input spikes */
+        if let Some(synapses) = self.synapses.as_mut() {
+            synapses.deliver(instant, &input_spike_event.spikes);
+        }
+
+        let output_spikes = match self.recurrence {
+            RecurrenceMode::Legacy => {
+                let recurrent = std::mem::take(&mut self.prev_output_spikes);
+                self.step(instant, &input_spike_event.spikes, &recurrent)
+            },
+            RecurrenceMode::Delayed => self.step_delayed(instant, &input_spike_event.spikes),
+            RecurrenceMode::Settling(max_iterations) => self.settle(instant, &input_spike_event.spikes, max_iterations),
+        };
+
+        self.emit(instant, output_spikes)
+    }
+
+    /*
+        The layer can fire without input while some neurons are active (see Neuron::is_active()),
+        or, in the delayed recurrence mode, while it receives the spikes of the previous instant
+    */
+    fn advance_to(&mut self, instant: u64) -> Vec<SpikeEvent> {
+        let mut output_events = vec![];
+
+        let no_input = vec![0u8; self.weights.cols()];
+        while let Some(last) = self.last_instant {
+            let recurrent_spikes = self.recurrence == RecurrenceMode::Delayed && self.prev_output_spikes.contains(&1);
+            if last + 1 >= instant || !(recurrent_spikes || self.neurons.iter().any(|neuron| neuron.is_active())) {
+                break;
+            }
+
+            let output_spikes = match self.recurrence {
+                RecurrenceMode::Legacy => {
+                    /* (the recurrent spikes wait for the next instant with some input, as if this one were skipped) */
+                    let output_spikes = self.step(last + 1, &no_input, &[]);
+                    if !output_spikes.contains(&1) {
+                        self.last_instant = Some(last + 1);
+                        continue;
+                    }
+                    output_spikes
+                },
+                RecurrenceMode::Delayed => self.step_delayed(last + 1, &no_input),
+                RecurrenceMode::Settling(max_iterations) => self.settle(last + 1, &no_input, max_iterations),
+            };
+            output_events.extend(self.emit(last + 1, output_spikes));
+        }
+
+        output_events
+    }
+
+    fn reset(&mut self) {
         self.prev_output_spikes.clear();    /* reset prev_output_spikes */
+        self.last_instant = None;
         self.neurons.iter_mut().for_each(|neuron| neuron.initialize());  /* reset neurons */
+        if let Some(synapses) = self.synapses.as_mut() {
+            synapses.reset();   /* reset dynamic synapses */
+        }
     }
 }
 
@@ -135,7 +718,14 @@ impl<N: Neuron + Clone + Send + 'static> Clone for Layer<N> {
             neurons: self.neurons.clone(),
             weights: self.weights.clone(),
             intra_weights: self.intra_weights.clone(),
-            prev_output_spikes: self.prev_output_spikes.clone()
+            prev_output_spikes: self.prev_output_spikes.clone(),
+            last_instant: self.last_instant,
+            recurrence: self.recurrence,
+            sign_policy: self.sign_policy,
+            weight_limit: self.weight_limit,
+            faults: self.faults.clone(),
+            synapses: self.synapses.clone(),
+            wta: self.wta
         }
     }
 }
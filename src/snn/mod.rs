@@ -1,11 +1,22 @@
 
 pub mod builders;
-    mod layer; /* private */
+pub mod connectivity;
+pub mod layer;
 pub mod neuron;
+pub mod readout;
+pub mod snapshot;
+pub mod stream;
+pub mod synapses;
+pub mod weights;
+pub mod wta;
     mod dyn_snn;
+    mod network;
     mod snn;
     mod processor;
 
+pub use self::dyn_snn::DynSNN;
+pub use self::network::SpikingNetwork;
+
 /**
     Object representing the output spikes generated by a single layer
 */
@@ -19,4 +30,13 @@ impl SpikeEvent {
     pub fn new(ts: u64, spikes: Vec<u8>) -> Self {
         Self { ts, spikes }
     }
+
+    /* Getters */
+    pub fn get_ts(&self) -> u64 {
+        self.ts
+    }
+
+    pub fn get_spikes(&self) -> &Vec<u8> {
+        &self.spikes
+    }
 }
@@ -0,0 +1,128 @@
+use std::slice::IterMut;
+use std::sync::{Arc, Mutex};
+use crate::neuron::Neuron;
+use crate::snn::dyn_snn::DynSNN;
+use crate::snn::layer::SpikingLayer;
+use crate::snn::processor::Processor;
+use crate::snn::stream::SpikeStream;
+
+/* * Spiking Network of generic layers * */
+
+/**
+    Object representing a Spiking Neural Network made of layers of different kinds, each one
+    implementing the SpikingLayer trait (e.g. the default Layer, or user-defined readout, convolutional
+    or plastic layers).
+    Like DynSNN, all the checks are done at *runtime*.
+*/
+#[derive(Default)]
+pub struct SpikingNetwork {
+    layers: Vec<Arc<Mutex<dyn SpikingLayer>>>,
+    elapsed: u64    /* number of instants processed since the last reset */
+}
+
+impl SpikingNetwork {
+    pub fn new() -> Self {
+        Self { layers: Vec::new(), elapsed: 0 }
+    }
+
+    /**
+        It appends a (pre-constructed) layer to the network.
+        - It panics if the input dimension of the layer differs from the output dimension of the last layer
+    */
+    pub fn add_layer<L: SpikingLayer + 'static>(mut self, layer: L) -> Self {
+        if let Some(last_layer) = self.layers.last() {
+            let output_dimension = last_layer.lock().unwrap().get_output_dimension();
+
+            if layer.get_input_dimension() != output_dimension {
+                panic!("The input dimension of the layer ({}) must be equal to the output dimension \
+                        of the previous layer ({})", layer.get_input_dimension(), output_dimension);
+            }
+        }
+
+        self.layers.push(Arc::new(Mutex::new(layer)));
+        self
+    }
+
+    /* Getters */
+    pub fn get_layers_number(&self) -> usize {
+        self.layers.len()
+    }
+
+    /** - It panics if the network has no layers */
+    pub fn get_input_layer_dimension(&self) -> usize {
+        self.layers.first().expect("The network must have at least one layer").lock().unwrap().get_input_dimension()
+    }
+
+    /** - It panics if the network has no layers */
+    pub fn get_output_layer_dimension(&self) -> usize {
+        self.layers.last().expect("The network must have at least one layer").lock().unwrap().get_output_dimension()
+    }
+
+    /** It opens a streaming inference on the network (same as DynSNN::stream()) */
+    pub fn stream(&mut self) -> SpikeStream<'_> {
+        let input_layer_dimension = self.get_input_layer_dimension();
+        self.reset();
+        let (input, output, threads) = Processor{}.open_stream(self.layers.iter_mut());
+
+        SpikeStream::new(input, output, threads, input_layer_dimension, &mut self.elapsed)
+    }
+
+    /**
+        Actually process input spikes by means of the network and produce corresponding output spikes
+        (same as DynSNN::process())
+    */
+    pub fn process(&mut self, spikes: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+        self.reset();
+        self.process_continue(spikes)
+    }
+
+    /** Same as process(), starting from the state reached at the end of the previous input (see DynSNN::process_continue()) */
+    pub fn process_continue(&mut self, spikes: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+        // * check and compute the spikes duration *
+        let spikes_duration = Processor::compute_spikes_duration(spikes);
+
+        let input_layer_dimension = self.get_input_layer_dimension();
+        let output_layer_dimension = self.get_output_layer_dimension();
+
+        // * encode spikes into SpikeEvent(s) *
+        let input_spike_events = Processor::encode_spikes(input_layer_dimension, spikes, spikes_duration);
+
+        // * process input *
+        let processor = Processor{};
+        let output_spike_events = processor.process_events(self.layers.iter_mut(), input_spike_events,
+                                                           self.elapsed, spikes_duration as u64);
+        self.elapsed += spikes_duration as u64;
+
+        // * decode output into array shape *
+        Processor::decode_spikes(output_layer_dimension, output_spike_events, spikes_duration)
+    }
+
+    /** Bring all the layers to the initial state (see DynSNN::reset()) */
+    pub fn reset(&mut self) {
+        self.layers.iter().for_each(|layer| layer.lock().unwrap().reset());
+        self.elapsed = 0;
+    }
+
+    /** Number of instants processed since the last reset (see DynSNN::get_elapsed_instants()) */
+    pub fn get_elapsed_instants(&self) -> u64 {
+        self.elapsed
+    }
+}
+
+/**
+    A DynSNN becomes a SpikingNetwork with a copy of its layers, so that other kinds of layers can be added
+*/
+impl<N: Neuron + Clone + 'static> From<&DynSNN<N>> for SpikingNetwork {
+    fn from(snn: &DynSNN<N>) -> Self {
+        snn.get_layers().into_iter().fold(SpikingNetwork::new(), |network, layer| network.add_layer(layer))
+    }
+}
+
+impl<'a> IntoIterator for &'a mut SpikingNetwork {
+    type Item = &'a mut Arc<Mutex<dyn SpikingLayer>>;
+    type IntoIter = IterMut<'a, Arc<Mutex<dyn SpikingLayer>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.layers.iter_mut()
+    }
+}
@@ -1,5 +1,9 @@
 /* * Neuron submodule * */
 
+use std::any::Any;
+use crate::fault::model::FaultInjector;
+use crate::utils::fixed::FixedFormat;
+
 /**
     Trait for the implementation of all the Neuron models.
     It represents a general Neuron of a Layer
@@ -15,8 +19,218 @@ pub trait Neuron: Send {
     */
     fn compute_v_mem(&mut self, t: u64, extra_weighted_sum: f64, intra_weighted_sum: f64) -> u8;
 
+    /**
+        Same as compute_v_mem(), but the neuron receives the whole synaptic input of the instant,
+        which carries also the excitatory and the inhibitory currents separately (see SynapticInput):
+        it is the method invoked by the Layer.
+        - The default implementation uses only the extra and the intra weighted sums
+    */
+    fn compute_v_mem_with_input(&mut self, t: u64, input: &SynapticInput) -> u8 {
+        self.compute_v_mem(t, input.extra_weighted_sum, input.intra_weighted_sum)
+    }
+
+    /**
+        Same as compute_v_mem_with_input(), but the parameters, the state and the outputs of the internal
+        operations of the neuron are read through the *injector*, which applies the active faults
+        (see DynSNN::process_with_faults()); the errors of the faulty weights are already in the input.
+        - The default implementation ignores the injector: it is invoked only if the layer has no
+          neuron faults, since they are rejected for the models not supporting them (see get_fault_width())
+    */
+    fn compute_v_mem_with_faults(&mut self, t: u64, input: &SynapticInput, injector: &dyn FaultInjector) -> u8 {
+        let _ = injector;
+        self.compute_v_mem_with_input(t, input)
+    }
+
+    /**
+        Number of bits of the elements of the neuron which can be affected by the faults (see NeuronSite),
+        e.g. 64 for the f64 values, or the width of the registers of a fixed-point model
+        - The default implementation returns None: such models do not support the neuron faults
+    */
+    fn get_fault_width(&self) -> Option<u32> {
+        None
+    }
+
+    /**
+        Fixed-point format of the neuron, in which the layer accumulates its weighted sums
+        (see WeightMatrix::fixed_weighted_sum()), as the accumulator of a hardware implementation
+        - The default implementation returns None: the weighted sums are accumulated in f64
+    */
+    fn get_fixed_format(&self) -> Option<FixedFormat> {
+        None
+    }
+
+    /**
+        First phase of the update of a neuron of a winner-take-all layer (see WtaMode): it integrates the
+        input of the instant as compute_v_mem_with_input() does, but without firing, and returns the
+        state of the membrane; then the layer decides which neurons fire (and resets them with set_v_mem()).
+        - The default implementation returns None: such models cannot be used in a winner-take-all layer
+    */
+    fn integrate(&mut self, t: u64, input: &SynapticInput) -> Option<Membrane> {
+        let _ = (t, input);
+        None
+    }
+
+    /** It sets the membrane potential (used by the winner-take-all layers, see integrate()) */
+    fn set_v_mem(&mut self, v_mem: f64) {
+        let _ = v_mem;
+    }
+
+    /**
+        It returns true if the neuron can fire without receiving any input spike (e.g. because of its
+        synaptic currents), so that the layer processes it also in the instants without input
+        (see SpikingLayer::advance_to())
+        - The default implementation returns false: the neuron can fire only when it receives some input
+    */
+    fn is_active(&self) -> bool {
+        false
+    }
+
+    /**
+        It returns the dynamic state of the neuron (e.g. the membrane potential and the last update
+        instant), so that it can be stored in a snapshot of the network (see NetworkSnapshot)
+        - The default implementation returns None: such models cannot be used in a snapshot
+    */
+    fn get_state(&self) -> Option<Vec<f64>> {
+        None
+    }
+
+    /** It restores a state returned by get_state() (for a neuron of the same model) */
+    fn set_state(&mut self, state: &[f64]) {
+        let _ = state;
+    }
+
     /**
         Bring the Neuron to the initial state: initialize all data structures
      */
     fn initialize(&mut self);
-}
\ No newline at end of file
+}
+
+/**
+    It checks that a state to restore (see Neuron::set_state()) has the expected number of values
+    - It panics otherwise
+*/
+pub fn check_state(state: &[f64], length: usize) {
+    if state.len() != length {
+        panic!("The state of the neuron must have {} values, not {}", length, state.len());
+    }
+}
+
+/** State of the membrane of a neuron, after the integration of the input of an instant */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Membrane {
+    pub v_mem: f64,     /* membrane potential (not reset yet) */
+    pub v_th: f64,      /* threshold potential */
+    pub v_reset: f64,   /* potential after a spike */
+}
+
+/**
+    Synaptic input received by a neuron in an instant: the same spikes are summed both by origin
+    (extra and intra weights) and by sign (excitatory and inhibitory weights)
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SynapticInput {
+    pub extra_weighted_sum: f64,    /* weighted sum of the input spikes (from the previous layer) */
+    pub intra_weighted_sum: f64,    /* weighted sum of the previous output spikes of the layer */
+    pub excitatory: f64,            /* sum of the positive weights of both the sums (>= 0) */
+    pub inhibitory: f64,            /* sum of the negative weights of both the sums (<= 0) */
+}
+
+/**
+    Trait of the neurons which can be *boxed*, i.e. stored as trait objects, so that a Layer
+    (and therefore a network) can mix different models: it is implemented by every Neuron + Clone.
+    - Homogeneous networks should keep using the concrete model (e.g. DynSNN<LifNeuron>), which
+      avoids the dynamic dispatch
+*/
+pub trait DynNeuron: Neuron {
+    fn clone_box(&self) -> BoxedNeuron;
+
+    /** It allows to downcast the neuron to its concrete model (e.g. to read its parameters) */
+    fn as_any(&self) -> &dyn Any;
+}
+
+/** Neuron of any model (e.g. DynSNN<BoxedNeuron> is a network with heterogeneous neurons) */
+pub type BoxedNeuron = Box<dyn DynNeuron>;
+
+/** It boxes a neuron, so that it can be mixed with neurons of other models */
+pub fn boxed<N: Neuron + Clone + 'static>(neuron: N) -> BoxedNeuron {
+    Box::new(neuron)
+}
+
+impl<N: Neuron + Clone + 'static> DynNeuron for N {
+    fn clone_box(&self) -> BoxedNeuron {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl dyn DynNeuron {
+    /**
+        It returns the neuron as its concrete model M (None if it is a neuron of another model)
+        - Ex: snn.get_neuron(0, 1).downcast_ref::<LifNeuron>()
+    */
+    pub fn downcast_ref<M: Neuron + 'static>(&self) -> Option<&M> {
+        self.as_any().downcast_ref::<M>()
+    }
+}
+
+impl Clone for BoxedNeuron {
+    fn clone(&self) -> Self {
+        self.as_ref().clone_box()
+    }
+}
+
+impl Neuron for BoxedNeuron {
+    fn compute_v_mem(&mut self, t: u64, extra_weighted_sum: f64, intra_weighted_sum: f64) -> u8 {
+        self.as_mut().compute_v_mem(t, extra_weighted_sum, intra_weighted_sum)
+    }
+
+    fn compute_v_mem_with_input(&mut self, t: u64, input: &SynapticInput) -> u8 {
+        self.as_mut().compute_v_mem_with_input(t, input)
+    }
+
+    fn compute_v_mem_with_faults(&mut self, t: u64, input: &SynapticInput, injector: &dyn FaultInjector) -> u8 {
+        self.as_mut().compute_v_mem_with_faults(t, input, injector)
+    }
+
+    fn get_fault_width(&self) -> Option<u32> {
+        self.as_ref().get_fault_width()
+    }
+
+    fn get_fixed_format(&self) -> Option<FixedFormat> {
+        self.as_ref().get_fixed_format()
+    }
+
+    fn integrate(&mut self, t: u64, input: &SynapticInput) -> Option<Membrane> {
+        self.as_mut().integrate(t, input)
+    }
+
+    fn set_v_mem(&mut self, v_mem: f64) {
+        self.as_mut().set_v_mem(v_mem)
+    }
+
+    fn is_active(&self) -> bool {
+        self.as_ref().is_active()
+    }
+
+    fn get_state(&self) -> Option<Vec<f64>> {
+        self.as_ref().get_state()
+    }
+
+    fn set_state(&mut self, state: &[f64]) {
+        self.as_mut().set_state(state)
+    }
+
+    fn initialize(&mut self) {
+        self.as_mut().initialize()
+    }
+}
+
+impl std::fmt::Debug for dyn DynNeuron {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DynNeuron")
+    }
+}
+
@@ -1,9 +1,9 @@
 use std::sync::{Arc, Mutex};
-use std::sync::mpsc::channel;
+use std::sync::mpsc::{channel, Receiver, Sender};
 use std::thread;
 use std::thread::JoinHandle;
-use crate::neuron::Neuron;
-use crate::snn::layer::Layer;
+use crate::snn::layer::SpikingLayer;
+use crate::snn::stream::StreamMessage;
 use crate::SpikeEvent;
 
 #[derive(Debug)]
@@ -11,13 +11,17 @@ pub struct Processor { }
 
 impl Processor {
     /**
-        Spikes is a Vec of spike events that will be processed through the layers of the network.
+        Spikes is a Vec of spike events that will be processed through the layers of the network,
+        lasting *duration* instants (the layers can fire until the end of the input, see SpikingLayer::advance_to()).
+        - The instants of the spike events are relative to *offset*, i.e. the number of instants already
+          processed since the layers have been reset (the returned spike events are relative to it too)
+        - The layers are not reset, so that their state carries over from the previous input
         - This method creates a new thread for each layer.
         Each thread will process the input spike events received from the previous layer through a shared channel
         and will send the computed output spike events to the next layer by using another shared channel.
      */
-    pub fn process_events<'a, N: Neuron + Clone + Send + 'static, S: IntoIterator<Item=&'a mut Arc<Mutex<Layer<N>>>>>
-    (&self, snn: S, spikes: Vec<SpikeEvent>) -> Vec<SpikeEvent> {
+    pub fn process_events<'a, L: SpikingLayer + ?Sized + 'static, S: IntoIterator<Item=&'a mut Arc<Mutex<L>>>>
+    (&self, snn: S, spikes: Vec<SpikeEvent>, offset: u64, duration: u64) -> Vec<SpikeEvent> {
         /* create the threads' pool */
         let mut threads = Vec::<JoinHandle<()>>::new();
 
@@ -34,8 +38,32 @@ impl Processor {
             let thread = thread::spawn(move || {
                 /* retrieve layer */
                 let mut layer = layer_ref.lock().unwrap();
-                /* execute layer task */
-                layer.process(layer_rc, layer_tx);
+
+                let send = |output_spike_event: SpikeEvent| {
+                    let instant = output_spike_event.ts;
+
+                    layer_tx.send(output_spike_event)
+                        .expect(&format!("Unexpected error sending input spike event t={}", instant));
+                };
+
+                /* listen to SpikeEvent(s) coming from the previous layer and process them */
+                while let Ok(input_spike_event) = layer_rc.recv() {
+                    /* (the layer may fire without input before this instant) */
+                    layer.advance_to(input_spike_event.ts).into_iter().for_each(send);
+
+                    /* (at least one output fired -> send output spikes to the next layer) */
+                    if let Some(output_spike_event) = layer.process_event(&input_spike_event) {
+                        send(output_spike_event);
+                    }
+                }
+
+                /* (and after the last input, until the end of it) */
+                layer.advance_to(offset + duration).into_iter().for_each(send);
+
+                /*
+                    we don't need to drop the sender, because it will be
+                    automatically dropped when the thread terminates
+                */
             });
 
             threads.push(thread);   /* push the new thread into threads' pool */
@@ -45,12 +73,13 @@ impl Processor {
         let net_output_rc = layer_rc;
 
         /* fire input SpikeEvents into *net_input_tx* */
-        for spike_event in spikes {
+        for mut spike_event in spikes {
             /* * check if there is at least 1 spike, otherwise skip to the next instant * */
             if spike_event.spikes.iter().all(|spike| *spike == 0u8) {
                 continue;   /* (process only *effective* spike events) */
             }
 
+            spike_event.ts += offset;
             let instant = spike_event.ts;
 
             net_input_tx.send(spike_event)
@@ -62,7 +91,8 @@ impl Processor {
         /* get output SpikeEvents from *net_output* rc */
         let mut output_events = Vec::<SpikeEvent>::new();
 
-        while let Ok(spike_event) = net_output_rc.recv() {
+        while let Ok(mut spike_event) = net_output_rc.recv() {
+            spike_event.ts -= offset;
             output_events.push(spike_event);
         }
 
@@ -73,4 +103,115 @@ impl Processor {
 
         output_events
     }
-}
\ No newline at end of file
+
+    /**
+        Same as process_events(), but the input is not known in advance: it returns the sender feeding
+        the first layer and the receiver of the output of the last one, together with the threads of the
+        layers, which terminate when the sender is dropped (see SpikeStream)
+        - Each layer is reset whenever it receives a StreamMessage::Reset (which is forwarded to the next layer)
+     */
+    pub(crate) fn open_stream<'a, L: SpikingLayer + ?Sized + 'static, S: IntoIterator<Item=&'a mut Arc<Mutex<L>>>>
+    (&self, snn: S) -> (Sender<StreamMessage>, Receiver<StreamMessage>, Vec<JoinHandle<()>>) {
+        let mut threads = Vec::<JoinHandle<()>>::new();
+        let (net_input_tx, mut layer_rc) = channel::<StreamMessage>();
+
+        for layer_ref in snn {
+            let (layer_tx, next_layer_rc) = channel::<StreamMessage>();
+            let layer_ref = layer_ref.clone();
+
+            let thread = thread::spawn(move || {
+                let mut layer = layer_ref.lock().unwrap();
+
+                /* (the next layer may have been dropped with the stream: the messages are lost) */
+                let send = |message: StreamMessage| { let _ = layer_tx.send(message); };
+
+                while let Ok(message) = layer_rc.recv() {
+                    match message {
+                        StreamMessage::Event(input_spike_event) => {
+                            layer.advance_to(input_spike_event.ts).into_iter().for_each(|event| send(StreamMessage::Event(event)));
+                            if let Some(output_spike_event) = layer.process_event(&input_spike_event) {
+                                send(StreamMessage::Event(output_spike_event));
+                            }
+                        },
+                        StreamMessage::Advance(instant) => {
+                            layer.advance_to(instant).into_iter().for_each(|event| send(StreamMessage::Event(event)));
+                            send(StreamMessage::Advance(instant));
+                        },
+                        StreamMessage::Reset => {
+                            layer.reset();
+                            send(StreamMessage::Reset);
+                        },
+                    }
+                }
+            });
+
+            threads.push(thread);
+            layer_rc = next_layer_rc;
+        }
+
+        (net_input_tx, layer_rc, threads)
+    }
+
+    /**
+        This function checks if each vector passed in 'spikes' has the same number of spikes.
+        If yes, it returns the duration, otherwise it triggers an error
+     */
+    pub fn compute_spikes_duration(spikes: &Vec<Vec<u8>>) -> usize {
+        // compute length of the first Vec (0 if it does not exist)
+        let spikes_duration = spikes.get(0)
+                                            .unwrap_or(&Vec::new())
+                                            .len();
+
+        for neuron_spikes in spikes {
+            if neuron_spikes.len() != spikes_duration {
+                panic!("The number of spikes duration must be equal for each neuron");
+            }
+        }
+        spikes_duration
+    }
+
+    /**
+        This function encodes the received input spikes in a Vec of **SpikeEvent** to process them.
+     */
+    pub fn encode_spikes(input_layer_dimension: usize, spikes: &Vec<Vec<u8>>, spikes_duration: usize) -> Vec<SpikeEvent> {
+        let mut spike_events = Vec::<SpikeEvent>::new();
+
+        if spikes.len() != input_layer_dimension {
+            panic!("The number of input spikes is not coherent with the input layer dimension: \
+                    'spikes' must have a Vec for each neuron");
+        }
+
+        for t in 0..spikes_duration {
+            let mut t_spikes = Vec::<u8>::new();
+
+            /* retrieve the input spikes for each neuron */
+            for in_neuron_index in 0..spikes.len(){
+                /* check for 0 or 1 only */
+                if spikes[in_neuron_index][t] != 0 && spikes[in_neuron_index][t] != 1 {
+                    panic!("Error: input spike must be 0 or 1 at for N={} at t={}", in_neuron_index, t);
+                }
+                t_spikes.push(spikes[in_neuron_index][t]);
+            }
+
+            let t_spike_event = SpikeEvent::new(t as u64, t_spikes);
+            spike_events.push(t_spike_event);
+        }
+
+        spike_events
+    }
+
+    /**
+        This function decodes a Vec of SpikeEvents and returns an output spikes matrix of 0/1
+     */
+    pub fn decode_spikes(output_layer_dimension: usize, spikes: Vec<SpikeEvent>, spikes_duration: usize) -> Vec<Vec<u8>> {
+        let mut raw_spikes  = vec![vec![0; spikes_duration]; output_layer_dimension];
+
+        for spike_event in spikes {
+            for (out_neuron_index, spike) in spike_event.spikes.into_iter().enumerate() {
+                raw_spikes[out_neuron_index][spike_event.ts as usize] = spike;
+            }
+        }
+
+        raw_spikes
+    }
+}
@@ -0,0 +1,101 @@
+/* * Readout submodule * */
+
+/**
+    Value returned by a ReadoutLayer for each of its neurons
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadoutMode {
+    Trace,  /* the potential at each time instant */
+    Max,    /* the maximum potential over time (a single value) */
+    Final,  /* the potential at the last instant (a single value) */
+}
+
+/**
+    Object representing a non-spiking readout layer of leaky integrators: instead of spikes,
+    it emits the potential of each of its neurons, for regression and classification.
+    At each instant t the potential of the neuron i is
+        v_i(t) = v_i(t - 1) * exp(-dt / tau) + sum_j weights[i][j] * spikes_j(t)
+    starting from v_i(-1) = 0
+    - Unlike the spiking layers, the weights can be both positive and negative
+*/
+#[derive(Debug, Clone)]
+pub struct ReadoutLayer {
+    weights: Vec<Vec<f64>>, /* weights between the neurons of this layer and the previous one */
+    tau: f64,
+    dt: f64,                /* time interval between two consecutive instants */
+}
+
+impl ReadoutLayer {
+    /**
+        - It panics if the rows of the weights have different lengths, or if tau and dt are not positive
+    */
+    pub fn new(weights: Vec<Vec<f64>>, tau: f64, dt: f64) -> Self {
+        let cols = weights.first().map_or(0, |row| row.len());
+
+        if weights.iter().any(|row| row.len() != cols) {
+            panic!("All the rows of the readout weights matrix must have the same length");
+        }
+        if tau <= 0.0 || dt <= 0.0 {
+            panic!("tau and dt of the readout layer must be positive");
+        }
+
+        Self { weights, tau, dt }
+    }
+
+    /* Getters */
+    pub fn get_input_dimension(&self) -> usize {
+        self.weights.first().map_or(0, |row| row.len())
+    }
+
+    pub fn get_output_dimension(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn get_weights(&self) -> Vec<Vec<f64>> {
+        self.weights.clone()
+    }
+
+    pub fn get_tau(&self) -> f64 {
+        self.tau
+    }
+
+    pub fn get_dt(&self) -> f64 {
+        self.dt
+    }
+
+    /**
+        It integrates the spikes of the previous layer (one row for each input, one column for each instant)
+        and returns the values of each readout neuron, according to the mode: a row for each neuron,
+        with a value for each instant (Trace) or a single value (Max, Final; 0 if there are no instants)
+        - It panics if the number of rows differs from the input dimension
+    */
+    pub fn integrate(&self, spikes: &[Vec<u8>], mode: ReadoutMode) -> Vec<Vec<f64>> {
+        if spikes.len() != self.get_input_dimension() {
+            panic!("The number of input spikes is not coherent with the readout input dimension: \
+                    'spikes' must have a Vec for each input");
+        }
+
+        let duration = spikes.first().map_or(0, |row| row.len());
+        let decay = (-self.dt / self.tau).exp();
+
+        self.weights.iter().map(|weights| {
+            let mut v = 0f64;
+            let trace = (0..duration).map(|t| {
+                v = v * decay + weights.iter().zip(spikes.iter())
+                    .filter(|(_, input_spikes)| input_spikes[t] != 0)
+                    .map(|(weight, _)| *weight)
+                    .sum::<f64>();
+                v
+            });
+
+            match mode {
+                ReadoutMode::Trace => trace.collect(),
+                ReadoutMode::Max => {
+                    let max = trace.fold(f64::NEG_INFINITY, f64::max);
+                    vec![if duration == 0 { 0.0 } else { max }]
+                },
+                ReadoutMode::Final => vec![trace.last().unwrap_or(0.0)],
+            }
+        }).collect()
+    }
+}
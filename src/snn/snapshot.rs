@@ -0,0 +1,72 @@
+/* * Snapshot submodule * */
+
+/**
+    Dynamic state of a layer: the state of each neuron (see Neuron::get_state()), the output spikes
+    of the last processed instant (the recurrent input of the next one) and the state of the dynamic
+    synapses (if any)
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerSnapshot {
+    pub(crate) neurons: Vec<Vec<f64>>,
+    pub(crate) prev_output_spikes: Vec<u8>,
+    pub(crate) last_instant: Option<u64>,
+    pub(crate) synapses: Option<Vec<f64>>,
+}
+
+impl LayerSnapshot {
+    /* Getters */
+    pub fn get_neurons_number(&self) -> usize {
+        self.neurons.len()
+    }
+
+    /** State of a neuron of the layer (see Neuron::get_state()) */
+    pub fn get_neuron_state(&self, index: usize) -> &Vec<f64> {
+        match self.neurons.get(index) {
+            Some(state) => state,
+            None => panic!("The neuron {} does not exist (the layer has {} neurons)", index, self.neurons.len()),
+        }
+    }
+
+    pub fn get_prev_output_spikes(&self) -> &Vec<u8> {
+        &self.prev_output_spikes
+    }
+
+    /** Last instant in which the layer has been processed (None after a reset) */
+    pub fn get_last_instant(&self) -> Option<u64> {
+        self.last_instant
+    }
+
+    pub fn has_dynamic_synapses(&self) -> bool {
+        self.synapses.is_some()
+    }
+}
+
+/**
+    Object representing the dynamic state of a whole network at the end of an instant (see
+    DynSNN::snapshot()), which can be restored into the same network or into one with the same
+    structure (e.g. a replica), and saved to a file (see format::snapshot)
+    - The parameters and the weights of the network are not included
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkSnapshot {
+    pub(crate) elapsed: u64,    /* number of instants processed since the last reset */
+    pub(crate) layers: Vec<LayerSnapshot>,
+}
+
+impl NetworkSnapshot {
+    /* Getters */
+    pub fn get_elapsed_instants(&self) -> u64 {
+        self.elapsed
+    }
+
+    pub fn get_layers_number(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn get_layer(&self, index: usize) -> &LayerSnapshot {
+        match self.layers.get(index) {
+            Some(layer) => layer,
+            None => panic!("The layer {} does not exist (the snapshot has {} layers)", index, self.layers.len()),
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use std::slice::IterMut;
-use std::sync::{Arc, Mutex};
-use crate::snn::layer::Layer;
+use std::sync::{Arc, Mutex, MutexGuard};
+use crate::snn::layer::{Layer, SpikingLayer};
 use crate::snn::neuron::Neuron;
 use crate::snn::processor::Processor;
+use crate::snn::snapshot::NetworkSnapshot;
 use crate::snn::SpikeEvent;
 
 /* * Spiking Neural Network structure * */
@@ -18,13 +19,15 @@ use crate::snn::SpikeEvent;
 #[derive(Debug)]
 pub struct SNN<N: Neuron + Clone + Send + 'static, const NET_INPUT_DIM: usize, const NET_OUTPUT_DIM: usize> {
     layers: Vec<Arc<Mutex<Layer<N>>>>,
+    elapsed: u64,   /* number of instants processed since the last reset */
 }
 
 impl<N: Neuron + Clone + Send + 'static, const NET_INPUT_DIM: usize, const NET_OUTPUT_DIM: usize>
 SNN<N, NET_INPUT_DIM, NET_OUTPUT_DIM> {
     pub fn new(layers: Vec<Arc<Mutex<Layer<N>>>>) -> Self {
         Self {
-            layers
+            layers,
+            elapsed: 0
         }
     }
 
@@ -37,6 +40,69 @@ SNN<N, NET_INPUT_DIM, NET_OUTPUT_DIM> {
         self.layers.iter().map(|layer| layer.lock().unwrap().clone()).collect()
     }
 
+    /*
+        Access to the weights and the neurons of the built network, by (layer, neuron, input) index.
+        The setters do the same checks of the builders (including the ones across two consecutive layers,
+        with Dale's law), and panic if an index is out of bounds
+    */
+    pub fn get_neuron(&self, layer: usize, index: usize) -> N {
+        self.layer(layer).get_neuron(index).clone()
+    }
+
+    pub fn set_neuron(&mut self, layer: usize, index: usize, neuron: N) {
+        self.layer(layer).set_neuron(index, neuron);
+    }
+
+    pub fn get_weight(&self, layer: usize, neuron: usize, input: usize) -> f64 {
+        self.layer(layer).get_weight(neuron, input)
+    }
+
+    pub fn set_weight(&mut self, layer: usize, neuron: usize, input: usize, weight: f64) {
+        if layer > 0 {
+            let next = self.layer(layer);
+            self.layer(layer - 1).check_outgoing_weights(&next, input, None, Some((neuron, weight)));
+        }
+        self.layer(layer).set_weight(neuron, input, weight);
+    }
+
+    pub fn get_intra_weight(&self, layer: usize, neuron: usize, from: usize) -> f64 {
+        self.layer(layer).get_intra_weight(neuron, from)
+    }
+
+    pub fn set_intra_weight(&mut self, layer: usize, neuron: usize, from: usize, weight: f64) {
+        if layer + 1 < self.layers.len() {
+            let next = self.layer(layer + 1);
+            self.layer(layer).check_outgoing_weights(&next, from, Some((neuron, weight)), None);
+        }
+        self.layer(layer).set_intra_weight(neuron, from, weight);
+    }
+
+    /** It replaces the weights of a layer (the dimensions of the matrix cannot change) */
+    pub fn set_weights(&mut self, layer: usize, weights: Vec<Vec<f64>>) {
+        if layer > 0 {
+            let next = self.layer(layer);
+            self.layer(layer - 1).check_across_layers(&next, None, Some(&weights));
+        }
+        self.layer(layer).set_weights(weights);
+    }
+
+    /** It replaces the intra weights of a layer (the dimensions of the matrix cannot change) */
+    pub fn set_intra_weights(&mut self, layer: usize, intra_weights: Vec<Vec<f64>>) {
+        if layer + 1 < self.layers.len() {
+            let next = self.layer(layer + 1);
+            self.layer(layer).check_across_layers(&next, Some(&intra_weights), None);
+        }
+        self.layer(layer).set_intra_weights(intra_weights);
+    }
+
+    /* It locks the given layer, panicking if it does not exist */
+    fn layer(&self, index: usize) -> MutexGuard<'_, Layer<N>> {
+        match self.layers.get(index) {
+            Some(layer) => layer.lock().unwrap(),
+            None => panic!("The layer {} does not exist (the network has {} layers)", index, self.layers.len()),
+        }
+    }
+
     /**
         Actually process input spikes by means of the Spiking Neural Network and produce corresponding output spikes
         - 'spikes' contains a binary array for each input layer's neuron, and each array has the same
@@ -45,15 +111,28 @@ SNN<N, NET_INPUT_DIM, NET_OUTPUT_DIM> {
         This method is able to check user input at compile-time.
         Ex:
             snn.process(&[[0,1,1], [1,0,1]])  /* input layer with 2 neurons, each receiving 3 spikes */
+        - The network starts from the initial state (see reset())
      */
     pub fn process<const SPIKES_DURATION: usize>(&mut self, spikes: &[[u8; SPIKES_DURATION]; NET_INPUT_DIM])
                                                  -> [[u8; SPIKES_DURATION]; NET_OUTPUT_DIM] {
+        self.reset();
+        self.process_continue(spikes)
+    }
+
+    /**
+        Same as process(), but the network starts from the state reached at the end of the previous input,
+        so that a long input can be processed in consecutive chunks (see DynSNN::process_continue())
+     */
+    pub fn process_continue<const SPIKES_DURATION: usize>(&mut self, spikes: &[[u8; SPIKES_DURATION]; NET_INPUT_DIM])
+                                                          -> [[u8; SPIKES_DURATION]; NET_OUTPUT_DIM] {
         /* encode spikes into SpikeEvent(s) */
         let input_spike_events = SNN::<N, NET_INPUT_DIM, NET_OUTPUT_DIM>::encode_spikes(spikes);
 
         /* process input and produce SNN output spikes */
         let processor = Processor {};
-        let output_spike_events = processor.process_events(self, input_spike_events);
+        let output_spike_events = processor.process_events(self.layers.iter_mut(), input_spike_events,
+                                                           self.elapsed, SPIKES_DURATION as u64);
+        self.elapsed += SPIKES_DURATION as u64;
 
         /* decode output into array shape */
         let decoded_output: [[u8; SPIKES_DURATION]; NET_OUTPUT_DIM] =
@@ -110,8 +189,11 @@ SNN<N, NET_INPUT_DIM, NET_OUTPUT_DIM> {
         }
 
         /* run SNN */
+         self.reset();
          let processor = Processor {};
-         let output_spike_events = processor.process_events(self, spikes_events);
+         let output_spike_events = processor.process_events(self.layers.iter_mut(), spikes_events,
+                                                            0, spikes_duration.unwrap_or(0) as u64);
+         self.elapsed = spikes_duration.unwrap_or(0) as u64;
 
         /* decode output spikes events */
 
@@ -135,6 +217,37 @@ SNN<N, NET_INPUT_DIM, NET_OUTPUT_DIM> {
         output_spikes
     }
 
+    /** Bring all the layers to the initial state, so that the next process_continue() starts from the instant 0 */
+    pub fn reset(&mut self) {
+        self.layers.iter().for_each(|layer| layer.lock().unwrap().reset());
+        self.elapsed = 0;
+    }
+
+    /** Number of instants processed since the last reset (the first instant of the next process_continue()) */
+    pub fn get_elapsed_instants(&self) -> u64 {
+        self.elapsed
+    }
+
+    /** It captures the dynamic state of the network (see DynSNN::snapshot()) */
+    pub fn snapshot(&self) -> NetworkSnapshot {
+        NetworkSnapshot {
+            elapsed: self.elapsed,
+            layers: self.layers.iter().map(|layer| layer.lock().unwrap().snapshot()).collect(),
+        }
+    }
+
+    /** It restores a state captured by snapshot() (see DynSNN::restore()) */
+    pub fn restore(&mut self, snapshot: &NetworkSnapshot) {
+        if snapshot.layers.len() != self.layers.len() {
+            panic!("The snapshot has {} layers, but the network has {}", snapshot.layers.len(), self.layers.len());
+        }
+
+        for (layer, layer_snapshot) in self.layers.iter().zip(&snapshot.layers) {
+            layer.lock().unwrap().restore(layer_snapshot);
+        }
+        self.elapsed = snapshot.elapsed;
+    }
+
     /* private functions */
 
     /**
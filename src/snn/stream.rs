@@ -0,0 +1,166 @@
+/* * Stream submodule * */
+
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use crate::SpikeEvent;
+
+/* Message flowing through the threads of the layers of a stream */
+pub(crate) enum StreamMessage {
+    Event(SpikeEvent),  /* spikes of an instant */
+    Advance(u64),       /* no input before the given instant (see SpikeStream::advance_to()) */
+    Reset,              /* bring the layers to the initial state */
+}
+
+/**
+    Object representing a streaming inference on a network (see DynSNN::stream()): the input SpikeEvents
+    are pushed one at a time, in increasing time order, and the output SpikeEvents of the last layer
+    can be received as soon as it emits them. Each layer runs in its own thread, as in process().
+    - The state of the network persists among the pushed events, until the stream is reset or closed
+    - The layers are locked by the stream, which borrows the network until it is closed (or dropped):
+      then the network keeps the state reached by the stream, whose instants it has processed
+*/
+pub struct SpikeStream<'a> {
+    input: Option<Sender<StreamMessage>>,   /* (None once the stream is closed) */
+    output: Receiver<StreamMessage>,
+    threads: Vec<JoinHandle<()>>,
+    input_dimension: usize,
+    next_instant: u64,                      /* first instant allowed for the next pushed event */
+    elapsed: &'a mut u64,                   /* instants processed by the network (updated when the stream is closed) */
+}
+
+impl<'a> SpikeStream<'a> {
+    pub(crate) fn new(input: Sender<StreamMessage>, output: Receiver<StreamMessage>, threads: Vec<JoinHandle<()>>,
+                      input_dimension: usize, elapsed: &'a mut u64) -> Self {
+        Self { input: Some(input), output, threads, input_dimension, next_instant: 0, elapsed }
+    }
+
+    pub fn get_input_dimension(&self) -> usize {
+        self.input_dimension
+    }
+
+    /**
+        It feeds the input spikes of an instant to the network (the events without spikes are skipped)
+        - It panics if the number of spikes differs from the input dimension, if a spike is not 0 or 1,
+          or if the instant is not after the one of the last pushed event
+    */
+    pub fn push(&mut self, event: SpikeEvent) {
+        if event.spikes.len() != self.input_dimension {
+            panic!("The number of input spikes is not coherent with the input layer dimension");
+        }
+        if event.spikes.iter().any(|spike| *spike > 1) {
+            panic!("Error: input spike must be 0 or 1 at t={}", event.ts);
+        }
+        if event.ts < self.next_instant {
+            panic!("The instants of the input events must be increasing (t={} is before t={})", event.ts, self.next_instant);
+        }
+        self.next_instant = event.ts + 1;
+
+        if event.spikes.contains(&1) {
+            self.send(StreamMessage::Event(event));
+        }
+    }
+
+    /**
+        Same as push(), taking the indices of the inputs firing in the instant *ts*
+        (as in the sparse events files, see io::events)
+        - It panics if an index exceeds the input dimension
+    */
+    pub fn push_sparse(&mut self, ts: u64, active_inputs: &[usize]) {
+        let mut spikes = vec![0u8; self.input_dimension];
+
+        for input in active_inputs {
+            match spikes.get_mut(*input) {
+                Some(spike) => *spike = 1,
+                None => panic!("The input {} does not exist (the network has {} inputs)", input, self.input_dimension),
+            }
+        }
+
+        self.push(SpikeEvent::new(ts, spikes));
+    }
+
+    /**
+        It declares that no input spikes arrive before *instant*, so that the layers firing without
+        input (e.g. with the delayed recurrence, see RecurrenceMode) can emit their spikes until then
+        - An instant which has already been reached has no effect
+    */
+    pub fn advance_to(&mut self, instant: u64) {
+        self.next_instant = self.next_instant.max(instant);
+        self.send(StreamMessage::Advance(instant));
+    }
+
+    /** It returns an output SpikeEvent if one is already available, without blocking */
+    pub fn try_recv(&self) -> Option<SpikeEvent> {
+        loop {
+            match self.output.try_recv() {
+                Ok(StreamMessage::Event(event)) => return Some(event),
+                Ok(_) => continue,
+                Err(_) => return None,
+            }
+        }
+    }
+
+    /** It waits for the next output SpikeEvent for at most *timeout* */
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<SpikeEvent> {
+        loop {
+            match self.output.recv_timeout(timeout) {
+                Ok(StreamMessage::Event(event)) => return Some(event),
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+    }
+
+    /** It returns the output SpikeEvents already available, without blocking */
+    pub fn try_iter(&self) -> impl Iterator<Item=SpikeEvent> + '_ {
+        std::iter::from_fn(|| self.try_recv())
+    }
+
+    /**
+        It brings the network to the initial state, so that a new (independent) input can be pushed,
+        starting again from any instant
+        - The output SpikeEvents of the previous input not received yet can still be received
+    */
+    pub fn reset(&mut self) {
+        self.next_instant = 0;
+        self.send(StreamMessage::Reset);
+    }
+
+    /**
+        It terminates the stream, waiting for the layers to process all the pushed events,
+        and returns the output SpikeEvents not received yet
+    */
+    pub fn close(mut self) -> Vec<SpikeEvent> {
+        self.terminate()
+    }
+
+    fn terminate(&mut self) -> Vec<SpikeEvent> {
+        drop(self.input.take());    /* (the threads terminate once the input sender is dropped) */
+
+        let output_events = self.output.iter()
+            .filter_map(|message| match message {
+                StreamMessage::Event(event) => Some(event),
+                _ => None,
+            })
+            .collect();
+
+        for thread in self.threads.drain(..) {
+            thread.join().unwrap();
+        }
+        *self.elapsed = self.next_instant;
+
+        output_events
+    }
+
+    fn send(&self, message: StreamMessage) {
+        self.input.as_ref().unwrap().send(message).expect("Unexpected error sending a message to the stream");
+    }
+}
+
+impl Drop for SpikeStream<'_> {
+    fn drop(&mut self) {
+        if self.input.is_some() {
+            self.terminate();
+        }
+    }
+}
@@ -0,0 +1,187 @@
+/* * Synapses submodule * */
+
+use crate::snn::weights::WeightMatrix;
+
+/**
+    Parameters of a dynamic synapse in the Tsodyks-Markram model of short-term plasticity
+    - u: utilization increment at each presynaptic spike (the efficacy of the first spike)
+    - tau_rec: time constant of the recovery of the resources (depression)
+    - tau_facil: time constant of the decay of the utilization (facilitation, 0 for a purely depressing synapse)
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StpParams {
+    pub u: f64,
+    pub tau_rec: f64,
+    pub tau_facil: f64,
+}
+
+impl StpParams {
+    /** - It panics if u is not in the range (0, 1] or a time constant is negative */
+    pub fn new(u: f64, tau_rec: f64, tau_facil: f64) -> Self {
+        if u <= 0.0 || u > 1.0 {
+            panic!("The utilization must be in the range (0, 1]");
+        }
+        if tau_rec < 0.0 || tau_facil < 0.0 {
+            panic!("The time constants must not be negative");
+        }
+
+        Self { u, tau_rec, tau_facil }
+    }
+}
+
+/* State of a single dynamic synapse */
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Synapse {
+    params: StpParams,
+    u: f64,                     /* utilization (fraction of the resources used by a spike) */
+    x: f64,                     /* available resources */
+    efficacy: f64,              /* u * x at the last presynaptic spike (the factor of the weight) */
+    last_spike: Option<u64>,    /* instant of the last presynaptic spike */
+}
+
+impl Synapse {
+    fn new(params: StpParams) -> Self {
+        Self { params, u: 0.0, x: 1.0, efficacy: 0.0, last_spike: None }
+    }
+
+    /* It delivers a presynaptic spike at the instant t */
+    fn deliver(&mut self, t: u64, dt: f64) {
+        let decay = |tau: f64, delta: f64| if tau > 0.0 { (-delta / tau).exp() } else { 0.0 };
+
+        if let Some(last_spike) = self.last_spike {
+            /* (between two spikes u decays to 0 and x recovers to 1) */
+            let delta = (t - last_spike) as f64 * dt;
+            self.u *= decay(self.params.tau_facil, delta);
+            self.x = 1.0 - (1.0 - self.x) * decay(self.params.tau_rec, delta);
+        }
+
+        self.u += self.params.u * (1.0 - self.u);
+        self.efficacy = self.u * self.x;
+        self.x -= self.efficacy;
+        self.last_spike = Some(t);
+    }
+}
+
+/**
+    Object representing the dynamic (Tsodyks-Markram) synapses of the extra-layer connections of a Layer:
+    each time a presynaptic spike is delivered, the effective weight of each of its synapses is
+    weight * u * x, where the utilization u facilitates and the resources x deplete with the spikes,
+    and both recover between them
+    - Each synapse (neuron, input) has its own parameters and state
+    - The state is reset at each process, as the one of the neurons
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicSynapses {
+    synapses: Vec<Vec<Synapse>>,    /* one row for each neuron, as the weights */
+    dt: f64,                        /* time interval between two consecutive instants */
+}
+
+impl DynamicSynapses {
+    /** It creates the synapses of *num_neurons* neurons with *num_inputs* inputs, all with the same parameters */
+    pub fn new(num_neurons: usize, num_inputs: usize, params: StpParams, dt: f64) -> Self {
+        Self { synapses: vec![vec![Synapse::new(params); num_inputs]; num_neurons], dt }
+    }
+
+    /* Getters */
+    pub fn get_neurons_number(&self) -> usize {
+        self.synapses.len()
+    }
+
+    pub fn get_inputs_number(&self) -> usize {
+        self.synapses.first().map_or(0, |row| row.len())
+    }
+
+    pub fn get_dt(&self) -> f64 {
+        self.dt
+    }
+
+    pub fn get_params(&self, neuron: usize, input: usize) -> StpParams {
+        self.synapse(neuron, input).params
+    }
+
+    /** Utilization of the synapse (after the last presynaptic spike) */
+    pub fn get_u(&self, neuron: usize, input: usize) -> f64 {
+        self.synapse(neuron, input).u
+    }
+
+    /** Available resources of the synapse (after the last presynaptic spike) */
+    pub fn get_x(&self, neuron: usize, input: usize) -> f64 {
+        self.synapse(neuron, input).x
+    }
+
+    /** Factor of the weight at the last presynaptic spike (0 before the first one) */
+    pub fn get_efficacy(&self, neuron: usize, input: usize) -> f64 {
+        self.synapse(neuron, input).efficacy
+    }
+
+    /** It sets the parameters of a single synapse */
+    pub fn set_params(&mut self, neuron: usize, input: usize, params: StpParams) {
+        self.synapse(neuron, input);
+        self.synapses[neuron][input] = Synapse::new(params);
+    }
+
+    /** Bring all the synapses to the initial state */
+    pub fn reset(&mut self) {
+        self.synapses.iter_mut().flatten().for_each(|synapse| *synapse = Synapse::new(synapse.params));
+    }
+
+    /* It delivers the spikes of the instant t to the synapses of the spiking inputs */
+    pub(crate) fn deliver(&mut self, t: u64, spikes: &[u8]) {
+        for row in self.synapses.iter_mut() {
+            for (synapse, spike) in row.iter_mut().zip(spikes.iter()) {
+                if *spike != 0 {
+                    synapse.deliver(t, self.dt);
+                }
+            }
+        }
+    }
+
+    /*
+        Same as WeightMatrix::signed_weighted_sums(), but each weight is scaled by the efficacy
+        of its synapse (the spikes must have been delivered first)
+    */
+    pub(crate) fn signed_weighted_sums(&self, weights: &WeightMatrix, row: usize, spikes: &[u8]) -> (f64, f64) {
+        let (mut positive, mut negative) = (0f64, 0f64);
+
+        for (col, (synapse, spike)) in self.synapses[row].iter().zip(spikes.iter()).enumerate() {
+            if *spike != 0 {
+                let weight = weights.get(row, col) * synapse.efficacy;
+                if weight < 0.0 { negative += weight } else { positive += weight }
+            }
+        }
+
+        (positive, negative)
+    }
+
+    /*
+        Dynamic state of all the synapses (for the snapshots of the network), row by row:
+        u, x, efficacy and the instant of the last presynaptic spike (-1 if none) of each synapse
+    */
+    pub(crate) fn get_state(&self) -> Vec<f64> {
+        self.synapses.iter().flatten()
+            .flat_map(|synapse| [synapse.u, synapse.x, synapse.efficacy, synapse.last_spike.map_or(-1.0, |t| t as f64)])
+            .collect()
+    }
+
+    /* It restores a state returned by get_state() (it panics if it does not fit the synapses) */
+    pub(crate) fn set_state(&mut self, state: &[f64]) {
+        let synapses_number = self.get_neurons_number() * self.get_inputs_number();
+        if state.len() != 4 * synapses_number {
+            panic!("The state of the dynamic synapses must have {} values, not {}", 4 * synapses_number, state.len());
+        }
+
+        for (synapse, values) in self.synapses.iter_mut().flatten().zip(state.chunks(4)) {
+            synapse.u = values[0];
+            synapse.x = values[1];
+            synapse.efficacy = values[2];
+            synapse.last_spike = if values[3] < 0.0 { None } else { Some(values[3] as u64) };
+        }
+    }
+
+    fn synapse(&self, neuron: usize, input: usize) -> &Synapse {
+        match self.synapses.get(neuron).and_then(|row| row.get(input)) {
+            Some(synapse) => synapse,
+            None => panic!("The synapse ({}, {}) does not exist", neuron, input),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+/* * Weights submodule * */
+
+use std::sync::Arc;
+use memmap2::Mmap;
+use crate::utils::fixed::FixedFormat;
+
+/**
+    Scalar type used to store the weights inside a binary blob
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightsType {
+    F32,
+    F64,
+}
+
+impl WeightsType {
+    /** Size (in bytes) of a single weight */
+    pub fn size(&self) -> usize {
+        match self {
+            WeightsType::F32 => 4,
+            WeightsType::F64 => 8,
+        }
+    }
+}
+
+/**
+    Policy on the signs of the weights of a layer, checked by the builders and by the setters
+    - Strict: the extra weights are positive (excitatory) and the intra weights negative (inhibitory)
+    - Dale: each presynaptic neuron is either excitatory or inhibitory (Dale's law), i.e. all its outgoing
+      weights (the ones of its column) have the same sign
+    - Unconstrained: any sign, e.g. for trained networks with mixed-sign weights
+
+    The reflexive links of the intra weights are ignored, and the null weights are compatible with any sign
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignPolicy {
+    #[default]
+    Strict,
+    Dale,
+    Unconstrained,
+}
+
+/* (violation of Dale's law across two consecutive layers) */
+const DALE_ACROSS_LAYERS: &str = "The outgoing weights of each neuron must have the same sign in its layer and in the next one (Dale's law)";
+
+impl SignPolicy {
+    /**
+        It checks the signs of a weights matrix (one row for each neuron), returning a description
+        of the first violation, if any
+        - intra: true for an intra weights matrix
+    */
+    pub fn validate(&self, weights: &[Vec<f64>], intra: bool) -> Result<(), String> {
+        let cols = weights.iter().map(|row| row.len()).max().unwrap_or(0);
+
+        (0..cols).try_for_each(|col| {
+            /* (outgoing weights of the presynaptic neuron col, without the reflexive link) */
+            let column: Vec<f64> = weights.iter().enumerate()
+                .map(|(i, row)| if intra && i == col { 0.0 } else { row.get(col).copied().unwrap_or(0.0) })
+                .collect();
+
+            self.validate_column(&column, col, intra)
+        })
+    }
+
+    /* It checks the outgoing weights of the presynaptic neuron col (without its reflexive link) */
+    pub(crate) fn validate_column(&self, column: &[f64], col: usize, intra: bool) -> Result<(), String> {
+        match self {
+            SignPolicy::Strict => {
+                if !intra && column.iter().any(|w| *w < 0.0) {
+                    return Err("The weights must be positive".to_string());
+                }
+                if intra && column.iter().any(|w| *w > 0.0) {
+                    return Err("The intra weights must be negative".to_string());
+                }
+            },
+            SignPolicy::Dale => {
+                if column.iter().any(|w| *w > 0.0) && column.iter().any(|w| *w < 0.0) {
+                    let presynaptic = if intra { "neuron" } else { "input" };
+                    return Err(format!("The outgoing weights of the {} {} must have the same sign (Dale's law)",
+                                       presynaptic, col));
+                }
+            },
+            SignPolicy::Unconstrained => {},
+        }
+
+        Ok(())
+    }
+
+    /**
+        With Dale's law, it checks that the outgoing weights of each neuron of a layer (its intra weights)
+        have the same sign of the ones towards the next layer (its extra weights)
+    */
+    pub fn validate_across_layers(&self, intra_weights: &[Vec<f64>], next_weights: &[Vec<f64>]) -> Result<(), String> {
+        if *self != SignPolicy::Dale {
+            return Ok(());
+        }
+
+        let mut matrix: Vec<Vec<f64>> = intra_weights.iter().enumerate()
+            .map(|(i, row)| row.iter().enumerate().map(|(j, w)| if i == j { 0.0 } else { *w }).collect())
+            .collect();
+        matrix.extend(next_weights.iter().cloned());
+
+        self.validate(&matrix, false).map_err(|_| DALE_ACROSS_LAYERS.to_string())
+    }
+
+    /*
+        (same as validate_across_layers(), for all the outgoing weights of a single neuron, in its layer
+        without the reflexive link and towards the next one)
+    */
+    pub(crate) fn validate_outgoing_weights(&self, outgoing: &[f64]) -> Result<(), String> {
+        if *self != SignPolicy::Dale {
+            return Ok(());
+        }
+
+        self.validate_column(outgoing, 0, false).map_err(|_| DALE_ACROSS_LAYERS.to_string())
+    }
+}
+
+/**
+    Object representing a (rows x cols) matrix of weights of a Layer.
+    The weights can be either owned by the Layer (the matrix has been passed through a builder)
+    or *memory-mapped* from a binary network file: in this case the weights are read directly
+    from the (little-endian) blob of the file, without copying them into the memory.
+    - A memory-mapped matrix is turned into an owned one the first time it is modified
+*/
+#[derive(Debug, Clone)]
+pub enum WeightMatrix {
+    Owned(Vec<Vec<f64>>),
+    Mapped(MappedWeights),
+}
+
+/* Object representing a weights blob inside a memory-mapped file */
+#[derive(Debug, Clone)]
+pub struct MappedWeights {
+    mmap: Arc<Mmap>,            /* memory-mapped file (shared among the layers of the same network) */
+    offset: usize,              /* offset (in bytes) of the blob inside the file */
+    rows: usize,                /* number of rows of the matrix */
+    cols: usize,                /* number of columns of the matrix */
+    weights_type: WeightsType,  /* scalar type of the weights */
+}
+
+impl MappedWeights {
+    /**
+        It creates a matrix view over the blob starting at *offset*.
+        - It panics if the blob exceeds the size of the mapped file
+    */
+    pub fn new(mmap: Arc<Mmap>, offset: usize, rows: usize, cols: usize, weights_type: WeightsType) -> Self {
+        let blob_end = offset + rows * cols * weights_type.size();
+
+        if blob_end > mmap.len() {
+            panic!("The weights blob exceeds the size of the mapped file");
+        }
+
+        Self { mmap, offset, rows, cols, weights_type }
+    }
+
+    fn get(&self, row: usize, col: usize) -> f64 {
+        if row >= self.rows || col >= self.cols {
+            panic!("Weight index ({}, {}) out of bounds", row, col);
+        }
+
+        let size = self.weights_type.size();
+        let start = self.offset + (row * self.cols + col) * size;
+        let bytes = &self.mmap[start..start + size];
+
+        match self.weights_type {
+            WeightsType::F32 => f32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            WeightsType::F64 => f64::from_le_bytes(bytes.try_into().unwrap()),
+        }
+    }
+}
+
+impl WeightMatrix {
+    /* Getters */
+    pub fn rows(&self) -> usize {
+        match self {
+            WeightMatrix::Owned(weights) => weights.len(),
+            WeightMatrix::Mapped(mapped) => mapped.rows,
+        }
+    }
+
+    /** Number of columns of the matrix (0 if the matrix has no rows) */
+    pub fn cols(&self) -> usize {
+        match self {
+            WeightMatrix::Owned(weights) => weights.first().map_or(0, |row| row.len()),
+            WeightMatrix::Mapped(mapped) => if mapped.rows == 0 { 0 } else { mapped.cols },
+        }
+    }
+
+    pub fn is_mapped(&self) -> bool {
+        matches!(self, WeightMatrix::Mapped(_))
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        match self {
+            WeightMatrix::Owned(weights) => weights[row][col],
+            WeightMatrix::Mapped(mapped) => mapped.get(row, col),
+        }
+    }
+
+    /**
+        It sets a single weight (a memory-mapped matrix is copied into an owned one first)
+    */
+    pub fn set(&mut self, row: usize, col: usize, weight: f64) {
+        if let WeightMatrix::Mapped(_) = self {
+            *self = WeightMatrix::Owned(self.to_vec());
+        }
+
+        if let WeightMatrix::Owned(weights) = self {
+            weights[row][col] = weight;
+        }
+    }
+
+    /**
+        It returns the *weighted sums* of the given row, i.e. the sums of the weights corresponding to the
+        non-zero spikes, with the positive and the negative weights summed separately: the
+        (excitatory, inhibitory) pair of sums
+        - skip: index of a column to be ignored (e.g. the reflexive link of the intra weights)
+    */
+    pub fn signed_weighted_sums(&self, row: usize, spikes: &[u8], skip: Option<usize>) -> (f64, f64) {
+        let (mut positive, mut negative) = (0f64, 0f64);
+        let mut add = |weight: f64| if weight < 0.0 { negative += weight } else { positive += weight };
+
+        match self {
+            WeightMatrix::Owned(weights) => {
+                for (col, (weight, spike)) in weights[row].iter().zip(spikes.iter()).enumerate() {
+                    if *spike != 0 && Some(col) != skip {
+                        add(*weight);
+                    }
+                }
+            },
+            WeightMatrix::Mapped(mapped) => {
+                for (col, spike) in spikes.iter().enumerate().take(mapped.cols) {
+                    if *spike != 0 && Some(col) != skip {
+                        add(mapped.get(row, col));
+                    }
+                }
+            }
+        }
+
+        (positive, negative)
+    }
+
+    /**
+        It returns the weighted sum of the given row accumulated in the fixed-point *format*: each weight
+        corresponding to a non-zero spike, as mapped by *weight* (e.g. scaled by the efficacy of its synapse),
+        is quantized in the format, and the raw values are added column by column with the overflow mode of
+        the format, as a hardware accumulator of the same width would do
+        - skip: index of a column to be ignored (e.g. the reflexive link of the intra weights)
+    */
+    pub fn fixed_weighted_sum<F: Fn(usize, f64) -> f64>(&self, row: usize, spikes: &[u8], skip: Option<usize>,
+                                                         format: &FixedFormat, weight: F) -> i64 {
+        spikes.iter().enumerate().take(self.cols())
+            .filter(|(col, spike)| **spike != 0 && Some(*col) != skip)
+            .fold(0i64, |sum, (col, _)| format.add(sum, format.quantize(weight(col, self.get(row, col)))))
+    }
+
+    /** It copies the weights into a Vec (one for each row) */
+    pub fn to_vec(&self) -> Vec<Vec<f64>> {
+        match self {
+            WeightMatrix::Owned(weights) => weights.clone(),
+            WeightMatrix::Mapped(mapped) =>
+                (0..mapped.rows).map(|row|
+                    (0..mapped.cols).map(|col| mapped.get(row, col)).collect()
+                ).collect()
+        }
+    }
+}
+
+impl From<Vec<Vec<f64>>> for WeightMatrix {
+    fn from(weights: Vec<Vec<f64>>) -> Self {
+        WeightMatrix::Owned(weights)
+    }
+}
+
+/**
+    It checks that the absolute values of the weights do not exceed the given limit (if any)
+*/
+pub(crate) fn validate_limit(weights: &[Vec<f64>], limit: Option<f64>) -> Result<(), String> {
+    match limit {
+        Some(limit) if weights.iter().flatten().any(|w| w.abs() > limit) =>
+            Err(format!("The weights must be in the range [-{}, {}]", limit, limit)),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,79 @@
+/* * WTA submodule * */
+
+use crate::snn::neuron::Membrane;
+
+/**
+    Winner-take-all competition among the neurons of a layer, resolved in the same instant
+    (unlike the lateral inhibition of the intra weights, which acts only in the next one):
+    - None: no competition, each neuron fires on its own
+    - Hard: only the neuron with the highest membrane potential above the threshold fires
+    - KWinners(k): the (at most) k neurons with the highest membrane potentials above the threshold fire
+    - Soft(strength): the neurons above the threshold are considered from the highest membrane potential
+      down, and each one which fires lowers by *strength* the potential of all the others (so a
+      neuron fires only if it stays above the threshold after the inhibition of the previous winners)
+
+    In the Hard and KWinners modes the losers above the threshold are reset without firing;
+    ties are broken in favour of the lowest index
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum WtaMode {
+    #[default]
+    None,
+    Hard,
+    KWinners(usize),
+    Soft(f64),
+}
+
+impl WtaMode {
+    /** - It panics if k is 0 or the strength is negative */
+    pub(crate) fn validate(&self) {
+        match *self {
+            WtaMode::KWinners(0) => panic!("The number of winners must be positive"),
+            WtaMode::Soft(strength) if strength < 0.0 => panic!("The strength of the inhibition must not be negative"),
+            _ => {},
+        }
+    }
+
+    /*
+        It resolves the competition among the neurons with the given membranes (integrated, not reset yet),
+        returning the output spikes and the new membrane potential of each neuron
+    */
+    pub(crate) fn resolve(&self, membranes: &[Membrane]) -> (Vec<u8>, Vec<f64>) {
+        let mut spikes = vec![0u8; membranes.len()];
+        let mut potentials: Vec<f64> = membranes.iter().map(|membrane| membrane.v_mem).collect();
+
+        /* neurons above the threshold, from the highest membrane potential down */
+        let mut candidates: Vec<usize> = (0..membranes.len())
+            .filter(|&i| membranes[i].v_mem > membranes[i].v_th)
+            .collect();
+        candidates.sort_by(|&a, &b| membranes[b].v_mem.total_cmp(&membranes[a].v_mem).then(a.cmp(&b)));
+
+        let winners = match *self {
+            WtaMode::None => candidates.len(),
+            WtaMode::Hard => 1,
+            WtaMode::KWinners(k) => k,
+            WtaMode::Soft(strength) => {
+                let mut fired = 0f64;
+                for &i in &candidates {
+                    if membranes[i].v_mem - strength * fired > membranes[i].v_th {
+                        spikes[i] = 1;
+                        fired += 1.0;
+                    }
+                }
+
+                /* (the neurons which do not fire are inhibited by all the winners) */
+                for (i, potential) in potentials.iter_mut().enumerate() {
+                    *potential = if spikes[i] == 1 { membranes[i].v_reset } else { *potential - strength * fired };
+                }
+                return (spikes, potentials);
+            },
+        };
+
+        for (rank, &i) in candidates.iter().enumerate() {
+            spikes[i] = if rank < winners { 1 } else { 0 };
+            potentials[i] = membranes[i].v_reset;
+        }
+
+        (spikes, potentials)
+    }
+}
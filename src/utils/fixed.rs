@@ -0,0 +1,182 @@
+/* * Fixed-point submodule * */
+
+/**
+    Behaviour of a fixed-point value exceeding the range of its format
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    Saturate,   /* the value is clamped to the minimum/maximum of the format */
+    Wrap,       /* the most significant bits are discarded (two's complement wrap-around) */
+}
+
+/**
+    Rounding applied when fraction bits are discarded (e.g. after a multiplication or a shift)
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Floor,      /* truncation towards -inf (a plain arithmetic shift) */
+    HalfUp,     /* to the nearest value, ties towards +inf (add half an LSB, then shift) */
+    HalfEven,   /* to the nearest value, ties to the even one (convergent rounding) */
+}
+
+/**
+    Signed two's complement fixed-point format: *int_bits* integer bits (sign included)
+    and *frac_bits* fraction bits, so a value is stored as a raw integer equal to value * 2^frac_bits.
+    Every operation is computed exactly on the raw values and then rounded and brought back
+    into the range of the format, as a hardware datapath of the same width would do.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedFormat {
+    int_bits: u32,
+    frac_bits: u32,
+    overflow: Overflow,
+    rounding: Rounding,
+}
+
+impl FixedFormat {
+    /**
+        Maximum width of a format: every raw value (and every value of the format) is exactly
+        representable as f64, e.g. in the state of a neuron (see Neuron::get_state())
+    */
+    pub const MAX_WIDTH: u32 = 53;
+
+    /**
+        It creates a saturating format with round-half-up.
+        - It panics if int_bits is 0 (there is no room for the sign) or the width exceeds MAX_WIDTH bits
+    */
+    pub fn new(int_bits: u32, frac_bits: u32) -> Self {
+        if int_bits == 0 || int_bits + frac_bits > Self::MAX_WIDTH {
+            panic!("A fixed-point format needs at least 1 integer bit (the sign) and at most {} bits", Self::MAX_WIDTH);
+        }
+
+        Self { int_bits, frac_bits, overflow: Overflow::Saturate, rounding: Rounding::HalfUp }
+    }
+
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /* Getters of the format parameters */
+    pub fn get_int_bits(&self) -> u32 {
+        self.int_bits
+    }
+
+    pub fn get_frac_bits(&self) -> u32 {
+        self.frac_bits
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.int_bits + self.frac_bits
+    }
+
+    pub fn get_overflow(&self) -> Overflow {
+        self.overflow
+    }
+
+    pub fn get_rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    /** Minimum raw value of the format */
+    pub fn min_raw(&self) -> i64 {
+        (-(1i128 << (self.get_width() - 1))) as i64
+    }
+
+    /** Maximum raw value of the format */
+    pub fn max_raw(&self) -> i64 {
+        ((1i128 << (self.get_width() - 1)) - 1) as i64
+    }
+
+    /** Value of the least significant bit */
+    pub fn resolution(&self) -> f64 {
+        (-(self.frac_bits as f64)).exp2()
+    }
+
+    /**
+        It converts a real value into the raw value of the format
+        (NaN becomes 0, while the infinities overflow)
+    */
+    pub fn quantize(&self, value: f64) -> i64 {
+        if value.is_nan() {
+            return 0;
+        }
+
+        let scaled = value * (self.frac_bits as f64).exp2();
+        let rounded = match self.rounding {
+            Rounding::Floor => scaled.floor(),
+            Rounding::HalfUp => (scaled + 0.5).floor(),
+            Rounding::HalfEven => scaled.round_ties_even(),
+        };
+
+        /* (values beyond the i128 range are clamped by the cast, which is enough to overflow the format) */
+        self.fit(rounded as i128)
+    }
+
+    /** It converts a raw value of the format into a real value */
+    pub fn to_f64(&self, raw: i64) -> f64 {
+        raw as f64 * self.resolution()
+    }
+
+    /**
+        It returns the value nearest to *value* which is exactly representable in the format
+        (e.g. to prepare the weights of a network to be run in fixed-point arithmetic)
+    */
+    pub fn round_to_grid(&self, value: f64) -> f64 {
+        self.to_f64(self.quantize(value))
+    }
+
+    /** It brings a raw value back into the range of the format, according to the overflow mode */
+    pub fn fit(&self, raw: i128) -> i64 {
+        match self.overflow {
+            Overflow::Saturate => raw.clamp(self.min_raw() as i128, self.max_raw() as i128) as i64,
+            Overflow::Wrap => {
+                /* (sign extension of the *width* least significant bits) */
+                let unused = 128 - self.get_width();
+                ((raw << unused) >> unused) as i64
+            }
+        }
+    }
+
+    pub fn add(&self, a: i64, b: i64) -> i64 {
+        self.fit(a as i128 + b as i128)
+    }
+
+    pub fn sub(&self, a: i64, b: i64) -> i64 {
+        self.fit(a as i128 - b as i128)
+    }
+
+    /** Product of two raw values (the product has 2 * frac_bits fraction bits, rounded to frac_bits) */
+    pub fn mul(&self, a: i64, b: i64) -> i64 {
+        self.fit(self.shift_right_raw(a as i128 * b as i128, self.frac_bits))
+    }
+
+    /** Arithmetic shift to the right by *shift* bits (i.e. a division by 2^shift), rounded */
+    pub fn shift_right(&self, a: i64, shift: u32) -> i64 {
+        self.fit(self.shift_right_raw(a as i128, shift))
+    }
+
+    fn shift_right_raw(&self, value: i128, shift: u32) -> i128 {
+        if shift == 0 {
+            return value;
+        }
+        let shift = shift.min(127);
+
+        match self.rounding {
+            Rounding::Floor => value >> shift,
+            Rounding::HalfUp => (value + (1i128 << (shift - 1))) >> shift,
+            Rounding::HalfEven => {
+                let floor = value >> shift;
+                let remainder = value - (floor << shift);
+                let half = 1i128 << (shift - 1);
+
+                if remainder > half || (remainder == half && floor & 1 == 1) { floor + 1 } else { floor }
+            }
+        }
+    }
+}
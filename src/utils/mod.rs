@@ -0,0 +1,4 @@
+/* * Utilities * */
+
+pub mod fixed;
+pub mod random;
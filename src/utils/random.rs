@@ -0,0 +1,53 @@
+/* * Random submodule * */
+
+/**
+    Seeded pseudo-random generator (SplitMix64).
+    The library uses it wherever some randomness is needed (e.g. to encode analog inputs into spikes),
+    so that the same seed always reproduces the same results, on every platform
+*/
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /** It returns a uniformly distributed u64 */
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /** It returns a uniformly distributed f64 in [0, 1) */
+    pub fn next_f64(&mut self) -> f64 {
+        /* (53 random bits, i.e. the precision of the f64 mantissa) */
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /** It returns true with probability p */
+    pub fn bernoulli(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    /** It returns a normally distributed f64 (mean 0, standard deviation 1), with the Box-Muller transform */
+    pub fn standard_normal(&mut self) -> f64 {
+        let u1 = 1.0 - self.next_f64();     /* (in (0, 1], so that the logarithm is finite) */
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+
+    /** It returns a uniformly distributed usize in [0, n) */
+    pub fn below(&mut self, n: usize) -> usize {
+        if n == 0 {
+            panic!("The upper bound must be greater than 0");
+        }
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+}
@@ -0,0 +1,25 @@
+#![allow(dead_code)]    /* (each test crate uses only some of the fixtures) */
+
+use std::path::PathBuf;
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::DynSNN;
+
+//Fixtures shared by the tests of the processing of a network over time (streams, continuation, snapshots)
+
+//This function builds a network with 2 inputs, a recurrent layer of 3 neurons and a recurrent layer of 2 outputs
+pub fn build_snn() -> DynSNN<LifNeuron> {
+    DynSnnBuilder::new(2)
+        .add_layer(vec![LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0); 3],
+                   vec![vec![0.1, 0.2], vec![0.3, 0.4], vec![0.5, 0.6]],
+                   vec![vec![0.0, -0.1, -0.15], vec![-0.05, 0.0, -0.1], vec![-0.15, -0.1, 0.0]])
+        .add_layer(vec![LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0); 2],
+                   vec![vec![0.2, 0.3, 0.4], vec![0.5, 0.1, 0.3]],
+                   vec![vec![0.0, -0.2], vec![-0.1, 0.0]])
+        .build()
+}
+
+//This function returns a (unique) path in the temporary directory
+pub fn temp_file(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("pds_snn_{}_{}", std::process::id(), name))
+}
@@ -0,0 +1,270 @@
+mod common;
+
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::DynSNN;
+use pds_snn::format::binary;
+use pds_snn::layer::RecurrenceMode;
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::synapses::{DynamicSynapses, StpParams};
+use pds_snn::weights::{SignPolicy, WeightsType};
+use pds_snn::wta::WtaMode;
+use common::temp_file;
+
+//Tests related to the binary network format
+
+//This function builds a small network with two layers
+fn build_snn() -> DynSNN<LifNeuron> {
+    DynSnnBuilder::new(2)
+        .add_layer(vec![
+            LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0),
+            LifNeuron::new(0.2, 0.05, 0.1, 1.0, 1.0),
+            LifNeuron::new(0.3, 0.05, 0.1, 1.0, 0.5)], vec![
+            vec![0.1, 0.2],
+            vec![0.3, 0.4],
+            vec![0.5, 0.6]], vec![
+            vec![0.0, -0.1, -0.15],
+            vec![-0.05, 0.0, -0.1],
+            vec![-0.15, -0.1, 0.0]
+        ])
+        .add_layer(vec![
+            LifNeuron::new(0.12, 0.05, 0.0, 0.8, 1.0),
+            LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0)], vec![
+            vec![0.25, 0.5, 0.125],
+            vec![0.75, 0.0, 0.5]], vec![
+            vec![0.0, -0.25],
+            vec![-0.5, 0.0]
+        ])
+        .build()
+}
+
+//This function builds a network with mixed-sign weights, which complies with Dale's law
+fn build_dale_snn() -> DynSNN<LifNeuron> {
+    DynSnnBuilder::new(2)
+        .with_sign_policy(SignPolicy::Dale)
+        .add_layer_with_same_neurons(LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0), 2,
+                                     vec![vec![0.5, -0.25], vec![0.75, -0.5]],
+                                     vec![vec![0.0, -0.1], vec![0.2, 0.0]])
+        .add_layer_with_same_neurons(LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0), 1,
+                                     vec![vec![0.4, -0.3]], vec![vec![0.0]])
+        .build()
+}
+
+//This function converts the bytes of a (version 2) network file into the ones of a version 1 file,
+//dropping the configuration of the layers from their entries
+fn to_version_1(bytes: &[u8]) -> Vec<u8> {
+    let num_layers = u64::from_le_bytes(bytes[16..24].try_into().unwrap()) as usize;
+    let mut converted = bytes[0..24].to_vec();
+    converted[4..6].copy_from_slice(&1u16.to_le_bytes());
+
+    for l in 0..num_layers {
+        let entry = &bytes[24 + l * 96..24 + l * 96 + 48];
+        converted.extend_from_slice(&entry[0..24]);
+        for offset in entry[24..48].chunks(8) {
+            let offset = u64::from_le_bytes(offset.try_into().unwrap()) - 48 * num_layers as u64;
+            converted.extend_from_slice(&offset.to_le_bytes());
+        }
+    }
+    converted.extend_from_slice(&bytes[24 + num_layers * 96..]);
+    converted
+}
+
+#[test]
+fn test_save_and_load_f64() {
+    let path = temp_file("save_and_load_f64.bin");
+    let mut snn = build_snn();
+
+    binary::save(&snn, &path, WeightsType::F64).unwrap();
+    let mut loaded = binary::load::<LifNeuron, _>(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.get_layers_number(), 2);
+
+    for (layer, loaded_layer) in snn.get_layers().iter().zip(loaded.get_layers().iter()) {
+        assert_eq!(layer.get_weights(), loaded_layer.get_weights());
+        assert_eq!(layer.get_intra_weights(), loaded_layer.get_intra_weights());
+
+        for (neuron, loaded_neuron) in layer.get_neurons().iter().zip(loaded_layer.get_neurons().iter()) {
+            assert_eq!(neuron.get_v_th(), loaded_neuron.get_v_th());
+            assert_eq!(neuron.get_v_rest(), loaded_neuron.get_v_rest());
+            assert_eq!(neuron.get_v_reset(), loaded_neuron.get_v_reset());
+            assert_eq!(neuron.get_tau(), loaded_neuron.get_tau());
+            assert_eq!(neuron.get_dt(), loaded_neuron.get_dt());
+        }
+    }
+
+    let input_spikes = vec![vec![1, 0, 1, 1, 0], vec![0, 1, 1, 0, 1]];
+    assert_eq!(snn.process(&input_spikes), loaded.process(&input_spikes));
+}
+
+#[test]
+fn test_load_mapped_f64() {
+    let path = temp_file("load_mapped_f64.bin");
+    let mut snn = build_snn();
+
+    binary::save(&snn, &path, WeightsType::F64).unwrap();
+    let mut mapped = binary::load_mapped::<LifNeuron, _>(&path).unwrap();
+
+    for (layer, mapped_layer) in snn.get_layers().iter().zip(mapped.get_layers().iter()) {
+        assert_eq!(layer.get_weights(), mapped_layer.get_weights());
+        assert_eq!(layer.get_intra_weights(), mapped_layer.get_intra_weights());
+    }
+
+    let input_spikes = vec![vec![1, 0, 1, 1, 0, 1], vec![0, 1, 1, 0, 1, 1]];
+    assert_eq!(snn.process(&input_spikes), mapped.process(&input_spikes));
+
+    drop(mapped);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_load_mapped_f32() {
+    let path = temp_file("load_mapped_f32.bin");
+    let snn = build_snn();
+
+    binary::save(&snn, &path, WeightsType::F32).unwrap();
+    let mapped = binary::load_mapped::<LifNeuron, _>(&path).unwrap();
+
+    /* all the weights of the network are exactly representable as f32, except the 0.1-like ones */
+    for (layer, mapped_layer) in snn.get_layers().iter().zip(mapped.get_layers().iter()) {
+        for (row, mapped_row) in layer.get_weights().iter().zip(mapped_layer.get_weights().iter()) {
+            for (w, mapped_w) in row.iter().zip(mapped_row.iter()) {
+                assert_eq!(*mapped_w, *w as f32 as f64);
+            }
+        }
+    }
+
+    drop(mapped);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_f32_file_is_smaller() {
+    let snn = build_snn();
+    let mut bytes_f32 = Vec::new();
+    let mut bytes_f64 = Vec::new();
+
+    binary::write(&snn, &mut bytes_f32, WeightsType::F32).unwrap();
+    binary::write(&snn, &mut bytes_f64, WeightsType::F64).unwrap();
+
+    assert_eq!(&bytes_f64[0..4], b"PSNN");
+    assert!(bytes_f32.len() < bytes_f64.len());
+}
+
+#[test]
+fn test_read_wrong_magic_number() {
+    let snn = build_snn();
+    let mut bytes = Vec::new();
+
+    binary::write(&snn, &mut bytes, WeightsType::F64).unwrap();
+    bytes[0] = b'X';
+
+    let result = binary::read::<LifNeuron, _>(&mut bytes.as_slice());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_read_truncated_file() {
+    let snn = build_snn();
+    let mut bytes = Vec::new();
+
+    binary::write(&snn, &mut bytes, WeightsType::F64).unwrap();
+    bytes.truncate(bytes.len() - 8);
+
+    let result = binary::read::<LifNeuron, _>(&mut bytes.as_slice());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_read_with_sign_policy() {
+    let snn = build_dale_snn();
+    let mut bytes = Vec::new();
+
+    binary::write(&snn, &mut bytes, WeightsType::F64).unwrap();
+
+    let error = binary::read_with_sign_policy::<LifNeuron, _>(&mut bytes.as_slice(), SignPolicy::Strict).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+    /* (the sign policy stored in the file) */
+    let loaded = binary::read::<LifNeuron, _>(&mut bytes.as_slice()).unwrap();
+    for (layer, loaded_layer) in snn.get_layers().iter().zip(loaded.get_layers().iter()) {
+        assert_eq!(loaded_layer.get_sign_policy(), SignPolicy::Dale);
+        assert_eq!(layer.get_weights(), loaded_layer.get_weights());
+    }
+}
+
+#[test]
+fn test_load_mapped_with_sign_policy() {
+    let path = temp_file("load_mapped_with_sign_policy.bin");
+    let mut snn = build_dale_snn();
+    /* (the second neuron of the first layer is inhibitory towards the next layer, but excitatory in its own) */
+    snn.set_sign_policy(SignPolicy::Unconstrained);
+    snn.set_weights(1, vec![vec![0.4, 0.3]]);
+
+    binary::save(&snn, &path, WeightsType::F64).unwrap();
+    let error = binary::load_mapped_with_sign_policy::<LifNeuron, _>(&path, SignPolicy::Dale).unwrap_err();
+    let mapped = binary::load_mapped_with_sign_policy::<LifNeuron, _>(&path, SignPolicy::Unconstrained).unwrap();
+
+    assert!(error.to_string().contains("Layer 1"));
+    assert_eq!(mapped.get_layers()[0].get_sign_policy(), SignPolicy::Unconstrained);
+
+    drop(mapped);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_layer_configuration_round_trip() {
+    let mut snn = build_dale_snn();
+    snn.set_weight_limit(Some(0.8));
+    snn.set_wta_mode(0, WtaMode::Soft(0.25));
+    snn.set_wta_mode(1, WtaMode::KWinners(1));
+    snn.set_recurrence_mode(RecurrenceMode::Settling(4));
+    let mut synapses = DynamicSynapses::new(1, 2, StpParams::new(0.5, 5.0, 10.0), 1.0);
+    synapses.set_params(0, 1, StpParams::new(0.2, 3.0, 0.0));
+    snn.set_dynamic_synapses(1, Some(synapses.clone()));
+
+    let mut bytes = Vec::new();
+    binary::write(&snn, &mut bytes, WeightsType::F64).unwrap();
+    let mut loaded = binary::read::<LifNeuron, _>(&mut bytes.as_slice()).unwrap();
+
+    for (layer, loaded_layer) in snn.get_layers().iter().zip(loaded.get_layers().iter()) {
+        assert_eq!(loaded_layer.get_sign_policy(), SignPolicy::Dale);
+        assert_eq!(loaded_layer.get_weight_limit(), Some(0.8));
+        assert_eq!(loaded_layer.get_wta_mode(), layer.get_wta_mode());
+        assert_eq!(loaded_layer.get_recurrence_mode(), RecurrenceMode::Settling(4));
+        assert_eq!(loaded_layer.get_dynamic_synapses(), layer.get_dynamic_synapses());
+    }
+    assert_eq!(loaded.get_layers()[1].get_dynamic_synapses(), Some(&synapses));
+
+    let input_spikes = vec![vec![1, 0, 1, 1, 0, 1], vec![1, 1, 1, 0, 1, 1]];
+    assert_eq!(snn.process(&input_spikes), loaded.process(&input_spikes));
+}
+
+#[test]
+fn test_read_version_1() {
+    let snn = build_snn();
+    let mut bytes = Vec::new();
+
+    binary::write(&snn, &mut bytes, WeightsType::F64).unwrap();
+    let loaded = binary::read::<LifNeuron, _>(&mut to_version_1(&bytes).as_slice()).unwrap();
+
+    for (layer, loaded_layer) in snn.get_layers().iter().zip(loaded.get_layers().iter()) {
+        assert_eq!(layer.get_weights(), loaded_layer.get_weights());
+        assert_eq!(layer.get_intra_weights(), loaded_layer.get_intra_weights());
+        assert_eq!(loaded_layer.get_sign_policy(), SignPolicy::Strict);
+        assert_eq!(loaded_layer.get_wta_mode(), WtaMode::None);
+    }
+}
+
+#[test]
+fn test_read_wrong_layer_configuration() {
+    let mut snn = build_snn();
+    snn.set_wta_mode(0, WtaMode::KWinners(2));
+    let mut bytes = Vec::new();
+
+    binary::write(&snn, &mut bytes, WeightsType::F64).unwrap();
+    /* (0 winners, in the winner-take-all parameter of the first entry) */
+    bytes[24 + 56..24 + 64].copy_from_slice(&0u64.to_le_bytes());
+
+    let error = binary::read::<LifNeuron, _>(&mut bytes.as_slice()).unwrap_err();
+    assert_eq!(error.to_string(), "Layer 0: wrong number of winners");
+}
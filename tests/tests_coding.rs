@@ -0,0 +1,91 @@
+use pds_snn::coding::decoder::{self, Decoding};
+use pds_snn::coding::encoder::{Encoder, LatencyEncoder, RateEncoder};
+
+//Tests related to the encoders and the decoders
+
+#[test]
+fn test_rate_encoder_dimensions() {
+    let mut encoder = RateEncoder::new(20, 1.0, 42);
+    let spikes = encoder.encode(&[0.0, 0.5, 1.0]);
+
+    assert_eq!(spikes.len(), 3);
+    assert!(spikes.iter().all(|neuron_spikes| neuron_spikes.len() == 20));
+    assert!(spikes.iter().flatten().all(|spike| *spike == 0 || *spike == 1));
+
+    /* a value of 0 never fires, a value of 1 (with max probability 1) always fires */
+    assert!(spikes[0].iter().all(|spike| *spike == 0));
+    assert!(spikes[2].iter().all(|spike| *spike == 1));
+}
+
+#[test]
+fn test_rate_encoder_is_reproducible() {
+    let values = [0.3, 0.6, 0.9, 0.1];
+
+    let spikes1 = RateEncoder::new(50, 0.8, 7).encode(&values);
+    let spikes2 = RateEncoder::new(50, 0.8, 7).encode(&values);
+    let spikes3 = RateEncoder::new(50, 0.8, 8).encode(&values);
+
+    assert_eq!(spikes1, spikes2);
+    assert_ne!(spikes1, spikes3);
+}
+
+#[test]
+fn test_rate_encoder_rate() {
+    let mut encoder = RateEncoder::new(10000, 0.5, 1);
+    let spikes = encoder.encode(&[0.4]);
+    let rate = decoder::firing_rates(&spikes)[0];
+
+    assert!((rate - 0.2).abs() < 0.02);
+}
+
+#[test]
+#[should_panic]
+fn test_rate_encoder_wrong_probability() {
+    RateEncoder::new(10, 1.5, 0);
+}
+
+#[test]
+fn test_latency_encoder() {
+    let mut encoder = LatencyEncoder::new(5);
+    let spikes = encoder.encode(&[1.0, 0.5, 0.0, 0.25]);
+
+    assert_eq!(spikes, vec![
+        vec![1, 0, 0, 0, 0],
+        vec![0, 0, 1, 0, 0],
+        vec![0, 0, 0, 0, 0],
+        vec![0, 0, 0, 1, 0]
+    ]);
+}
+
+#[test]
+fn test_spike_counts_and_rates() {
+    let spikes = vec![vec![1, 0, 1, 1], vec![0, 0, 0, 0], vec![0, 1, 0, 0]];
+
+    assert_eq!(decoder::spike_counts(&spikes), vec![3, 0, 1]);
+    assert_eq!(decoder::firing_rates(&spikes), vec![0.75, 0.0, 0.25]);
+    assert_eq!(decoder::first_spikes(&spikes), vec![Some(0), None, Some(1)]);
+}
+
+#[test]
+fn test_decode_class_spike_count() {
+    let spikes = vec![vec![1, 0, 1, 0], vec![0, 1, 1, 1], vec![1, 1, 1, 0]];
+
+    /* neurons #1 and #2 have the same count: the lowest index wins */
+    assert_eq!(decoder::decode_class(&spikes, Decoding::SpikeCount), Some(1));
+}
+
+#[test]
+fn test_decode_class_first_spike() {
+    let spikes = vec![vec![0, 1, 1, 0], vec![0, 1, 0, 0], vec![0, 0, 1, 1]];
+
+    /* neurons #0 and #1 fire first at t=1: the one with more spikes wins */
+    assert_eq!(decoder::decode_class(&spikes, Decoding::FirstSpike), Some(0));
+}
+
+#[test]
+fn test_decode_class_without_spikes() {
+    let spikes = vec![vec![0, 0, 0], vec![0, 0, 0]];
+
+    assert_eq!(decoder::decode_class(&spikes, Decoding::SpikeCount), None);
+    assert_eq!(decoder::decode_class(&spikes, Decoding::FirstSpike), None);
+}
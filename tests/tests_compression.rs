@@ -0,0 +1,128 @@
+use pds_snn::DynSNN;
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::coding::decoder::Decoding;
+use pds_snn::compression::pruning;
+use pds_snn::compression::quantization::{self, Granularity, Quantization, QuantizationScheme};
+use pds_snn::compression::report;
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::weights::SignPolicy;
+
+//Tests related to the post-training quantization and pruning
+
+//This function builds a network with 3 inputs and 2 outputs
+fn build_snn() -> DynSNN<LifNeuron> {
+    DynSnnBuilder::new(3)
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0), 2,
+                                     vec![vec![0.9, 0.1, 0.05], vec![0.02, 0.3, 0.7]],
+                                     vec![vec![0.0, -0.4], vec![-0.2, 0.0]])
+        .build()
+}
+
+#[test]
+fn test_symmetric_quantization() {
+    let weights = vec![vec![0.7, 0.1], vec![0.0, 0.35]];
+
+    /* 2 bits: levels -1, 0, 1 scaled by 0.7 */
+    let quantized = Quantization::new(2).quantize_matrix(&weights);
+    assert_eq!(quantized, vec![vec![0.7, 0.0], vec![0.0, 0.7]]);
+
+    /* 3 bits per channel: the second row has its own scale (0.35 / 3) */
+    let quantized = Quantization::new(3).with_granularity(Granularity::PerChannel).quantize_matrix(&weights);
+    assert_eq!(quantized[0], vec![0.7, 0.0]);
+    assert_eq!(quantized[1][0], 0.0);
+    assert!((quantized[1][1] - 0.35).abs() < 1e-12);
+}
+
+#[test]
+fn test_asymmetric_quantization() {
+    let weights = vec![vec![-0.3, 0.0, 0.6, 0.45]];
+    let quantized = Quantization::new(2)
+        .with_scheme(QuantizationScheme::Asymmetric)
+        .quantize_matrix(&weights);
+
+    /* 4 levels, scale 0.3 and zero point 1: -0.3, 0, 0.3, 0.6 */
+    let expected = [-0.3, 0.0, 0.6, 0.6];
+    for (w, e) in quantized[0].iter().zip(expected) {
+        assert!((w - e).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_quantize_network_keeps_signs() {
+    let snn = build_snn();
+    let quantized = quantization::quantize(&snn, &Quantization::new(2).with_scheme(QuantizationScheme::Asymmetric));
+
+    let layer = &quantized.get_layers()[0];
+    assert!(layer.get_weights().iter().flatten().all(|w| *w >= 0.0));
+    assert!(layer.get_intra_weights().iter().flatten().all(|w| *w <= 0.0));
+    /* (the original network is untouched) */
+    assert_eq!(snn.get_layers()[0].get_weights()[0], vec![0.9, 0.1, 0.05]);
+}
+
+#[test]
+fn test_quantization_within_weight_limit() {
+    let snn = DynSnnBuilder::new(2)
+        .with_sign_policy(SignPolicy::Unconstrained)
+        .with_weight_limit(Some(0.6))
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0), 1, vec![vec![0.6, -0.1]], vec![vec![0.0]])
+        .build();
+
+    /* 4 levels, scale 0.7 / 3 and zero point 0: the level 0.7 exceeds the limit, so 0.6 becomes 1.4 / 3 */
+    let quantized = quantization::quantize(&snn, &Quantization::new(2).with_scheme(QuantizationScheme::Asymmetric));
+
+    let layer = &quantized.get_layers()[0];
+    assert_eq!(layer.get_weight_limit(), Some(0.6));
+    assert!((layer.get_weights()[0][0] - 1.4 / 3.0).abs() < 1e-12);
+    assert_eq!(layer.get_weights()[0][1], 0.0);
+}
+
+#[test]
+fn test_quantization_skips_reflexive_links() {
+    let snn = DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0), 2,
+                                     vec![vec![0.5], vec![0.5]], vec![vec![0.9, -0.4], vec![-0.2, 0.0]])
+        .build();
+
+    /* (the scale is 0.4, not 0.9: the reflexive link is never read, so it is kept as it is) */
+    let quantized = quantization::quantize(&snn, &Quantization::new(2));
+
+    assert_eq!(quantized.get_layers()[0].get_intra_weights(), vec![vec![0.9, -0.4], vec![-0.4, 0.0]]);
+}
+
+#[test]
+fn test_pruning() {
+    let weights = vec![vec![0.9, 0.1, 0.05], vec![0.02, 0.3, 0.7]];
+    let pruned = pruning::prune_matrix(&weights, 0.5);
+
+    assert_eq!(pruned, vec![vec![0.9, 0.0, 0.0], vec![0.0, 0.3, 0.7]]);
+    assert_eq!(pruning::sparsity(&pruned), 0.5);
+
+    let snn = pruning::prune(&build_snn(), 1.0, false);
+    let layer = &snn.get_layers()[0];
+    assert_eq!(pruning::sparsity(&layer.get_weights()), 1.0);
+    assert_eq!(layer.get_intra_weights(), vec![vec![0.0, -0.4], vec![-0.2, 0.0]]);
+}
+
+#[test]
+fn test_compression_report() {
+    let snn = build_snn();
+    let inputs = vec![
+        vec![vec![1, 1, 1], vec![0, 0, 0], vec![0, 0, 0]],
+        vec![vec![0, 0, 0], vec![0, 0, 0], vec![1, 1, 1]],
+    ];
+    let labels = [0, 1];
+
+    let same = report::compare(&snn, &snn, &inputs, Some(&labels), Decoding::SpikeCount, 2);
+    assert_eq!(same.original_accuracy, Some(1.0));
+    assert_eq!(same.accuracy_change(), Some(0.0));
+    assert_eq!(same.agreement, 1.0);
+    assert_eq!(same.spike_count_change(), 0.0);
+
+    /* once all the weights are pruned the network never fires */
+    let pruned = pruning::prune(&snn, 1.0, true);
+    let report = report::compare(&snn, &pruned, &inputs, None, Decoding::SpikeCount, 2);
+    assert_eq!(report.compressed_accuracy, None);
+    assert_eq!(report.compressed_spikes, 0);
+    assert_eq!(report.spike_count_change(), -1.0);
+    assert_eq!(report.agreement, 0.0);
+}
@@ -0,0 +1,100 @@
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::format::binary::SerializableNeuron;
+use pds_snn::models::neuron::coba_lif::{CobaLifNeuron, Conductance};
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::neuron::Neuron;
+
+//Tests related to the conductance-based synapses
+
+fn coba(v_th: f64, e_inh: f64) -> CobaLifNeuron {
+    CobaLifNeuron::new(v_th, 0.0, 0.0, 10.0, Conductance::new(2.0, 5.0), Conductance::new(e_inh, 10.0), 1.0)
+}
+
+#[test]
+fn test_conductance_dynamics() {
+    let mut neuron = coba(100.0, -0.5);
+
+    neuron.compute_v_mem(0, 1.0, 0.0);
+    /* g_total = 2, v_inf = 1 */
+    let expected = 1.0 - (-0.2f64).exp();
+    assert!((neuron.get_v_mem() - expected).abs() < 1e-12);
+    assert_eq!(neuron.get_g_exc(), 1.0);
+
+    neuron.compute_v_mem(3, 0.0, 0.0);
+    assert!((neuron.get_g_exc() - (-3.0f64 / 5.0).exp()).abs() < 1e-12);
+
+    neuron.initialize();
+    assert_eq!((neuron.get_v_mem(), neuron.get_g_exc(), neuron.get_g_inh()), (0.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_reversal_potential_bound() {
+    /* however strong the input, the potential cannot cross the excitatory reversal potential */
+    let mut neuron = coba(2.5, -0.5);
+    for t in 0..50 {
+        assert_eq!(neuron.compute_v_mem(t, 1000.0, 0.0), 0);
+        assert!(neuron.get_v_mem() <= 2.0);
+    }
+}
+
+#[test]
+fn test_shunting_inhibition() {
+    /* inhibitory reversal potential equal to v_rest */
+    let mut shunted = coba(100.0, 0.0);
+    shunted.compute_v_mem(0, 0.0, -4.0);
+    assert_eq!(shunted.get_v_mem(), 0.0);
+    assert_eq!(shunted.get_g_inh(), 4.0);
+
+    /* the inhibition divides the excitation... */
+    let mut excited = coba(100.0, 0.0);
+    excited.compute_v_mem(0, 1.0, 0.0);
+    shunted.initialize();
+    shunted.compute_v_mem(0, 1.0, -4.0);
+    assert!(shunted.get_v_mem() > 0.0 && shunted.get_v_mem() < excited.get_v_mem());
+
+    /* ...while the additive one of the LIF model moves the resting neuron */
+    let mut lif = LifNeuron::new(100.0, 0.0, 0.0, 10.0, 1.0);
+    lif.compute_v_mem(0, 0.0, -4.0);
+    assert!(lif.get_v_mem() < 0.0);
+}
+
+#[test]
+fn test_coba_network() {
+    /* the intra weights drive the inhibitory conductance */
+    let build = |intra_weight: f64| DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(coba(0.5, -0.5), 2, vec![vec![5.0], vec![1.0]],
+                                     vec![vec![0.0, 0.0], vec![intra_weight, 0.0]])
+        .build();
+    let spikes = vec![vec![1; 6]];
+
+    let output = build(0.0).process(&spikes);
+    assert_eq!(output[0], vec![1; 6]);
+    assert!(output[1].contains(&1));
+
+    let inhibited = build(-5.0).process(&spikes);
+    assert_eq!(inhibited[0], vec![1; 6]);
+    assert_eq!(inhibited[1], vec![0; 6]);
+}
+
+#[test]
+fn test_fire_after_single_input() {
+    /* a slowly decaying excitatory conductance keeps charging the membrane after the input spike */
+    let neuron = CobaLifNeuron::new(0.5, 0.0, 0.0, 10.0, Conductance::new(5.0, 100.0), Conductance::new(-1.0, 5.0), 1.0);
+    let mut snn = DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(neuron, 1, vec![vec![0.5]], vec![vec![0.0]])
+        .build();
+
+    let output = snn.process(&vec![vec![1, 0, 0, 0, 0, 0]]);
+    assert_eq!(output[0][0], 0);
+    assert!(output[0].contains(&1));
+}
+
+#[test]
+fn test_coba_lif_params() {
+    let neuron = coba(1.0, -0.3);
+    let copy = CobaLifNeuron::from_params(&neuron.to_params());
+
+    assert_eq!(copy.get_excitatory(), Conductance::new(2.0, 5.0));
+    assert_eq!(copy.get_inhibitory(), Conductance::new(-0.3, 10.0));
+    assert_eq!(copy.get_tau_mem(), 10.0);
+}
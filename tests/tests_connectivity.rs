@@ -0,0 +1,97 @@
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::connectivity::{Connectivity, Connector, WeightDistribution};
+use pds_snn::models::neuron::lif::LifNeuron;
+
+//Tests related to the connectivity generators
+
+fn links(weights: &[Vec<f64>]) -> usize {
+    weights.iter().map(|row| row_links(row)).sum()
+}
+
+fn row_links(row: &[f64]) -> usize {
+    row.iter().filter(|weight| **weight != 0.0).count()
+}
+
+#[test]
+fn test_all_to_all_and_one_to_one() {
+    let all = Connectivity::new(Connector::AllToAll, WeightDistribution::Constant(0.5)).weights(3, 2);
+    assert_eq!(all, vec![vec![0.5; 3]; 2]);
+
+    let one = Connectivity::new(Connector::OneToOne, WeightDistribution::Constant(1.0)).weights(3, 3);
+    assert_eq!(one, vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]]);
+}
+
+#[test]
+#[should_panic(expected = "The one-to-one connector requires layers of the same dimension")]
+fn test_one_to_one_dimensions() {
+    let _ = Connectivity::new(Connector::OneToOne, WeightDistribution::Constant(1.0)).weights(3, 2);
+}
+
+#[test]
+fn test_fixed_probability_and_in_degree() {
+    let sparse = Connectivity::new(Connector::FixedProbability(0.1), WeightDistribution::Uniform { low: 0.2, high: 0.4 })
+        .with_seed(3);
+    let weights = sparse.weights(100, 100);
+    assert!((800..1200).contains(&links(&weights)), "{}", links(&weights));
+    assert!(weights.iter().flatten().all(|w| *w == 0.0 || (0.2..0.4).contains(w)));
+    assert_eq!(weights, sparse.weights(100, 100));
+    assert_ne!(weights, sparse.clone().with_seed(4).weights(100, 100));
+
+    let in_degree = Connectivity::new(Connector::FixedInDegree(5), WeightDistribution::Constant(1.0)).weights(20, 10);
+    assert!(in_degree.iter().all(|row| row_links(row) == 5));
+
+    /* without self links */
+    let intra = Connectivity::new(Connector::FixedInDegree(3), WeightDistribution::Constant(1.0)).intra_weights(4);
+    assert!(intra.iter().enumerate().all(|(i, row)| row[i] == 0.0 && row_links(row) == 3));
+}
+
+#[test]
+fn test_gaussian_2d() {
+    let connectivity = Connectivity::new(
+        Connector::Gaussian2D { pre_shape: (10, 10), post_shape: (5, 5), sigma: 0.5, max_probability: 1.0 },
+        WeightDistribution::Constant(1.0));
+    let weights = connectivity.weights(100, 25);
+
+    /* the neuron (0, 0) is close only to the inputs in the top-left corner */
+    assert!(links(&weights[..1]) > 0);
+    assert!(weights[0].iter().enumerate().all(|(j, w)| *w == 0.0 || (j / 10 < 3 && j % 10 < 3)));
+}
+
+#[test]
+fn test_weight_distributions() {
+    let normal = Connectivity::new(Connector::AllToAll, WeightDistribution::Normal { mean: 1.0, std: 0.1 })
+        .weights(100, 100);
+    let mean = normal.iter().flatten().sum::<f64>() / 10000.0;
+    assert!((mean - 1.0).abs() < 0.01, "{}", mean);
+
+    /* (the negative samples are rectified) */
+    let rectified = Connectivity::new(Connector::AllToAll, WeightDistribution::Normal { mean: 0.0, std: 1.0 })
+        .weights(10, 10);
+    assert!(rectified.iter().flatten().all(|w| *w >= 0.0));
+
+    let log_normal = Connectivity::new(Connector::AllToAll, WeightDistribution::LogNormal { mu: 0.0, sigma: 0.5 })
+        .weights(10, 10);
+    assert!(log_normal.iter().flatten().all(|w| *w > 0.0));
+}
+
+#[test]
+fn test_lateral_inhibition() {
+    let intra = Connectivity::lateral_inhibition(0.3).intra_weights(3);
+    assert_eq!(intra, vec![vec![0.0, -0.3, -0.3], vec![-0.3, 0.0, -0.3], vec![-0.3, -0.3, 0.0]]);
+    assert_eq!(Connectivity::none().intra_weights(2), vec![vec![0.0; 2]; 2]);
+}
+
+#[test]
+fn test_add_connected_layer() {
+    let neuron = LifNeuron::new(1.0, 0.0, 0.0, 1.0, 1.0);
+    let snn = DynSnnBuilder::new(784)
+        .add_connected_layer(neuron.clone(), 1000,
+                             &Connectivity::new(Connector::FixedProbability(0.05), WeightDistribution::Uniform { low: 0.0, high: 0.1 }).with_seed(1),
+                             &Connectivity::lateral_inhibition(0.2))
+        .add_connected_layer(neuron, 10, &Connectivity::new(Connector::AllToAll, WeightDistribution::Constant(0.1)), &Connectivity::none())
+        .build();
+
+    assert_eq!(snn.get_input_layer_dimension(), 784);
+    assert_eq!(snn.get_layers()[1].get_weights(), vec![vec![0.1; 1000]; 10]);
+    assert_eq!(snn.get_intra_weight(0, 0, 1), -0.2);
+}
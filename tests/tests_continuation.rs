@@ -0,0 +1,133 @@
+mod common;
+
+use pds_snn::builders::{DynSnnBuilder, SnnBuilder};
+use pds_snn::layer::RecurrenceMode;
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::weights::SignPolicy;
+use pds_snn::{SpikeEvent, SpikingNetwork};
+use common::build_snn;
+
+//Tests related to the processing of an input in consecutive chunks
+
+fn input_spikes() -> Vec<Vec<u8>> {
+    vec![vec![1, 0, 1, 1, 0, 0, 0, 1, 0, 1], vec![0, 1, 1, 0, 0, 0, 1, 1, 0, 0]]
+}
+
+/* (columns from..to of a spikes matrix) */
+fn chunk(spikes: &[Vec<u8>], from: usize, to: usize) -> Vec<Vec<u8>> {
+    spikes.iter().map(|row| row[from..to].to_vec()).collect()
+}
+
+/* (the chunks of a spikes matrix, concatenated again) */
+fn concat(chunks: Vec<Vec<Vec<u8>>>) -> Vec<Vec<u8>> {
+    let mut spikes = vec![vec![]; chunks[0].len()];
+    for chunk in chunks {
+        spikes.iter_mut().zip(chunk).for_each(|(row, chunk_row)| row.extend(chunk_row));
+    }
+    spikes
+}
+
+#[test]
+fn test_chunks_match_whole_input() {
+    let mut snn = build_snn();
+    let expected = snn.process(&input_spikes());
+    assert_eq!(snn.get_elapsed_instants(), 10);
+
+    for boundaries in [vec![0, 5, 10], vec![0, 1, 4, 6, 10], vec![0, 3, 3, 9, 10]] {
+        snn.reset();
+        assert_eq!(snn.get_elapsed_instants(), 0);
+
+        let outputs = boundaries.windows(2)
+            .map(|bounds| snn.process_continue(&chunk(&input_spikes(), bounds[0], bounds[1])))
+            .collect();
+        assert_eq!(concat(outputs), expected);
+        assert_eq!(snn.get_elapsed_instants(), 10);
+    }
+}
+
+#[test]
+fn test_process_starts_from_initial_state() {
+    let mut snn = build_snn();
+    let first = snn.process(&chunk(&input_spikes(), 0, 5));
+    snn.process_continue(&chunk(&input_spikes(), 5, 10));
+    assert_eq!(snn.get_elapsed_instants(), 10);
+
+    /* process() always resets the network, process_continue() does not */
+    assert_eq!(snn.process(&chunk(&input_spikes(), 0, 5)), first);
+    assert_eq!(snn.get_elapsed_instants(), 5);
+}
+
+#[test]
+fn test_snn_chunks() {
+    let mut snn = SnnBuilder::new()
+        .add_layer()
+        .weights([
+            [0.1, 0.2],
+            [0.3, 0.4]
+        ]).neurons([
+        LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0),
+        LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0),
+    ]).intra_weights([
+        [0.0, -0.1],
+        [-0.05, 0.0]
+    ]).build();
+
+    let expected = snn.process(&[[1, 1, 0, 1], [1, 0, 1, 1]]);
+
+    snn.reset();
+    let first = snn.process_continue(&[[1, 1], [1, 0]]);
+    let second = snn.process_continue(&[[0, 1], [1, 1]]);
+    assert_eq!(snn.get_elapsed_instants(), 4);
+
+    for neuron in 0..2 {
+        assert_eq!([first[neuron], second[neuron]].concat(), expected[neuron]);
+    }
+}
+
+#[test]
+fn test_spiking_network_chunks() {
+    let mut network = SpikingNetwork::from(&build_snn());
+    let expected = network.process(&input_spikes());
+
+    network.reset();
+    let outputs = vec![network.process_continue(&chunk(&input_spikes(), 0, 7)),
+                       network.process_continue(&chunk(&input_spikes(), 7, 10))];
+    assert_eq!(concat(outputs), expected);
+}
+
+#[test]
+fn test_delayed_recurrence_across_chunks() {
+    /* the neuron 1 repeats the spikes of the neuron 0 in the next instant, even in the next chunk */
+    let mut snn = DynSnnBuilder::new(1)
+        .with_sign_policy(SignPolicy::Unconstrained)
+        .add_layer_with_same_neurons(LifNeuron::new(1.0, 0.0, 0.0, 1e9, 1.0), 2,
+                                     vec![vec![1.5], vec![0.0]], vec![vec![0.0, 0.0], vec![1.5, 0.0]])
+        .build();
+    snn.set_recurrence_mode(RecurrenceMode::Delayed);
+
+    assert_eq!(snn.process(&vec![vec![0, 0, 1]]), vec![vec![0, 0, 1], vec![0, 0, 0]]);
+    assert_eq!(snn.process_continue(&vec![vec![0, 0]]), vec![vec![0, 0], vec![1, 0]]);
+}
+
+#[test]
+fn test_continue_after_stream() {
+    let mut snn = build_snn();
+    let expected = snn.process(&input_spikes());
+
+    let mut stream = snn.stream();
+    for t in 0..6 {
+        stream.push(SpikeEvent::new(t as u64, input_spikes().iter().map(|row| row[t]).collect()));
+    }
+    let first = stream.close();
+    assert_eq!(snn.get_elapsed_instants(), 6);
+
+    let second = snn.process_continue(&chunk(&input_spikes(), 6, 10));
+    for (neuron, row) in second.iter().enumerate() {
+        assert_eq!(row[..], expected[neuron][6..]);
+    }
+    for event in first {
+        for (neuron, spike) in event.get_spikes().iter().enumerate() {
+            assert_eq!(*spike, expected[neuron][event.get_ts() as usize]);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::path::PathBuf;
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::datasets::aedat::{self, AedatCamera};
+use pds_snn::datasets::events::{EventBinning, PolarityChannels, PolarityEvent};
+use pds_snn::datasets::nmnist::{self, NMNIST_HEIGHT, NMNIST_WIDTH};
+use pds_snn::models::neuron::lif::LifNeuron;
+
+//Tests related to the datasets loaders
+
+//This function returns the path of an events fixture file
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/events").join(name)
+}
+
+#[test]
+fn test_load_nmnist_events() {
+    let events = nmnist::load_events(fixture("nmnist_sample.bin")).unwrap();
+
+    assert_eq!(events, vec![
+        PolarityEvent::new(0, 0, true, 0),
+        PolarityEvent::new(33, 33, false, 1500),
+        PolarityEvent::new(5, 7, true, 2999),
+        /* (after an overflow event) */
+        PolarityEvent::new(1, 2, false, 8192 + 100)
+    ]);
+}
+
+#[test]
+fn test_read_nmnist_truncated_event() {
+    let bytes = [0u8, 1, 2, 3, 4, 5, 6];
+
+    assert!(nmnist::read_events(&mut bytes.as_slice()).is_err());
+}
+
+#[test]
+fn test_load_aedat2_dvs128_events() {
+    let events = aedat::load_events(fixture("dvs128_sample.aedat"), AedatCamera::Dvs128).unwrap();
+
+    assert_eq!(events, vec![
+        PolarityEvent::new(127 - 10, 20, true, 1000),
+        PolarityEvent::new(127, 0, false, 1500)
+    ]);
+}
+
+#[test]
+fn test_load_aedat31_events() {
+    let events = aedat::load_events(fixture("davis_sample_v31.aedat"), AedatCamera::Dvs128).unwrap();
+
+    /* (the invalid event and the non-polarity packet are skipped) */
+    assert_eq!(events, vec![
+        PolarityEvent::new(3, 4, true, 10),
+        PolarityEvent::new(5, 6, false, 20),
+        PolarityEvent::new(1, 1, true, (1u64 << 31) | 5)
+    ]);
+}
+
+#[test]
+fn test_read_aedat31_truncated_packet() {
+    let mut bytes = std::fs::read(fixture("davis_sample_v31.aedat")).unwrap();
+    bytes.truncate(bytes.len() - 3);
+
+    assert!(aedat::read_events(&mut bytes.as_slice(), AedatCamera::Dvs128).is_err());
+}
+
+#[test]
+fn test_read_aedat31_malformed_packet_header() {
+    let bytes = std::fs::read(fixture("davis_sample_v31.aedat")).unwrap();
+    /* (the first packet header follows the end of the text header) */
+    let end_header = bytes.windows(12).position(|window| window == b"#!END-HEADER").unwrap();
+    let packet = end_header + bytes[end_header..].iter().position(|b| *b == b'\n').unwrap() + 1;
+
+    let field = |offset: usize| i32::from_le_bytes(bytes[packet + offset..packet + offset + 4].try_into().unwrap());
+    let (size, capacity, number) = (field(4), field(20), field(24));
+
+    /* negative or null event size and capacity, negative number of events and a payload size overflowing */
+    for header in [(-1, capacity, number), (0, capacity, number), (size, -8, number), (size, 0, number),
+                   (size, capacity, -1), (i32::MAX, i32::MAX, number)] {
+        let mut malformed = bytes.clone();
+        for (offset, value) in [(4, header.0), (20, header.1), (24, header.2)] {
+            malformed[packet + offset..packet + offset + 4].copy_from_slice(&value.to_le_bytes());
+        }
+
+        let error = aedat::read_events(&mut malformed.as_slice(), AedatCamera::Dvs128).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
+
+#[test]
+fn test_binning_separate_channels() {
+    let events = vec![
+        PolarityEvent::new(0, 0, true, 100),
+        PolarityEvent::new(1, 0, false, 150),
+        PolarityEvent::new(1, 1, true, 1099),
+        PolarityEvent::new(1, 1, true, 1100),
+        PolarityEvent::new(0, 1, false, 2100)
+    ];
+    let binning = EventBinning::new(2, 2, 1000);
+    let spikes = binning.to_spikes(&events).unwrap();
+
+    /* 2 channels x 4 pixels, 3 bins of 1000us starting from the first event (t=100) */
+    assert_eq!(binning.get_dimension(), 8);
+    assert_eq!(spikes, vec![
+        vec![0, 0, 0],  /* OFF (0, 0) */
+        vec![1, 0, 0],  /* OFF (1, 0) */
+        vec![0, 0, 1],  /* OFF (0, 1) */
+        vec![0, 0, 0],  /* OFF (1, 1) */
+        vec![1, 0, 0],  /* ON (0, 0) */
+        vec![0, 0, 0],  /* ON (1, 0) */
+        vec![0, 0, 0],  /* ON (0, 1) */
+        vec![1, 1, 0]   /* ON (1, 1) */
+    ]);
+}
+
+#[test]
+fn test_binning_downsampling_and_channels() {
+    let events = vec![
+        PolarityEvent::new(0, 0, true, 0),
+        PolarityEvent::new(3, 3, false, 5),
+        PolarityEvent::new(2, 1, true, 12)
+    ];
+
+    let merged = EventBinning::new(4, 4, 10)
+        .with_downsampling(2)
+        .with_channels(PolarityChannels::Merged)
+        .to_spikes(&events).unwrap();
+    assert_eq!(merged, vec![vec![1, 0], vec![0, 1], vec![0, 0], vec![1, 0]]);
+
+    let on_only = EventBinning::new(4, 4, 10)
+        .with_downsampling(2)
+        .with_channels(PolarityChannels::OnOnly)
+        .with_duration(30)
+        .to_spikes(&events).unwrap();
+    assert_eq!(on_only, vec![vec![1, 0, 0], vec![0, 1, 0], vec![0, 0, 0], vec![0, 0, 0]]);
+}
+
+#[test]
+fn test_binning_event_outside_sensor() {
+    let events = vec![PolarityEvent::new(4, 0, true, 0)];
+
+    assert!(EventBinning::new(4, 4, 10).to_spikes(&events).is_err());
+}
+
+#[test]
+fn test_binning_to_spike_events() {
+    let events = vec![PolarityEvent::new(0, 0, true, 0), PolarityEvent::new(1, 0, true, 25)];
+    let spike_events = EventBinning::new(2, 1, 10)
+        .with_channels(PolarityChannels::Merged)
+        .to_spike_events(&events).unwrap();
+
+    assert_eq!(spike_events.len(), 2);
+    assert_eq!(spike_events[1].get_ts(), 2);
+    assert_eq!(spike_events[1].get_spikes(), &vec![0, 1]);
+}
+
+#[test]
+fn test_process_nmnist_recording() {
+    let events = nmnist::load_events(fixture("nmnist_sample.bin")).unwrap();
+    let binning = EventBinning::new(NMNIST_WIDTH, NMNIST_HEIGHT, 1000).with_downsampling(2);
+    let spikes = binning.to_spikes(&events).unwrap();
+
+    let dimension = binning.get_dimension();
+    assert_eq!(dimension, 2 * 17 * 17);
+
+    let mut snn = DynSnnBuilder::new(dimension)
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0), 2,
+                                     vec![vec![1.0; dimension], vec![0.0; dimension]],
+                                     vec![vec![0.0, 0.0], vec![0.0, 0.0]])
+        .build();
+
+    let output_spikes = snn.process(&spikes);
+
+    assert_eq!(output_spikes.len(), 2);
+    assert_eq!(output_spikes[0].len(), 9);
+    assert_eq!(output_spikes[0], vec![1, 1, 1, 0, 0, 0, 0, 0, 1]);
+    assert!(output_spikes[1].iter().all(|spike| *spike == 0));
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use std::process::Command;
+use pds_snn::DynSNN;
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::coding::decoder::Decoding;
+use pds_snn::coding::encoder::RateEncoder;
+use pds_snn::datasets::idx;
+use pds_snn::evaluation::classification::{self, ClassificationReport};
+use pds_snn::format::binary;
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::weights::WeightsType;
+use common::temp_file;
+
+//Tests related to the IDX loader and the classification evaluation
+
+//This function encodes an IDX file of unsigned bytes
+fn idx_bytes(dims: &[u32], data: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![0, 0, 0x08, dims.len() as u8];
+    for dim in dims {
+        bytes.extend_from_slice(&dim.to_be_bytes());
+    }
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+//This function builds a network with 2 inputs and 2 outputs, each output firing with its own input
+fn build_snn() -> DynSNN<LifNeuron> {
+    DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0), 2,
+                                     vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+                                     vec![vec![0.0, 0.0], vec![0.0, 0.0]])
+        .build()
+}
+
+#[test]
+fn test_read_idx_images_and_labels() {
+    let images = idx_bytes(&[2, 2, 2], &[0, 255, 51, 102, 255, 0, 0, 0]);
+    let array = idx::read_idx(&mut images.as_slice()).unwrap();
+
+    assert_eq!(array.dims, vec![2, 2, 2]);
+    assert_eq!(array.items(), vec![vec![0.0, 255.0, 51.0, 102.0], vec![255.0, 0.0, 0.0, 0.0]]);
+
+    let mut signed = vec![0, 0, 0x0B, 1, 0, 0, 0, 2];
+    signed.extend_from_slice(&(-300i16).to_be_bytes());
+    signed.extend_from_slice(&7i16.to_be_bytes());
+    assert_eq!(idx::read_idx(&mut signed.as_slice()).unwrap().data, vec![-300.0, 7.0]);
+}
+
+#[test]
+fn test_read_idx_limited() {
+    /* (the data of the last image is truncated, but it is never read) */
+    let images = idx_bytes(&[3, 2, 2], &[0, 255, 51, 102, 255, 0, 0, 0, 1]);
+
+    let array = idx::read_idx_limited(&mut images.as_slice(), Some(2)).unwrap();
+    assert_eq!(array.dims, vec![2, 2, 2]);
+    assert_eq!(array.items(), vec![vec![0.0, 255.0, 51.0, 102.0], vec![255.0, 0.0, 0.0, 0.0]]);
+
+    assert!(idx::read_idx_limited(&mut images.as_slice(), Some(3)).is_err());
+    let labels = idx_bytes(&[2], &[4, 1]);
+    assert_eq!(idx::read_idx_limited(&mut labels.as_slice(), Some(5)).unwrap().data, vec![4.0, 1.0]);
+}
+
+#[test]
+fn test_read_idx_errors() {
+    /* (gzip magic number) */
+    let compressed = [0x1f, 0x8b, 0x08, 0x00, 0, 0, 0, 0];
+    assert!(idx::read_idx(&mut compressed.as_slice()).is_err());
+
+    let truncated = idx_bytes(&[3], &[1, 2]);
+    assert!(idx::read_idx(&mut truncated.as_slice()).is_err());
+}
+
+#[test]
+fn test_classification_report() {
+    let mut report = ClassificationReport::new(3);
+    report.record(0, Some(0));
+    report.record(0, Some(1));
+    report.record(1, Some(1));
+    report.record(1, None);
+
+    assert_eq!(report.get_total(), 4);
+    assert_eq!(report.get_correct(), 2);
+    assert_eq!(report.accuracy(), 0.5);
+    assert_eq!(report.recalls(), vec![Some(0.5), Some(0.5), None]);
+    assert_eq!(report.confusion[0], vec![1, 1, 0]);
+}
+
+#[test]
+fn test_evaluate() {
+    let snn = build_snn();
+    let samples = vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.0], vec![0.0, 0.0]];
+    let labels = vec![0, 1, 1, 0];
+
+    let mut encoder = RateEncoder::new(10, 1.0, 0);
+    let report = classification::evaluate(&snn, &samples, &labels, &mut encoder, Decoding::SpikeCount, 2);
+
+    assert_eq!(report.confusion, vec![vec![1, 0], vec![1, 1]]);
+    assert_eq!(report.unclassified, vec![1, 0]);
+    assert_eq!(report.accuracy(), 0.5);
+}
+
+#[test]
+fn test_snn_eval_limit() {
+    let network_path = temp_file("network.psnn");
+    let images_path = temp_file("images-idx3-ubyte");
+    let labels_path = temp_file("labels-idx1-ubyte");
+    binary::save(&build_snn(), &network_path, WeightsType::F64).unwrap();
+    std::fs::write(&images_path, idx_bytes(&[3, 1, 2], &[255, 0, 0, 255, 255, 0])).unwrap();
+    std::fs::write(&labels_path, idx_bytes(&[3], &[0, 1, 1])).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_snn-eval"))
+        .args(["--network", network_path.to_str().unwrap()])
+        .args(["--images", images_path.to_str().unwrap(), "--labels", labels_path.to_str().unwrap()])
+        .args(["--limit", "2", "--steps", "5"])
+        .output()
+        .unwrap();
+
+    std::fs::remove_file(&network_path).unwrap();
+    std::fs::remove_file(&images_path).unwrap();
+    std::fs::remove_file(&labels_path).unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.contains("samples: 2"));
+    assert!(stdout.contains("accuracy: 1.0000 (2/2)"));
+}
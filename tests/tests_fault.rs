@@ -0,0 +1,299 @@
+use pds_snn::DynSNN;
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::fault::campaign::{Campaign, Component, Outcome};
+use pds_snn::fault::model::{Fault, FaultTarget, FaultType, NeuronSite};
+use pds_snn::models::neuron::cuba_lif::CubaLifNeuron;
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::weights::SignPolicy;
+
+//Tests related to the fault injection
+
+//This function builds a network with 2 inputs and 2 outputs, each output firing with its own input
+fn build_snn() -> DynSNN<LifNeuron> {
+    DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0), 2,
+                                     vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+                                     vec![vec![0.0, -2.0], vec![0.0, 0.0]])
+        .build()
+}
+
+//This function returns an input in which both the inputs fire at each instant
+fn input_spikes() -> Vec<Vec<u8>> {
+    vec![vec![1, 1, 1, 1, 1], vec![1, 1, 1, 1, 1]]
+}
+
+#[test]
+fn test_fault_apply() {
+    let target = FaultTarget::Weight { neuron: 0, input: 0 };
+
+    assert_eq!(Fault::new(0, target, FaultType::BitFlip, 63).apply(1.5), -1.5);
+    assert_eq!(Fault::new(0, target, FaultType::StuckAt1, 63).apply(-1.5), -1.5);
+    assert_eq!(Fault::new(0, target, FaultType::StuckAt0, 63).apply(-1.5), 1.5);
+    /* (bit 52 is the least significant bit of the exponent) */
+    assert_eq!(Fault::new(0, target, FaultType::BitFlip, 52).apply(1.0), 0.5);
+
+    let comparator = FaultTarget::Neuron { neuron: 0, site: NeuronSite::Comparator };
+    assert_eq!(Fault::new(0, comparator, FaultType::BitFlip, 0).apply(1.0), 0.0);
+    assert_eq!(Fault::new(0, comparator, FaultType::StuckAt1, 0).apply(0.0), 1.0);
+}
+
+#[test]
+fn test_no_faults() {
+    let mut snn = build_snn();
+
+    assert_eq!(snn.process_with_faults(&input_spikes(), &[]), snn.process(&input_spikes()));
+}
+
+#[test]
+fn test_permanent_weight_fault() {
+    let snn = build_snn();
+    /* 1.0 with the most significant bit of the exponent stuck at 0 is ~1e-154 */
+    let fault = Fault::new(0, FaultTarget::Weight { neuron: 0, input: 0 }, FaultType::StuckAt0, 61);
+
+    let output = snn.process_with_faults(&input_spikes(), &[fault]);
+
+    assert_eq!(output, vec![vec![0, 0, 0, 0, 0], vec![1, 1, 1, 1, 1]]);
+}
+
+#[test]
+fn test_transient_fault_window() {
+    let mut snn = build_snn();
+    let fault = Fault::new(0, FaultTarget::Weight { neuron: 1, input: 1 }, FaultType::BitFlip, 63)
+        .with_window(1, 2);
+
+    let output = snn.process_with_faults(&input_spikes(), &[fault]);
+
+    /* (the membrane potential is still negative at the instant 3, after the end of the window) */
+    assert_eq!(output[1], vec![1, 0, 0, 0, 1]);
+    /* (the network itself is not affected) */
+    assert_eq!(snn.process(&input_spikes())[1], vec![1, 1, 1, 1, 1]);
+}
+
+#[test]
+fn test_intra_weight_fault() {
+    let snn = build_snn();
+    /* the inhibition of neuron 0 by neuron 1 (-2.0) loses its sign, from the instant 1 */
+    let fault = Fault::new(0, FaultTarget::IntraWeight { neuron: 0, from: 1 }, FaultType::StuckAt0, 63);
+
+    let healthy = snn.process_with_faults(&input_spikes(), &[]);
+    let faulty = snn.process_with_faults(&input_spikes(), &[fault]);
+
+    assert_eq!(healthy[0], vec![1, 0, 0, 0, 0]);
+    assert_eq!(faulty[0], vec![1, 1, 1, 1, 1]);
+}
+
+#[test]
+fn test_neuron_faults() {
+    let snn = build_snn();
+    let spikes = vec![vec![1, 0, 1, 0, 1], vec![0, 0, 0, 0, 0]];
+
+    let comparator = Fault::new(0, FaultTarget::Neuron { neuron: 0, site: NeuronSite::Comparator },
+                                FaultType::StuckAt0, 0);
+    assert_eq!(snn.process_with_faults(&spikes, &[comparator])[0], vec![0, 0, 0, 0, 0]);
+
+    /* a threshold of 0.5 * 2 = 1.0 is not exceeded by a single weight of 1.0 */
+    let v_th = Fault::new(0, FaultTarget::Neuron { neuron: 0, site: NeuronSite::VTh }, FaultType::StuckAt1, 52)
+        .with_window(2, 2);
+    assert_eq!(snn.process_with_faults(&spikes, &[v_th])[0], vec![1, 0, 0, 0, 1]);
+
+    /* a negative reset potential leaves the neuron below the threshold at the next spike */
+    let v_reset = Fault::new(0, FaultTarget::Neuron { neuron: 0, site: NeuronSite::VReset }, FaultType::StuckAt1, 63);
+    let spikes = vec![vec![1, 1, 1, 1, 1], vec![0, 0, 0, 0, 0]];
+    let snn = DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 1.0, 1000.0, 1.0), 2,
+                                     vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+                                     vec![vec![0.0, 0.0], vec![0.0, 0.0]])
+        .build();
+    assert_eq!(snn.process_with_faults(&spikes, &[v_reset])[0], vec![1, 0, 1, 0, 1]);
+}
+
+#[test]
+fn test_no_op_fault_on_mixed_signs() {
+    /* CUBA neurons with an excitatory and an inhibitory input (with different time constants) */
+    let snn = DynSnnBuilder::new(3)
+        .with_sign_policy(SignPolicy::Unconstrained)
+        .add_layer_with_same_neurons(CubaLifNeuron::new(0.3, 0.0, 0.0, 10.0, 2.0, 8.0, 1.0), 2,
+                                     vec![vec![0.6, -0.4, 0.5], vec![-0.3, 0.5, 0.5]],
+                                     vec![vec![0.0, -0.2], vec![0.1, 0.0]])
+        .build();
+    let spikes = vec![vec![1, 1, 0, 1, 1, 0, 1], vec![1, 0, 1, 1, 0, 1, 1], vec![0; 7]];
+
+    /* the input 2 never fires, so the faulty weight is never read */
+    let fault = Fault::new(0, FaultTarget::Weight { neuron: 0, input: 2 }, FaultType::StuckAt1, 62);
+    let golden = snn.process_with_faults(&spikes, &[]);
+
+    assert_eq!(snn.process_with_faults(&spikes, &[fault]), golden);
+}
+
+//This function builds a network of CUBA neurons, which do not support the neuron faults
+fn build_cuba_snn() -> DynSNN<CubaLifNeuron> {
+    DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(CubaLifNeuron::new(0.5, 0.0, 0.0, 10.0, 3.0, 5.0, 1.0), 2,
+                                     vec![vec![1.0, 0.0], vec![0.0, 1.0]], vec![vec![0.0, -0.5], vec![0.0, 0.0]])
+        .build()
+}
+
+#[test]
+#[should_panic(expected = "The neuron model of layer 0 does not support the neuron faults")]
+fn test_neuron_fault_on_unsupported_model() {
+    let fault = Fault::new(0, FaultTarget::Neuron { neuron: 1, site: NeuronSite::VTh }, FaultType::StuckAt0, 62);
+
+    build_cuba_snn().process_with_faults(&input_spikes(), &[fault]);
+}
+
+#[test]
+fn test_campaign_on_unsupported_model() {
+    /* (the neuron components are left out for the models which do not support them) */
+    let result = Campaign::new(20, 1).run(&build_cuba_snn(), &[input_spikes()]);
+
+    assert_eq!(result.counts().total(), 20);
+    assert!(result.runs.iter().all(|run| !matches!(run.fault.target, FaultTarget::Neuron { .. })));
+}
+
+#[test]
+#[should_panic(expected = "No layer has the components of the campaign")]
+fn test_campaign_neurons_on_unsupported_model() {
+    Campaign::new(20, 1)
+        .with_components(vec![Component::Neuron(NeuronSite::VTh)])
+        .sample_faults(&build_cuba_snn(), 5);
+}
+
+#[test]
+fn test_campaign_weights_on_unsupported_model() {
+    let result = Campaign::new(20, 1)
+        .with_components(vec![Component::Weight, Component::IntraWeight])
+        .run(&build_cuba_snn(), &[input_spikes()]);
+
+    assert_eq!(result.counts().total(), 20);
+}
+
+#[test]
+#[should_panic]
+fn test_fault_target_out_of_range() {
+    let fault = Fault::new(0, FaultTarget::Weight { neuron: 0, input: 2 }, FaultType::StuckAt1, 0);
+
+    build_snn().process_with_faults(&input_spikes(), &[fault]);
+}
+
+#[test]
+fn test_campaign_sampling_is_seeded() {
+    let snn = build_snn();
+    let campaign = Campaign::new(50, 7).with_transient_duration(2);
+
+    let faults = campaign.sample_faults(&snn, 5);
+
+    assert_eq!(faults, campaign.sample_faults(&snn, 5));
+    assert_ne!(faults, Campaign::new(50, 8).sample_faults(&snn, 5));
+    for fault in faults {
+        assert_eq!(fault.layer, 0);
+        assert!(fault.bit < 64);
+        if fault.fault_type == FaultType::BitFlip {
+            assert!(fault.start < 5 && fault.end == fault.start + 1);
+        } else {
+            assert_eq!((fault.start, fault.end), (0, u64::MAX));
+        }
+    }
+}
+
+#[test]
+fn test_campaign_intra_weight_sampling() {
+    /* (the second layer has a single neuron, i.e. no intra weights but the reflexive link) */
+    let snn = DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0), 3,
+                                     vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]], vec![vec![0.0; 3]; 3])
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0), 1, vec![vec![1.0, 1.0, 1.0]], vec![vec![0.0]])
+        .build();
+
+    let faults = Campaign::new(100, 2)
+        .with_components(vec![Component::IntraWeight])
+        .sample_faults(&snn, 5);
+
+    for fault in faults {
+        assert_eq!(fault.layer, 0);
+        match fault.target {
+            FaultTarget::IntraWeight { neuron, from } => assert!(neuron != from && from < 3),
+            _ => panic!("Unexpected fault target"),
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "No layer has the components of the campaign")]
+fn test_campaign_intra_weights_of_single_neurons() {
+    let snn = DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0), 1, vec![vec![1.0]], vec![vec![0.0]])
+        .build();
+
+    Campaign::new(10, 1).with_components(vec![Component::IntraWeight]).sample_faults(&snn, 5);
+}
+
+#[test]
+fn test_campaign_on_layer_without_inputs() {
+    /* (the first layer has no inputs, so its weights are never sampled) */
+    let snn = DynSnnBuilder::new(0)
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0), 2, vec![vec![]; 2], vec![vec![0.0; 2]; 2])
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0), 1, vec![vec![1.0, 1.0]], vec![vec![0.0]])
+        .build();
+
+    let faults = Campaign::new(50, 3).sample_faults(&snn, 5);
+
+    assert!(faults.iter().all(|fault| fault.layer == 1 || !matches!(fault.target, FaultTarget::Weight { .. })));
+}
+
+#[test]
+fn test_campaign_run() {
+    let snn = build_snn();
+    let inputs = vec![input_spikes(), vec![vec![0, 1, 0, 1, 0], vec![1, 1, 1, 1, 1]]];
+
+    let result = Campaign::new(40, 1).with_threads(4).run(&snn, &inputs);
+
+    assert_eq!(result.golden[0], snn.process_with_faults(&inputs[0], &[]));
+    assert_eq!(result.counts().total(), 40);
+    /* (the outcomes do not depend on the number of threads) */
+    assert_eq!(result.runs, Campaign::new(40, 1).run(&snn, &inputs).runs);
+}
+
+#[test]
+fn test_campaign_comparator_stuck_at_0() {
+    let snn = build_snn();
+    let inputs = vec![vec![vec![1, 1, 1], vec![1, 1, 1]]];
+
+    /* the golden class is 1 (neuron 0 is inhibited by neuron 1): if neuron 0 can never fire only the output
+       changes, while if neuron 1 can never fire neuron 0 is no longer inhibited and the class changes */
+    let result = Campaign::new(10, 3)
+        .with_components(vec![Component::Neuron(NeuronSite::Comparator)])
+        .with_fault_types(vec![FaultType::StuckAt0])
+        .run(&snn, &inputs);
+
+    for run in &result.runs {
+        let neuron = match run.fault.target {
+            FaultTarget::Neuron { neuron, .. } => neuron,
+            _ => panic!("Unexpected fault target"),
+        };
+        let expected = if neuron == 0 { Outcome::OutputChanged } else { Outcome::ClassificationChanged };
+        assert_eq!(run.outcome, expected);
+    }
+}
+
+#[test]
+fn test_campaign_csv() {
+    let snn = build_snn();
+    let result = Campaign::new(30, 5)
+        .with_components(vec![Component::Weight, Component::Neuron(NeuronSite::VTh)])
+        .run(&snn, &[input_spikes()]);
+
+    let mut summary = Vec::<u8>::new();
+    result.write_summary_csv(&mut summary).unwrap();
+    let summary = String::from_utf8(summary).unwrap();
+    let lines: Vec<&str> = summary.lines().collect();
+
+    assert!(lines[0].starts_with("layer,component,runs,masked,output_changed,classification_changed"));
+    /* 2 (layer, component) lines, 1 layer total and 2 component totals */
+    assert_eq!(lines.len(), 1 + 2 + 1 + 2);
+    assert!(lines.iter().any(|line| line.starts_with("0,all,30,")));
+    assert!(lines.iter().any(|line| line.starts_with("all,v_th,")));
+
+    let mut runs = Vec::<u8>::new();
+    result.write_runs_csv(&mut runs).unwrap();
+    assert_eq!(String::from_utf8(runs).unwrap().lines().count(), 1 + 30);
+}
@@ -0,0 +1,169 @@
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::fault::campaign::{Campaign, Component};
+use pds_snn::fault::model::{Fault, FaultTarget, FaultType, NeuronSite};
+use pds_snn::models::neuron::fixed_lif::{FixedLifNeuron, Leak};
+use pds_snn::neuron::Neuron;
+use pds_snn::utils::fixed::{FixedFormat, Overflow, Rounding};
+use pds_snn::weights::SignPolicy;
+
+//Tests related to the fixed-point arithmetic and the fixed-point LIF model
+
+#[test]
+fn test_quantize_rounding() {
+    let format = FixedFormat::new(4, 2);
+
+    assert_eq!(format.quantize(1.25), 5);
+    assert_eq!(format.quantize(1.125), 5);      /* (half up) */
+    assert_eq!(format.with_rounding(Rounding::Floor).quantize(1.2), 4);
+    assert_eq!(format.with_rounding(Rounding::HalfEven).quantize(1.125), 4);
+    assert_eq!(format.with_rounding(Rounding::HalfEven).quantize(1.375), 6);
+    assert_eq!(format.round_to_grid(0.6), 0.5);
+    assert_eq!(format.quantize(f64::NAN), 0);
+}
+
+#[test]
+fn test_overflow() {
+    let saturate = FixedFormat::new(4, 2);
+    let wrap = saturate.with_overflow(Overflow::Wrap);
+
+    assert_eq!((saturate.min_raw(), saturate.max_raw()), (-32, 31));
+    assert_eq!(saturate.quantize(100.0), 31);
+    assert_eq!(saturate.add(30, 5), 31);
+    assert_eq!(saturate.sub(-30, 5), -32);
+    assert_eq!(wrap.add(30, 5), -29);
+    assert_eq!(wrap.quantize(8.0), -32);
+}
+
+#[test]
+#[should_panic(expected = "A fixed-point format needs at least 1 integer bit (the sign) and at most 53 bits")]
+fn test_format_too_wide() {
+    FixedFormat::new(32, 22);
+}
+
+#[test]
+fn test_widest_format_state() {
+    let format = FixedFormat::new(30, 23);
+    let mut neuron = FixedLifNeuron::new(format, 1e9, 0.0, 0.0, Leak::Shift(4));
+    neuron.set_v_mem(1e9);
+    neuron.compute_v_mem(3, format.to_f64(format.min_raw() + 1), 0.0);
+
+    /* (the raw values of the widest format survive the f64 state) */
+    let mut restored = FixedLifNeuron::new(format, 1e9, 0.0, 0.0, Leak::Shift(4));
+    restored.set_state(&neuron.get_state().unwrap());
+    assert_eq!(restored.get_v_mem_raw(), neuron.get_v_mem_raw());
+    assert_eq!(restored.get_ts(), 3);
+}
+
+#[test]
+fn test_mul_and_shift() {
+    let format = FixedFormat::new(8, 4);
+
+    /* 1.5 * 2.25 = 3.375 */
+    assert_eq!(format.mul(24, 36), 54);
+    /* 0.0625 * 0.5 = 0.03125, i.e. half an LSB */
+    assert_eq!(format.mul(1, 8), 1);
+    assert_eq!(format.with_rounding(Rounding::Floor).mul(1, 8), 0);
+    assert_eq!(format.with_rounding(Rounding::HalfEven).mul(1, 8), 0);
+    assert_eq!(format.shift_right(-5, 1), -2);
+    assert_eq!(format.with_rounding(Rounding::Floor).shift_right(-5, 1), -3);
+}
+
+#[test]
+fn test_leak() {
+    let format = FixedFormat::new(8, 8);
+
+    let mut neuron = FixedLifNeuron::new(format, 100.0, 0.0, 0.0, Leak::Shift(1));
+    neuron.compute_v_mem(0, 1.0, 0.0);
+    neuron.compute_v_mem(2, 0.0, 0.0);
+    assert_eq!(neuron.get_v_mem(), 0.25);
+
+    let table = Leak::table(1.0, 1.0, 3, &format);
+    assert_eq!(table, Leak::Table(vec![256, 94, 35]));
+
+    let mut neuron = FixedLifNeuron::new(format, 100.0, 0.0, 0.0, table);
+    neuron.compute_v_mem(0, 1.0, 0.0);
+    neuron.compute_v_mem(1, 0.0, 0.0);
+    assert_eq!(neuron.get_v_mem_raw(), 94);
+    /* (after the end of the table the potential is completely decayed) */
+    neuron.compute_v_mem(5, 0.0, 0.0);
+    assert_eq!(neuron.get_v_mem_raw(), 0);
+}
+
+#[test]
+fn test_fixed_lif_network() {
+    let format = FixedFormat::new(8, 8);
+    let neuron = FixedLifNeuron::new(format, 1.0, 0.0, 0.0, Leak::Shift(1));
+
+    let mut snn = DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(neuron, 1, vec![vec![format.round_to_grid(0.625)]], vec![vec![0.0]])
+        .build();
+
+    /* v_mem (raw): 160, 80 + 160 = 240, 120 + 160 = 280 > 256 (fire, reset to 0), 160 */
+    assert_eq!(snn.process(&vec![vec![1, 1, 1, 1]]), vec![vec![0, 0, 1, 0]]);
+    assert_eq!(snn.get_layers()[0].get_neurons()[0].get_v_mem_raw(), 160);
+}
+
+#[test]
+fn test_fixed_point_accumulation() {
+    /* (4.2 format: the raw values are in [-32, 31], i.e. [-8, 7.75]) */
+    let saturate = FixedFormat::new(4, 2);
+    let wrap = saturate.with_overflow(Overflow::Wrap);
+    let build = |format: FixedFormat| DynSnnBuilder::new(3)
+        .with_sign_policy(SignPolicy::Unconstrained)
+        .add_layer_with_same_neurons(FixedLifNeuron::new(format, 5.0, 0.0, 0.0, Leak::Shift(1)), 1,
+                                     vec![vec![6.0, 6.0, -6.0]], vec![vec![0.0]])
+        .build();
+    let spikes = vec![vec![1], vec![1], vec![1]];
+
+    /* saturating accumulator: 24 + 24 = 31, 31 - 24 = 7 (1.75, under the threshold), instead of 6 */
+    assert_eq!(build(saturate).process(&spikes), vec![vec![0]]);
+    /* wrapping accumulator: 24 + 24 = -16, -16 - 24 = 24 (6, over the threshold) */
+    assert_eq!(build(wrap).process(&spikes), vec![vec![1]]);
+
+    /* (the faulty weights are accumulated in place of the correct ones: -6 becomes 6, so 31 + 24 = 31) */
+    let sign = Fault::new(0, FaultTarget::Weight { neuron: 0, input: 2 }, FaultType::StuckAt0, 63);
+    assert_eq!(build(saturate).process_with_faults(&spikes, &[sign]), vec![vec![1]]);
+}
+
+#[test]
+fn test_fixed_lif_neuron_faults() {
+    let format = FixedFormat::new(8, 8);
+    let snn = DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(FixedLifNeuron::new(format, 1.0, 0.0, 0.0, Leak::Shift(1)), 1,
+                                     vec![vec![format.round_to_grid(0.625)]], vec![vec![0.0]])
+        .build();
+    let spikes = vec![vec![1, 1, 1, 1]];
+    let site_fault = |site: NeuronSite, fault_type: FaultType, bit: u8| {
+        Fault::new(0, FaultTarget::Neuron { neuron: 0, site }, fault_type, bit)
+    };
+
+    assert_eq!(snn.process_with_faults(&spikes, &[]), vec![vec![0, 0, 1, 0]]);
+    /* the raw threshold 256 loses its only bit, so every positive potential fires */
+    assert_eq!(snn.process_with_faults(&spikes, &[site_fault(NeuronSite::VTh, FaultType::StuckAt0, 8)]),
+               vec![vec![1, 1, 1, 1]]);
+    /* the raw weighted sum 160 becomes 160 + 256 = 416 */
+    assert_eq!(snn.process_with_faults(&spikes, &[site_fault(NeuronSite::Adder, FaultType::StuckAt1, 8)]),
+               vec![vec![1, 1, 1, 1]]);
+    /* the sign bit of the 16 bits register makes the potential negative */
+    assert_eq!(snn.process_with_faults(&spikes, &[site_fault(NeuronSite::VMem, FaultType::StuckAt1, 15)]),
+               vec![vec![0, 0, 0, 0]]);
+    assert_eq!(snn.process_with_faults(&spikes, &[site_fault(NeuronSite::Comparator, FaultType::StuckAt1, 0)]),
+               vec![vec![1, 1, 1, 1]]);
+
+    /* (the campaigns sample only the bits of the registers) */
+    let faults = Campaign::new(100, 5).with_components(vec![Component::Neuron(NeuronSite::VMem)]).sample_faults(&snn, 4);
+    assert!(faults.iter().all(|fault| fault.bit < 16));
+}
+
+#[test]
+#[should_panic(expected = "The bit 16 does not exist in the neuron 0 of layer 0 (16 bits)")]
+fn test_fixed_lif_fault_beyond_width() {
+    let format = FixedFormat::new(8, 8);
+    let snn = DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(FixedLifNeuron::new(format, 1.0, 0.0, 0.0, Leak::Shift(1)), 1,
+                                     vec![vec![0.5]], vec![vec![0.0]])
+        .build();
+
+    snn.process_with_faults(&vec![vec![1]], &[Fault::new(0, FaultTarget::Neuron { neuron: 0, site: NeuronSite::VTh },
+                                                          FaultType::BitFlip, 16)]);
+}
@@ -0,0 +1,195 @@
+use std::path::PathBuf;
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::DynSNN;
+use pds_snn::format::graph::{self, GraphNode};
+use pds_snn::models::neuron::cuba_lif::{CubaLifNeuron, SynapseKernel};
+use pds_snn::models::neuron::lif::LifNeuron;
+
+//Tests related to the import and export of the JSON graphs
+
+//This function returns the path of a graph fixture file
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/graph").join(name)
+}
+
+//This function builds a network equivalent to the one in the two_layers fixture
+fn build_snn() -> DynSNN<LifNeuron> {
+    DynSnnBuilder::new(2)
+        .add_layer(vec![
+            LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0),
+            LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0),
+            LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0)], vec![
+            vec![0.1, 0.2],
+            vec![0.3, 0.4],
+            vec![0.5, 0.6]], vec![
+            vec![0.0, -0.1, -0.15],
+            vec![-0.05, 0.0, -0.1],
+            vec![-0.15, -0.1, 0.0]
+        ])
+        .add_layer(vec![
+            LifNeuron::new(0.3, 0.05, 0.0, 1.0, 1.0),
+            LifNeuron::new(0.3, 0.05, 0.0, 1.0, 1.0)], vec![
+            vec![0.11, 0.29, 0.3],
+            vec![0.33, 0.41, 0.57]], vec![
+            vec![0.0, 0.0],
+            vec![0.0, 0.0]
+        ])
+        .build()
+}
+
+#[test]
+fn test_export_nodes_and_edges() {
+    let graph = graph::export(&build_snn());
+
+    assert_eq!(graph.nodes.len(), 2 + 3 * 2);
+    assert_eq!(graph.nodes["input"], GraphNode::Input { shape: vec![2] });
+    assert_eq!(graph.nodes["output"], GraphNode::Output { shape: vec![2] });
+    assert_eq!(graph.nodes["lif0"].node_type(), "LIF");
+    assert_eq!(graph.nodes["rec1"], GraphNode::Linear { weight: vec![vec![0.0, 0.0], vec![0.0, 0.0]] });
+
+    assert!(graph.edges.contains(&("lif0".to_string(), "rec0".to_string())));
+    assert!(graph.edges.contains(&("rec0".to_string(), "lif0".to_string())));
+    assert!(graph.edges.contains(&("lif1".to_string(), "output".to_string())));
+}
+
+#[test]
+fn test_export_and_import() {
+    let mut snn = build_snn();
+    let mut imported = graph::import::<LifNeuron>(&graph::export(&snn), 1.0).unwrap();
+
+    for (layer, imported_layer) in snn.get_layers().iter().zip(imported.get_layers().iter()) {
+        assert_eq!(layer.get_weights(), imported_layer.get_weights());
+        assert_eq!(layer.get_intra_weights(), imported_layer.get_intra_weights());
+    }
+
+    let input_spikes = vec![vec![1, 0, 1, 1, 0, 1], vec![0, 0, 1, 1, 1, 0]];
+    assert_eq!(snn.process(&input_spikes), imported.process(&input_spikes));
+}
+
+#[test]
+fn test_json_round_trip() {
+    let graph = graph::export(&build_snn());
+    let decoded = graph::NetworkGraph::from_json(&graph.to_json()).unwrap();
+
+    assert_eq!(graph, decoded);
+}
+
+#[test]
+fn test_load_fixture() {
+    let mut loaded = graph::load::<LifNeuron, _>(fixture("two_layers.json"), 1.0).unwrap();
+    let mut snn = build_snn();
+
+    assert_eq!(loaded.get_layers_number(), 2);
+
+    /* r = 0.5 is folded into the weights of the second layer */
+    let second_layer = &loaded.get_layers()[1];
+    assert_eq!(second_layer.get_weights(), snn.get_layers()[1].get_weights());
+    assert_eq!(second_layer.get_neurons()[0].get_v_reset(), 0.0);
+
+    let input_spikes = vec![vec![1, 0, 1, 1, 0, 1], vec![0, 0, 1, 1, 1, 0]];
+    assert_eq!(snn.process(&input_spikes), loaded.process(&input_spikes));
+}
+
+#[test]
+fn test_reject_unsupported_node() {
+    let error = graph::load::<LifNeuron, _>(fixture("conv.json"), 1.0).unwrap_err();
+
+    assert!(error.to_string().contains("Unsupported node type 'Conv2d'"));
+}
+
+#[test]
+fn test_load_cuba_lif_fixture() {
+    let mut loaded = graph::load::<CubaLifNeuron, _>(fixture("cuba.json"), 1.0).unwrap();
+    let neuron = loaded.get_neuron(0, 0);
+
+    assert_eq!((neuron.get_tau_exc(), neuron.get_tau_inh(), neuron.get_tau_mem()), (2.0, 2.0, 4.0));
+    assert_eq!((neuron.get_v_th(), neuron.get_v_reset()), (1.0, 0.0));
+    assert_eq!(neuron.get_kernel(), SynapseKernel::Exponential);
+
+    let mut snn = DynSnnBuilder::new(2)
+        .add_layer(vec![CubaLifNeuron::new(1.0, 0.0, 0.0, 4.0, 2.0, 2.0, 1.0)], vec![vec![0.5, 0.5]], vec![vec![0.0]])
+        .build();
+    let input_spikes = vec![vec![1, 1, 0, 0, 1, 0], vec![1, 0, 1, 0, 0, 0]];
+    assert_eq!(loaded.process(&input_spikes), snn.process(&input_spikes));
+
+    /* (a CubaLIF node does not describe a LifNeuron) */
+    let error = graph::load::<LifNeuron, _>(fixture("cuba.json"), 1.0).unwrap_err();
+    assert!(error.to_string().contains("Node 'lif0': expected a LIF node, found CubaLIF"));
+}
+
+#[test]
+fn test_export_and_import_cuba_lif() {
+    let mut snn = DynSnnBuilder::new(2)
+        .add_layer(vec![CubaLifNeuron::new(0.5, 0.0, 0.1, 10.0, 3.0, 5.0, 1.0),
+                        CubaLifNeuron::new(0.4, 0.0, 0.0, 8.0, 2.0, 2.0, 1.0).with_kernel(SynapseKernel::Alpha)],
+                   vec![vec![0.4, 0.2], vec![0.1, 0.5]], vec![vec![0.0, -0.3], vec![-0.2, 0.0]])
+        .build();
+
+    let exported = graph::export(&snn);
+    assert_eq!(exported.nodes["lif0"].node_type(), "CubaLIF");
+    let decoded = graph::NetworkGraph::from_json(&exported.to_json()).unwrap();
+    assert_eq!(decoded, exported);
+
+    let mut imported = graph::import::<CubaLifNeuron>(&decoded, 1.0).unwrap();
+    assert_eq!(imported.get_neuron(0, 1).get_kernel(), SynapseKernel::Alpha);
+    assert_eq!(imported.get_neuron(0, 0).get_tau_inh(), 5.0);
+
+    let input_spikes = vec![vec![1, 0, 1, 1, 0, 1, 0, 0], vec![0, 0, 1, 1, 1, 0, 0, 0]];
+    assert_eq!(imported.process(&input_spikes), snn.process(&input_spikes));
+}
+
+#[test]
+fn test_reject_affine_bias() {
+    let mut graph = graph::export(&build_snn());
+    graph.nodes.insert("fc0".to_string(), GraphNode::Affine {
+        weight: vec![vec![0.1, 0.2], vec![0.3, 0.4], vec![0.5, 0.6]],
+        bias: vec![0.0, 0.1, 0.0]
+    });
+
+    assert!(graph::import::<LifNeuron>(&graph, 1.0).is_err());
+}
+
+#[test]
+fn test_reject_wrong_dimensions() {
+    let mut graph = graph::export(&build_snn());
+    graph.nodes.insert("fc1".to_string(), GraphNode::Affine {
+        weight: vec![vec![0.1, 0.2], vec![0.3, 0.4]],
+        bias: vec![0.0, 0.0]
+    });
+
+    assert!(graph::import::<LifNeuron>(&graph, 1.0).is_err());
+}
+
+#[test]
+fn test_reject_cycle() {
+    let mut graph = graph::export(&build_snn());
+    graph.edges.retain(|(_, target)| target != "output");
+    graph.edges.push(("lif1".to_string(), "input".to_string()));
+
+    let error = graph::import::<LifNeuron>(&graph, 1.0).unwrap_err();
+
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("Node 'input' is reached twice"));
+}
+
+#[test]
+fn test_reject_loop_back_to_first_layer() {
+    let mut graph = graph::export(&build_snn());
+    graph.edges.retain(|(_, target)| target != "output");
+    graph.edges.push(("lif1".to_string(), "fc0".to_string()));
+
+    let error = graph::import::<LifNeuron>(&graph, 1.0).unwrap_err();
+
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_reject_node_with_two_predecessors() {
+    let mut graph = graph::export(&build_snn());
+    graph.nodes.insert("side".to_string(), GraphNode::Linear { weight: vec![vec![0.1; 3]; 3] });
+    graph.edges.push(("side".to_string(), "fc1".to_string()));
+
+    let error = graph::import::<LifNeuron>(&graph, 1.0).unwrap_err();
+
+    assert!(error.to_string().contains("Node 'fc1' has more than one incoming forward connection"));
+}
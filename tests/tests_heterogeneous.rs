@@ -0,0 +1,72 @@
+use pds_snn::DynSNN;
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::models::neuron::fixed_lif::{FixedLifNeuron, Leak};
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::neuron::{boxed, BoxedNeuron, Neuron};
+use pds_snn::utils::fixed::FixedFormat;
+
+//Tests related to the networks mixing different neuron models
+
+//Custom model: a neuron firing whenever it receives a positive input (no memory)
+#[derive(Debug, Clone)]
+struct RelayNeuron;
+
+impl Neuron for RelayNeuron {
+    fn compute_v_mem(&mut self, _t: u64, extra_weighted_sum: f64, intra_weighted_sum: f64) -> u8 {
+        (extra_weighted_sum + intra_weighted_sum > 0.0) as u8
+    }
+
+    fn initialize(&mut self) {}
+}
+
+fn lif() -> LifNeuron {
+    LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0)
+}
+
+fn fixed_lif() -> FixedLifNeuron {
+    FixedLifNeuron::new(FixedFormat::new(8, 8), 0.5, 0.0, 0.0, Leak::Shift(1))
+}
+
+const WEIGHTS: [[f64; 2]; 2] = [[0.25, 0.5], [0.5, 0.25]];
+
+#[test]
+fn test_mixed_layers() {
+    let mut mixed: DynSNN<BoxedNeuron> = DynSnnBuilder::new(2)
+        .add_layer(vec![boxed(lif()), boxed(lif())], WEIGHTS.map(|row| row.to_vec()).to_vec(),
+                   vec![vec![0.0, 0.0], vec![0.0, 0.0]])
+        .add_layer(vec![boxed(fixed_lif()), boxed(fixed_lif())], WEIGHTS.map(|row| row.to_vec()).to_vec(),
+                   vec![vec![0.0, -0.25], vec![-0.25, 0.0]])
+        .build();
+
+    /* (the same network, split into two homogeneous networks) */
+    let mut first = DynSnnBuilder::new(2)
+        .add_layer(vec![lif(), lif()], WEIGHTS.map(|row| row.to_vec()).to_vec(),
+                   vec![vec![0.0, 0.0], vec![0.0, 0.0]])
+        .build();
+    let mut second = DynSnnBuilder::new(2)
+        .add_layer(vec![fixed_lif(), fixed_lif()], WEIGHTS.map(|row| row.to_vec()).to_vec(),
+                   vec![vec![0.0, -0.25], vec![-0.25, 0.0]])
+        .build();
+
+    let input_spikes = vec![vec![1, 0, 1, 1, 0, 1, 1, 1], vec![0, 1, 1, 1, 1, 0, 1, 1]];
+    let expected = second.process(&first.process(&input_spikes));
+
+    assert!(expected.iter().flatten().any(|spike| *spike == 1));
+    assert_eq!(mixed.process(&input_spikes), expected);
+}
+
+#[test]
+fn test_mixed_neurons_in_layer() {
+    let mut snn = DynSnnBuilder::new(1)
+        .add_layer(vec![boxed(RelayNeuron), boxed(LifNeuron::new(1.5, 0.0, 0.0, 100.0, 1.0))],
+                   vec![vec![1.0], vec![1.0]],
+                   vec![vec![0.0, 0.0], vec![0.0, 0.0]])
+        .build();
+
+    /* the relay fires with each input, the LIF neuron integrates two of them */
+    assert_eq!(snn.process(&vec![vec![1, 1, 1, 1]]), vec![vec![1, 1, 1, 1], vec![0, 1, 0, 1]]);
+
+    let neuron = snn.get_neuron(0, 1);
+    assert_eq!(neuron.downcast_ref::<LifNeuron>().unwrap().get_v_th(), 1.5);
+    assert!(neuron.downcast_ref::<RelayNeuron>().is_none());
+}
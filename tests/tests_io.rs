@@ -0,0 +1,219 @@
+mod common;
+
+use pds_snn::SpikeEvent;
+use pds_snn::io::{self, csv, events, npy, SpikesFormat};
+use common::temp_file;
+
+//Tests related to the spikes I/O
+
+//This function builds the bytes of a .npy file (version 1.0), as numpy.save() does
+fn npy_bytes(header: &str, data: &[u8]) -> Vec<u8> {
+    let mut header = header.to_string();
+    while !(10 + header.len() + 1).is_multiple_of(64) {
+        header.push(' ');
+    }
+    header.push('\n');
+
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+#[test]
+fn test_read_spikes_csv() {
+    let text = "# input spikes\n1,0,1\n0, 0, 1\n\n";
+    let spikes = csv::read_spikes(text.as_bytes()).unwrap();
+
+    assert_eq!(spikes, vec![vec![1, 0, 1], vec![0, 0, 1]]);
+}
+
+#[test]
+fn test_write_and_read_spikes_csv() {
+    let spikes = vec![vec![1, 0, 1, 1], vec![0, 0, 1, 0], vec![0, 1, 0, 0]];
+    let mut bytes = Vec::new();
+
+    csv::write_spikes(&mut bytes, &spikes).unwrap();
+
+    assert_eq!(String::from_utf8(bytes.clone()).unwrap(), "1,0,1,1\n0,0,1,0\n0,1,0,0\n");
+    assert_eq!(csv::read_spikes(bytes.as_slice()).unwrap(), spikes);
+}
+
+#[test]
+fn test_read_spikes_csv_wrong_spike() {
+    assert!(csv::read_spikes("1,0,2\n0,0,1\n".as_bytes()).is_err());
+}
+
+#[test]
+fn test_read_spikes_csv_different_durations() {
+    assert!(csv::read_spikes("1,0,1\n0,1\n".as_bytes()).is_err());
+}
+
+#[test]
+fn test_read_analog_csv() {
+    let values = csv::read_analog("0.5,1\n-2.25,3e-1\n".as_bytes()).unwrap();
+
+    assert_eq!(values, vec![vec![0.5, 1.0], vec![-2.25, 0.3]]);
+    assert!(csv::read_analog("0.5,x\n".as_bytes()).is_err());
+}
+
+#[test]
+fn test_read_events() {
+    let text = "# index, time\n0,1\n2 0\n1\t3\n";
+    let events = events::read_events(text.as_bytes()).unwrap();
+
+    assert_eq!(events, vec![(0, 1), (2, 0), (1, 3)]);
+    assert!(events::read_events("0,1,2\n".as_bytes()).is_err());
+    assert!(events::read_events("0,-1\n".as_bytes()).is_err());
+}
+
+#[test]
+fn test_events_to_spikes_and_back() {
+    let spikes = vec![vec![0, 1, 0, 0], vec![0, 0, 0, 1], vec![1, 0, 0, 0]];
+    let events = events::from_spikes(&spikes);
+
+    assert_eq!(events, vec![(2, 0), (0, 1), (1, 3)]);
+    assert_eq!(events::to_spikes(&events, 3, None).unwrap(), spikes);
+    assert_eq!(events::to_spikes(&events, 3, Some(5)).unwrap()[0].len(), 5);
+}
+
+#[test]
+fn test_events_out_of_bounds() {
+    assert!(events::to_spikes(&[(3, 0)], 3, None).is_err());
+    assert!(events::to_spikes(&[(0, 4)], 3, Some(4)).is_err());
+}
+
+#[test]
+fn test_events_too_long() {
+    /* (a single corrupted timestamp must not allocate the whole matrix) */
+    assert!(events::to_spikes(&[(0, 0), (1, u64::MAX)], 2, None).is_err());
+    assert!(events::to_spikes(&[(0, 0)], 2, Some(events::MAX_SPIKES_SIZE)).is_err());
+}
+
+#[test]
+fn test_write_and_read_spikes_npy() {
+    let spikes = vec![vec![1, 0, 1, 1], vec![0, 0, 1, 0], vec![0, 1, 0, 0]];
+    let mut bytes = Vec::new();
+
+    npy::write_spikes(&mut bytes, &spikes).unwrap();
+
+    assert_eq!(&bytes[0..6], b"\x93NUMPY");
+    assert_eq!((bytes.len() - 12) % 64, 0);
+    assert_eq!(npy::read_spikes(&mut bytes.as_slice()).unwrap(), spikes);
+}
+
+#[test]
+fn test_read_spikes_npy_bool_fortran_order() {
+    /* [[1, 0, 1], [0, 1, 1]] stored column by column */
+    let bytes = npy_bytes("{'descr': '|b1', 'fortran_order': True, 'shape': (2, 3), }", &[1, 0, 0, 1, 1, 1]);
+
+    assert_eq!(npy::read_spikes(&mut bytes.as_slice()).unwrap(), vec![vec![1, 0, 1], vec![0, 1, 1]]);
+}
+
+#[test]
+fn test_read_spikes_npy_wrong_dtype() {
+    let data: Vec<u8> = [1.0f64, 0.0].iter().flat_map(|v| v.to_le_bytes()).collect();
+    let bytes = npy_bytes("{'descr': '<f8', 'fortran_order': False, 'shape': (1, 2), }", &data);
+
+    assert!(npy::read_spikes(&mut bytes.as_slice()).is_err());
+}
+
+#[test]
+fn test_read_spikes_npy_wrong_spike() {
+    let bytes = npy_bytes("{'descr': '|u1', 'fortran_order': False, 'shape': (1, 2), }", &[1, 2]);
+
+    assert!(npy::read_spikes(&mut bytes.as_slice()).is_err());
+}
+
+#[test]
+fn test_read_spikes_npy_truncated() {
+    /* (the shape declares far more data than the file contains) */
+    let bytes = npy_bytes("{'descr': '|u1', 'fortran_order': False, 'shape': (1000000, 1000000), }", &[1, 0]);
+
+    let error = npy::read_spikes(&mut bytes.as_slice()).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_read_analog_npy() {
+    let data: Vec<u8> = [0.5f32, -1.0, 2.25].iter().flat_map(|v| v.to_le_bytes()).collect();
+    let bytes = npy_bytes("{'descr': '<f4', 'fortran_order': False, 'shape': (3,), }", &data);
+    assert_eq!(npy::read_analog(&mut bytes.as_slice()).unwrap(), vec![vec![0.5, -1.0, 2.25]]);
+
+    let data: Vec<u8> = [1.5f64, 0.25].iter().flat_map(|v| v.to_be_bytes()).collect();
+    let bytes = npy_bytes("{'descr': '>f8', 'fortran_order': False, 'shape': (2, 1), }", &data);
+    assert_eq!(npy::read_analog(&mut bytes.as_slice()).unwrap(), vec![vec![1.5], vec![0.25]]);
+
+    let bytes = npy_bytes("{'descr': '|u1', 'fortran_order': False, 'shape': (1, 2), }", &[0, 255]);
+    assert_eq!(npy::read_analog(&mut bytes.as_slice()).unwrap(), vec![vec![0.0, 255.0]]);
+}
+
+#[test]
+fn test_read_integer_analog_npy() {
+    let data: Vec<u8> = [-3i16, 300].iter().flat_map(|v| v.to_le_bytes()).collect();
+    let bytes = npy_bytes("{'descr': '<i2', 'fortran_order': False, 'shape': (1, 2), }", &data);
+    assert_eq!(npy::read_analog(&mut bytes.as_slice()).unwrap(), vec![vec![-3.0, 300.0]]);
+
+    let data: Vec<u8> = [-70000i32, 5].iter().flat_map(|v| v.to_be_bytes()).collect();
+    let bytes = npy_bytes("{'descr': '>i4', 'fortran_order': False, 'shape': (2,), }", &data);
+    assert_eq!(npy::read_analog(&mut bytes.as_slice()).unwrap(), vec![vec![-70000.0, 5.0]]);
+
+    let data: Vec<u8> = [-1i64, 1 << 40].iter().flat_map(|v| v.to_le_bytes()).collect();
+    let bytes = npy_bytes("{'descr': '<i8', 'fortran_order': False, 'shape': (2, 1), }", &data);
+    assert_eq!(npy::read_analog(&mut bytes.as_slice()).unwrap(), vec![vec![-1.0], vec![(1u64 << 40) as f64]]);
+
+    let data: Vec<u8> = [65535u16, 1].iter().flat_map(|v| v.to_be_bytes()).collect();
+    let bytes = npy_bytes("{'descr': '>u2', 'fortran_order': False, 'shape': (1, 2), }", &data);
+    assert_eq!(npy::read_analog(&mut bytes.as_slice()).unwrap(), vec![vec![65535.0, 1.0]]);
+
+    let data: Vec<u8> = [u32::MAX, 7].iter().flat_map(|v| v.to_le_bytes()).collect();
+    let bytes = npy_bytes("{'descr': '<u4', 'fortran_order': False, 'shape': (2,), }", &data);
+    assert_eq!(npy::read_analog(&mut bytes.as_slice()).unwrap(), vec![vec![u32::MAX as f64, 7.0]]);
+
+    let bytes = npy_bytes("{'descr': '<c8', 'fortran_order': False, 'shape': (1,), }", &[0; 8]);
+    assert!(npy::read_analog(&mut bytes.as_slice()).is_err());
+}
+
+#[test]
+fn test_write_and_read_analog_npy() {
+    let values = vec![vec![0.1, 0.2, 0.3], vec![-4.0, 5.5, 1e-3]];
+    let mut bytes = Vec::new();
+
+    npy::write_analog(&mut bytes, &values).unwrap();
+
+    assert_eq!(npy::read_analog(&mut bytes.as_slice()).unwrap(), values);
+}
+
+#[test]
+fn test_spike_events_conversions() {
+    let spikes = vec![vec![0, 1, 0, 0, 1], vec![0, 0, 0, 1, 1]];
+    let spike_events = io::to_spike_events(&spikes);
+
+    assert_eq!(spike_events.len(), 3);
+    assert_eq!(spike_events[0].get_ts(), 1);
+    assert_eq!(spike_events[2].get_spikes(), &vec![1, 1]);
+
+    assert_eq!(io::from_spike_events(&spike_events, Some(5)).unwrap(), spikes);
+    assert_eq!(io::from_spike_events(&spike_events, None).unwrap(), spikes);
+    assert!(io::from_spike_events(&[SpikeEvent::new(0, vec![1]), SpikeEvent::new(1, vec![1, 0])], None).is_err());
+}
+
+#[test]
+fn test_save_and_load_spike_events() {
+    let spike_events = vec![SpikeEvent::new(0, vec![1, 0, 1]), SpikeEvent::new(3, vec![0, 1, 0])];
+
+    for (format, name) in [(SpikesFormat::Csv, "events.csv"), (SpikesFormat::Npy, "events.npy"),
+                           (SpikesFormat::Events, "events.txt")] {
+        let path = temp_file(name);
+        assert_eq!(SpikesFormat::from_path(&path), format);
+
+        io::save_spike_events(&path, format, &spike_events).unwrap();
+        let loaded = io::load_spike_events(&path, format, 3).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[1].get_ts(), 3);
+        assert_eq!(loaded[1].get_spikes(), &vec![0, 1, 0]);
+    }
+}
@@ -0,0 +1,106 @@
+use std::sync::{Arc, Mutex};
+use pds_snn::{DynSNN, SpikeEvent, SpikingNetwork};
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::layer::{Layer, SpikingLayer};
+use pds_snn::models::neuron::lif::LifNeuron;
+
+//Tests related to the public layers and the custom layer kinds
+
+//Custom layer firing an output when at least *threshold* of its inputs fire in the same instant
+struct CoincidenceLayer {
+    inputs: usize,
+    threshold: usize,
+    resets: usize,
+}
+
+impl SpikingLayer for CoincidenceLayer {
+    fn get_input_dimension(&self) -> usize {
+        self.inputs
+    }
+
+    fn get_output_dimension(&self) -> usize {
+        1
+    }
+
+    fn process_event(&mut self, input_spike_event: &SpikeEvent) -> Option<SpikeEvent> {
+        let fired = input_spike_event.get_spikes().iter().filter(|spike| **spike == 1).count();
+
+        if fired >= self.threshold {
+            Some(SpikeEvent::new(input_spike_event.get_ts(), vec![1]))
+        } else {
+            None
+        }
+    }
+
+    fn reset(&mut self) {
+        self.resets += 1;
+    }
+}
+
+//This function builds the layer of a network with 2 inputs and 3 outputs
+fn build_layer() -> Layer<LifNeuron> {
+    Layer::new(vec![LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0); 3],
+               vec![vec![0.1, 0.2], vec![0.3, 0.4], vec![0.5, 0.6]],
+               vec![vec![0.0, -0.1, -0.15], vec![-0.05, 0.0, -0.1], vec![-0.15, -0.1, 0.0]])
+}
+
+#[test]
+fn test_dyn_snn_from_layers() {
+    let mut snn = DynSNN::new(vec![Arc::new(Mutex::new(build_layer()))]);
+    let mut built = DynSnnBuilder::new(2)
+        .add_layer(vec![LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0); 3],
+                   vec![vec![0.1, 0.2], vec![0.3, 0.4], vec![0.5, 0.6]],
+                   vec![vec![0.0, -0.1, -0.15], vec![-0.05, 0.0, -0.1], vec![-0.15, -0.1, 0.0]])
+        .build();
+
+    let input_spikes = vec![vec![1, 0, 1, 1], vec![0, 1, 1, 0]];
+    assert_eq!(snn.process(&input_spikes), built.process(&input_spikes));
+
+    let layer = build_layer();
+    assert_eq!(layer.get_input_dimension(), 2);
+    assert_eq!(layer.get_output_dimension(), 3);
+}
+
+#[test]
+fn test_spiking_network_with_custom_layer() {
+    let mut network = SpikingNetwork::new()
+        .add_layer(build_layer())
+        .add_layer(CoincidenceLayer { inputs: 3, threshold: 2, resets: 0 });
+
+    assert_eq!(network.get_layers_number(), 2);
+    assert_eq!(network.get_input_layer_dimension(), 2);
+    assert_eq!(network.get_output_layer_dimension(), 1);
+
+    let input_spikes = vec![vec![1, 0, 1, 1, 0, 1], vec![0, 0, 1, 1, 1, 0]];
+    let hidden_spikes = DynSNN::new(vec![Arc::new(Mutex::new(build_layer()))]).process(&input_spikes);
+
+    /* the custom layer fires when at least 2 hidden neurons fire */
+    let expected: Vec<u8> = (0..6)
+        .map(|t| (hidden_spikes.iter().filter(|spikes| spikes[t] == 1).count() >= 2) as u8)
+        .collect();
+    assert_eq!(network.process(&input_spikes), vec![expected]);
+}
+
+#[test]
+fn test_spiking_network_from_dyn_snn() {
+    let mut snn = DynSnnBuilder::new(2)
+        .add_layer(vec![LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0); 3],
+                   vec![vec![0.1, 0.2], vec![0.3, 0.4], vec![0.5, 0.6]],
+                   vec![vec![0.0, -0.1, -0.15], vec![-0.05, 0.0, -0.1], vec![-0.15, -0.1, 0.0]])
+        .add_layer(vec![LifNeuron::new(0.3, 0.05, 0.0, 1.0, 1.0); 2],
+                   vec![vec![0.11, 0.29, 0.3], vec![0.33, 0.41, 0.57]],
+                   vec![vec![0.0, 0.0], vec![0.0, 0.0]])
+        .build();
+    let mut network = SpikingNetwork::from(&snn);
+
+    let input_spikes = vec![vec![1, 0, 1, 1, 0, 1], vec![0, 0, 1, 1, 1, 0]];
+    assert_eq!(network.process(&input_spikes), snn.process(&input_spikes));
+}
+
+#[test]
+#[should_panic(expected = "The input dimension of the layer")]
+fn test_spiking_network_wrong_dimensions() {
+    let _ = SpikingNetwork::new()
+        .add_layer(build_layer())
+        .add_layer(CoincidenceLayer { inputs: 2, threshold: 1, resets: 0 });
+}
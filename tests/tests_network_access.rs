@@ -0,0 +1,109 @@
+mod common;
+
+use pds_snn::DynSNN;
+use pds_snn::builders::{DynSnnBuilder, SnnBuilder};
+use pds_snn::format::binary;
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::weights::WeightsType;
+use common::temp_file;
+
+//Tests related to the access to the weights and the neurons of a built network
+
+//This function builds a network with 2 inputs, 3 hidden neurons and 2 outputs
+fn build_dyn_snn() -> DynSNN<LifNeuron> {
+    DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0), 3,
+                                     vec![vec![0.1, 0.2], vec![0.3, 0.4], vec![0.5, 0.6]],
+                                     vec![vec![0.0, -0.1, -0.15], vec![-0.05, 0.0, -0.1], vec![-0.15, -0.1, 0.0]])
+        .add_layer_with_same_neurons(LifNeuron::new(0.3, 0.05, 0.0, 1.0, 1.0), 2,
+                                     vec![vec![0.11, 0.29, 0.3], vec![0.33, 0.41, 0.57]],
+                                     vec![vec![0.0, 0.0], vec![0.0, 0.0]])
+        .build()
+}
+
+#[test]
+fn test_dyn_snn_set_weights() {
+    let mut snn = build_dyn_snn();
+
+    snn.set_weight(1, 0, 2, 0.9);
+    snn.set_intra_weight(0, 2, 1, -0.5);
+    assert_eq!(snn.get_weight(1, 0, 2), 0.9);
+    assert_eq!(snn.get_intra_weight(0, 2, 1), -0.5);
+    assert_eq!(snn.get_layers()[1].get_weights()[0], vec![0.11, 0.29, 0.9]);
+
+    snn.set_weights(0, vec![vec![0.0, 0.0], vec![0.0, 0.0], vec![0.0, 0.0]]);
+    assert_eq!(snn.process(&vec![vec![1, 1, 1], vec![1, 1, 1]]), vec![vec![0, 0, 0], vec![0, 0, 0]]);
+}
+
+#[test]
+fn test_dyn_snn_set_neuron() {
+    let mut snn = build_dyn_snn();
+
+    snn.set_neuron(1, 1, LifNeuron::new(0.7, 0.0, 0.0, 2.0, 1.0));
+
+    assert_eq!(snn.get_neuron(1, 1).get_v_th(), 0.7);
+    assert_eq!(snn.get_neuron(1, 0).get_v_th(), 0.3);
+}
+
+#[test]
+fn test_snn_set_weights() {
+    let mut snn = SnnBuilder::new()
+        .add_layer()
+        .weights([
+            [0.1, 0.2],
+            [0.3, 0.4]
+        ]).neurons([
+        LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0),
+        LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0),
+    ]).intra_weights([
+        [0.0, -0.1],
+        [-0.05, 0.0]
+    ]).build();
+
+    assert_eq!(snn.process(&[[1, 1], [1, 1]])[0], [1, 0]);
+
+    snn.set_weight(0, 0, 0, 0.0);
+    snn.set_weight(0, 0, 1, 0.0);
+    assert_eq!(snn.get_weight(0, 0, 1), 0.0);
+    assert_eq!(snn.process(&[[1, 1], [1, 1]])[0], [0, 0]);
+}
+
+#[test]
+fn test_set_mapped_weight() {
+    let path = temp_file("access.psnn");
+    binary::save(&build_dyn_snn(), &path, WeightsType::F64).unwrap();
+    let mut snn: DynSNN<LifNeuron> = binary::load_mapped(&path).unwrap();
+
+    /* (the modified matrix is copied, the file is not modified) */
+    snn.set_weight(0, 1, 0, 0.8);
+    assert_eq!(snn.get_weight(0, 1, 0), 0.8);
+    assert_eq!(snn.get_weight(0, 2, 1), 0.6);
+
+    let reloaded: DynSNN<LifNeuron> = binary::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(reloaded.get_weight(0, 1, 0), 0.3);
+}
+
+#[test]
+#[should_panic(expected = "The weights must be positive")]
+fn test_set_negative_weight() {
+    build_dyn_snn().set_weight(0, 0, 0, -0.1);
+}
+
+#[test]
+#[should_panic(expected = "The intra weights must be negative")]
+fn test_set_positive_intra_weight() {
+    build_dyn_snn().set_intra_weight(0, 0, 1, 0.1);
+}
+
+#[test]
+#[should_panic(expected = "does not exist")]
+fn test_set_weight_out_of_bounds() {
+    build_dyn_snn().set_weight(0, 0, 2, 0.1);
+}
+
+#[test]
+#[should_panic(expected = "The number of columns in the weights matrix")]
+fn test_set_weights_wrong_dimensions() {
+    build_dyn_snn().set_weights(1, vec![vec![0.1, 0.2], vec![0.3, 0.4]]);
+}
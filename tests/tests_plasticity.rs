@@ -0,0 +1,83 @@
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::layer::{Layer, SpikingLayer};
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::synapses::{DynamicSynapses, StpParams};
+use pds_snn::SpikeEvent;
+
+//Tests related to the short-term plasticity of the dynamic synapses
+
+fn layer(params: StpParams, v_th: f64) -> Layer<LifNeuron> {
+    let mut layer = Layer::new(vec![LifNeuron::new(v_th, 0.0, 0.0, 1e9, 1.0)], vec![vec![1.0]], vec![vec![0.0]]);
+    layer.set_dynamic_synapses(Some(DynamicSynapses::new(1, 1, params, 1.0)));
+    layer
+}
+
+fn efficacies(layer: &mut Layer<LifNeuron>, instants: &[u64]) -> Vec<f64> {
+    instants.iter().map(|t| {
+        layer.process_event(&SpikeEvent::new(*t, vec![1]));
+        layer.get_dynamic_synapses().unwrap().get_efficacy(0, 0)
+    }).collect()
+}
+
+#[test]
+fn test_depression() {
+    /* without facilitation and recovery each spike uses half of the remaining resources */
+    let mut depressing = layer(StpParams::new(0.5, 1e9, 0.0), 100.0);
+    let values = efficacies(&mut depressing, &[0, 1, 2, 3]);
+    for (value, expected) in values.iter().zip([0.5, 0.25, 0.125, 0.0625]) {
+        assert!((value - expected).abs() < 1e-6);
+    }
+
+    /* after a long pause the resources recover */
+    let mut recovering = layer(StpParams::new(0.5, 2.0, 0.0), 100.0);
+    let values = efficacies(&mut recovering, &[0, 1, 100]);
+    assert!(values[1] < values[0]);
+    assert!((values[2] - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_facilitation() {
+    /* with instantaneous recovery the utilization grows: 0.1, 0.19, 0.271 */
+    let mut facilitating = layer(StpParams::new(0.1, 0.0, 1e9), 100.0);
+    let values = efficacies(&mut facilitating, &[0, 1, 2]);
+    for (value, expected) in values.iter().zip([0.1, 0.19, 0.271]) {
+        assert!((value - expected).abs() < 1e-6);
+    }
+
+    let synapses = facilitating.get_dynamic_synapses().unwrap();
+    assert!((synapses.get_u(0, 0) - 0.271).abs() < 1e-6);
+    assert!((synapses.get_x(0, 0) - 0.729).abs() < 1e-6);
+}
+
+#[test]
+fn test_dynamic_synapses_in_network() {
+    /* a depressing synapse on the input 0 and a facilitating one on the input 1 */
+    let mut snn = DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(LifNeuron::new(0.35, 0.0, 0.0, 1.0, 1.0), 2,
+                                     vec![vec![1.0, 0.0], vec![0.0, 1.0]], vec![vec![0.0, 0.0], vec![0.0, 0.0]])
+        .build();
+    let spikes = vec![vec![1; 5], vec![1; 5]];
+
+    /* static synapses: both neurons always fire */
+    assert_eq!(snn.process(&spikes), vec![vec![1; 5], vec![1; 5]]);
+
+    let mut synapses = DynamicSynapses::new(2, 2, StpParams::new(0.5, 1e9, 0.0), 1.0);
+    synapses.set_params(1, 1, StpParams::new(0.1, 0.0, 1e9));
+    snn.set_dynamic_synapses(0, Some(synapses));
+
+    let output = snn.process(&spikes);
+    assert_eq!(output, vec![vec![1, 0, 0, 0, 0], vec![0, 0, 1, 0, 1]]);
+
+    /* the synapses are reset at each process */
+    assert_eq!(snn.process(&spikes), output);
+
+    snn.set_dynamic_synapses(0, None);
+    assert_eq!(snn.process(&spikes), vec![vec![1; 5], vec![1; 5]]);
+}
+
+#[test]
+#[should_panic(expected = "The dynamic synapses must have the dimensions of the weights matrix")]
+fn test_dynamic_synapses_dimensions() {
+    let mut layer = layer(StpParams::new(0.5, 1.0, 0.0), 1.0);
+    layer.set_dynamic_synapses(Some(DynamicSynapses::new(1, 2, StpParams::new(0.5, 1.0, 0.0), 1.0)));
+}
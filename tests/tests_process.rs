@@ -558,3 +558,35 @@ fn test_dyn_snn_input_spikes_lower_than_input_dimension() {
 
     let _output_spikes = snn.process(&vec![vec![1,0]]);
 }
+
+#[test]
+fn test_dyn_snn_process_batch() {
+    #[rustfmt::skip]
+
+    let mut snn = DynSnnBuilder::new(2)
+        .add_layer(vec![
+            LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0),
+            LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0),
+            LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0)], vec![
+            vec![0.1, 0.2],
+            vec![0.3, 0.4],
+            vec![0.5, 0.6]], vec![
+            vec![0.0, -0.1, -0.15],
+            vec![-0.05, 0.0, -0.1],
+            vec![-0.15, -0.1, 0.0]
+        ])
+        .build();
+
+    let inputs = vec![
+        vec![vec![1,0,1],vec![0,0,1]],
+        vec![vec![1,1,1,1],vec![0,1,0,1]],
+        vec![vec![0,0],vec![0,0]],
+        vec![vec![0,1,1,0,1],vec![1,0,1,1,0]]
+    ];
+
+    let outputs = snn.process_batch(&inputs, 3);
+    let outputs_expected: Vec<Vec<Vec<u8>>> = inputs.iter().map(|input| snn.process(input)).collect();
+
+    assert_eq!(outputs, outputs_expected);
+    assert_eq!(outputs[0], vec![vec![0,0,0],vec![1,0,1],vec![1,0,1]]);
+}
@@ -0,0 +1,60 @@
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::coding::decoder;
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::readout::{ReadoutLayer, ReadoutMode};
+
+//Tests related to the non-spiking readout layer
+
+#[test]
+fn test_readout_modes() {
+    /* decay = exp(-dt / tau) = 0.5 */
+    let readout = ReadoutLayer::new(vec![vec![1.0, 0.5], vec![-1.0, 0.0]], 1.0, 2f64.ln());
+    let spikes = vec![vec![1, 0, 1, 0], vec![0, 1, 1, 0]];
+
+    let trace = readout.integrate(&spikes, ReadoutMode::Trace);
+    assert_eq!(trace.len(), 2);
+    let expected = [[1.0, 1.0, 2.0, 1.0], [-1.0, -0.5, -1.25, -0.625]];
+    for (row, expected_row) in trace.iter().zip(expected) {
+        for (value, expected_value) in row.iter().zip(expected_row) {
+            assert!((value - expected_value).abs() < 1e-12);
+        }
+    }
+
+    let max = readout.integrate(&spikes, ReadoutMode::Max);
+    assert!((max[0][0] - 2.0).abs() < 1e-12);
+    assert!((max[1][0] + 0.5).abs() < 1e-12);
+
+    let last = readout.integrate(&spikes, ReadoutMode::Final);
+    assert!((last[0][0] - 1.0).abs() < 1e-12);
+
+    assert_eq!(readout.integrate(&[vec![], vec![]], ReadoutMode::Max), vec![vec![0.0], vec![0.0]]);
+}
+
+#[test]
+fn test_dyn_snn_process_readout() {
+    let mut snn = DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0), 2,
+                                     vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+                                     vec![vec![0.0, 0.0], vec![0.0, 0.0]])
+        .build();
+    let readout = ReadoutLayer::new(vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![0.5, 0.5]], 1.0, 1.0);
+
+    let input_spikes = vec![vec![1, 1, 0, 0], vec![0, 0, 0, 1]];
+    let output_spikes = snn.process(&input_spikes);
+    let values = snn.process_readout(&input_spikes, &readout, ReadoutMode::Max);
+
+    assert_eq!(values, readout.integrate(&output_spikes, ReadoutMode::Max));
+    assert_eq!(decoder::decode_readout_class(&values), Some(0));
+    assert_eq!(decoder::decode_readout_class(&snn.process_readout(&input_spikes, &readout, ReadoutMode::Final)),
+               Some(1));
+}
+
+#[test]
+#[should_panic(expected = "readout input dimension")]
+fn test_process_readout_wrong_dimension() {
+    let mut snn = DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0), 1, vec![vec![1.0]], vec![vec![0.0]])
+        .build();
+
+    snn.process_readout(&vec![vec![1]], &ReadoutLayer::new(vec![vec![1.0, 1.0]], 1.0, 1.0), ReadoutMode::Trace);
+}
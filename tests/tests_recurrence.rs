@@ -0,0 +1,87 @@
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::layer::{Layer, RecurrenceMode, SpikingLayer};
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::weights::SignPolicy;
+use pds_snn::DynSNN;
+
+//Tests related to the semantics of the intra weights
+
+/* the neuron 0 fires at each input spike, and excites the neuron 1 through the intra weights */
+fn relay(recurrence: RecurrenceMode) -> DynSNN<LifNeuron> {
+    let mut snn = DynSnnBuilder::new(1)
+        .with_sign_policy(SignPolicy::Unconstrained)
+        .add_layer_with_same_neurons(LifNeuron::new(1.0, 0.0, 0.0, 1e9, 1.0), 2,
+                                     vec![vec![1.5], vec![0.0]], vec![vec![0.0, 0.0], vec![1.5, 0.0]])
+        .build();
+    snn.set_recurrence_mode(recurrence);
+    snn
+}
+
+#[test]
+fn test_legacy_recurrence() {
+    /* the spike of the instant 0 reaches the neuron 1 only at the next input, in the instant 3 */
+    let mut snn = relay(RecurrenceMode::Legacy);
+    assert_eq!(snn.process(&vec![vec![1, 0, 0, 1, 0]]), vec![vec![1, 0, 0, 1, 0], vec![0, 0, 0, 1, 0]]);
+}
+
+#[test]
+fn test_delayed_recurrence() {
+    /* each spike reaches the neuron 1 in the next instant, even without input */
+    let mut snn = relay(RecurrenceMode::Delayed);
+    assert_eq!(snn.process(&vec![vec![1, 0, 0, 1, 0]]), vec![vec![1, 0, 0, 1, 0], vec![0, 1, 0, 0, 1]]);
+
+    /* the recurrent activity stops at the end of the input */
+    assert_eq!(snn.process(&vec![vec![0, 0, 0, 1]]), vec![vec![0, 0, 0, 1], vec![0, 0, 0, 0]]);
+}
+
+#[test]
+fn test_settling_recurrence() {
+    /* each spike reaches the neuron 1 in the same instant */
+    let mut snn = relay(RecurrenceMode::Settling(10));
+    assert_eq!(snn.process(&vec![vec![1, 0, 0, 1, 0]]), vec![vec![1, 0, 0, 1, 0], vec![1, 0, 0, 1, 0]]);
+
+    /* with a single iteration the recurrent spikes are ignored */
+    snn.set_recurrence_mode(RecurrenceMode::Settling(1));
+    assert_eq!(snn.process(&vec![vec![1, 0, 0, 1, 0]]), vec![vec![1, 0, 0, 1, 0], vec![0, 0, 0, 0, 0]]);
+}
+
+#[test]
+fn test_self_sustained_activity() {
+    /* two neurons exciting each other keep firing after a single input spike */
+    let mut layer = Layer::new(vec![LifNeuron::new(1.0, 0.0, 0.0, 1e9, 1.0); 2],
+                               vec![vec![1.5], vec![0.0]], vec![vec![0.0, 0.0], vec![0.0, 0.0]]);
+    layer.set_sign_policy(SignPolicy::Unconstrained);
+    layer.set_intra_weights(vec![vec![0.0, 1.5], vec![1.5, 0.0]]);
+    layer.set_recurrence_mode(RecurrenceMode::Delayed);
+    assert_eq!(layer.get_recurrence_mode(), RecurrenceMode::Delayed);
+
+    let mut snn = DynSNN::new(vec![std::sync::Arc::new(std::sync::Mutex::new(layer.clone()))]);
+    assert_eq!(snn.process(&vec![vec![1, 0, 0, 0, 0, 0]]), vec![vec![1, 0, 1, 0, 1, 0], vec![0, 1, 0, 1, 0, 1]]);
+
+    /* the layer alone fires only when it is brought forward */
+    layer.reset();
+    assert!(layer.process_event(&pds_snn::SpikeEvent::new(0, vec![1])).is_some());
+    let events = layer.advance_to(3);
+    assert_eq!(events.iter().map(|event| event.get_ts()).collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(events[0].get_spikes(), &vec![0, 1]);
+}
+
+#[test]
+fn test_delayed_spikes_reach_next_layer() {
+    let mut snn = DynSnnBuilder::new(1)
+        .with_sign_policy(SignPolicy::Unconstrained)
+        .add_layer_with_same_neurons(LifNeuron::new(1.0, 0.0, 0.0, 1e9, 1.0), 2,
+                                     vec![vec![1.5], vec![0.0]], vec![vec![0.0, 0.0], vec![1.5, 0.0]])
+        .add_layer_with_same_neurons(LifNeuron::new(1.0, 0.0, 0.0, 1e9, 1.0), 1,
+                                     vec![vec![0.0, 1.5]], vec![vec![0.0]])
+        .build();
+    snn.set_recurrence_mode(RecurrenceMode::Delayed);
+
+    assert_eq!(snn.process(&vec![vec![1, 0, 0, 0]]), vec![vec![0, 1, 0, 0]]);
+}
+
+#[test]
+#[should_panic(expected = "The maximum number of iterations must be positive")]
+fn test_zero_iterations() {
+    relay(RecurrenceMode::Settling(0));
+}
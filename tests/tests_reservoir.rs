@@ -0,0 +1,114 @@
+use pds_snn::reservoir::liquid::{self, Reservoir};
+use pds_snn::reservoir::ridge::{self, RidgeReadout};
+use pds_snn::utils::random::Rng;
+
+//Tests related to the reservoir computing tools
+
+#[test]
+fn test_reservoir_build() {
+    let reservoir = Reservoir::new(4, 50)
+        .with_excitatory_ratio(0.8)
+        .with_connection_probability(0.2)
+        .with_spectral_radius(0.9)
+        .with_seed(7);
+    let snn = reservoir.build();
+
+    assert_eq!(snn.get_layers_number(), 1);
+    assert_eq!(snn.get_input_layer_dimension(), 4);
+    assert_eq!(snn.get_output_layer_dimension(), 50);
+
+    /* same seed, same network */
+    assert_eq!(snn.get_layers()[0].get_intra_weights(), reservoir.build().get_layers()[0].get_intra_weights());
+
+    /* Dale's law: excitatory neurons (the first 40) have positive outgoing weights */
+    let intra_weights = snn.get_layers()[0].get_intra_weights();
+    for row in &intra_weights {
+        assert!(row[..40].iter().all(|weight| *weight >= 0.0));
+        assert!(row[40..].iter().all(|weight| *weight <= 0.0));
+    }
+    assert!(intra_weights.iter().enumerate().all(|(i, row)| row[i] == 0.0));
+
+    /* spectral scaling */
+    let radius = liquid::spectral_radius(&intra_weights, &mut Rng::new(1));
+    assert!((radius - 0.9).abs() < 0.05, "{}", radius);
+}
+
+#[test]
+fn test_spectral_radius() {
+    /* rotation (complex eigenvalues) scaled by 2 */
+    let rotation = vec![vec![0.0, -2.0], vec![2.0, 0.0]];
+    assert!((liquid::spectral_radius(&rotation, &mut Rng::new(0)) - 2.0).abs() < 1e-9);
+
+    let diagonal = vec![vec![0.5, 0.0], vec![0.0, -3.0]];
+    assert!((liquid::spectral_radius(&diagonal, &mut Rng::new(0)) - 3.0).abs() < 1e-6);
+
+    let nilpotent = vec![vec![0.0, 1.0], vec![0.0, 0.0]];
+    assert_eq!(liquid::spectral_radius(&nilpotent, &mut Rng::new(0)), 0.0);
+}
+
+#[test]
+fn test_distance_dependence() {
+    /* with a short lambda only the close neurons are connected */
+    let local = Reservoir::new(1, 27).with_connection_probability(1.0).with_distance_dependence(0.1).build();
+    assert!(local.get_layers()[0].get_intra_weights().iter().flatten().all(|weight| *weight == 0.0));
+
+    let dense = Reservoir::new(1, 27).with_connection_probability(1.0).build();
+    assert_eq!(dense.get_layers()[0].get_intra_weights().iter().flatten().filter(|w| **w != 0.0).count(), 27 * 26);
+}
+
+#[test]
+#[should_panic(expected = "The connection probability must be in the range [0, 1]")]
+fn test_reservoir_invalid_probability() {
+    let _ = Reservoir::new(1, 10).with_connection_probability(1.5);
+}
+
+#[test]
+fn test_filter_spikes() {
+    let filtered = liquid::filter_spikes(&[vec![1, 0, 1], vec![0, 0, 0]], 1.0 / 2f64.ln());
+
+    assert_eq!(filtered.len(), 3);
+    assert_eq!(filtered[0], vec![1.0, 0.0]);
+    assert!((filtered[1][0] - 0.5).abs() < 1e-12);
+    assert!((filtered[2][0] - 1.25).abs() < 1e-12);
+}
+
+#[test]
+fn test_ridge_regression() {
+    /* y = 2 x0 - x1 + 3 */
+    let states: Vec<Vec<f64>> = (0..20).map(|i| vec![i as f64, (i * i % 7) as f64]).collect();
+    let targets: Vec<Vec<f64>> = states.iter().map(|x| vec![2.0 * x[0] - x[1] + 3.0]).collect();
+
+    let readout = RidgeReadout::fit(&states, &targets, 1e-9);
+    assert!((readout.get_weights()[0][0] - 2.0).abs() < 1e-6);
+    assert!((readout.get_weights()[0][1] + 1.0).abs() < 1e-6);
+    assert!((readout.get_biases()[0] - 3.0).abs() < 1e-6);
+    assert!(readout.score(&states, &targets) < 1e-9);
+
+    /* the regularization shrinks the weights */
+    let regularized = RidgeReadout::fit(&states, &targets, 1e4);
+    assert!(regularized.get_weights()[0][0].abs() < 2.0);
+}
+
+#[test]
+fn test_reservoir_classification() {
+    /* two classes: spikes on the first or on the second half of the inputs */
+    let mut rng = Rng::new(3);
+    let mut inputs = vec![];
+    let mut labels = vec![];
+    for sample in 0..40 {
+        let label = sample % 2;
+        inputs.push((0..8).map(|input|
+            (0..20).map(|_| (input / 4 == label && rng.bernoulli(0.5)) as u8).collect()
+        ).collect::<Vec<Vec<u8>>>());
+        labels.push(label);
+    }
+
+    let snn = Reservoir::new(8, 40).with_connection_probability(0.2).with_seed(5).build();
+    let states = liquid::collect_states(&snn, &inputs, 5.0, 4);
+    assert_eq!(states.len(), 40);
+    assert_eq!(states[0].len(), 40);
+
+    let readout = RidgeReadout::fit(&states[..30], &ridge::one_hot(&labels[..30], 2), 1e-2);
+    let correct = (30..40).filter(|&i| readout.predict_class(&states[i]) == Some(labels[i])).count();
+    assert!(correct >= 9, "{}", correct);
+}
@@ -0,0 +1,203 @@
+use pds_snn::builders::{DynSnnBuilder, SnnBuilder};
+use pds_snn::format::graph;
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::weights::SignPolicy;
+
+//Tests related to the sign policies of the weights
+
+fn neuron() -> LifNeuron {
+    LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0)
+}
+
+#[test]
+fn test_validate() {
+    let mixed = vec![vec![0.5, -0.2], vec![0.3, -0.1]];
+    let not_dale = vec![vec![0.5, -0.2], vec![-0.3, -0.1]];
+
+    assert_eq!(SignPolicy::Strict.validate(&mixed, false), Err("The weights must be positive".to_string()));
+    assert!(SignPolicy::Dale.validate(&mixed, false).is_ok());
+    assert!(SignPolicy::Dale.validate(&not_dale, false).unwrap_err().contains("input 0"));
+    assert!(SignPolicy::Unconstrained.validate(&not_dale, false).is_ok());
+
+    /* the reflexive links of the intra weights are ignored */
+    let intra_weights = vec![vec![1.0, -0.2], vec![-0.3, 1.0]];
+    assert!(SignPolicy::Strict.validate(&intra_weights, true).is_ok());
+    assert!(SignPolicy::Strict.validate(&intra_weights, false).is_err());
+}
+
+#[test]
+#[should_panic(expected = "The weights must be positive")]
+fn test_strict_by_default() {
+    let _ = DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(neuron(), 1, vec![vec![0.5, -0.5]], vec![vec![0.0]])
+        .build();
+}
+
+#[test]
+fn test_unconstrained_network() {
+    /* inhibitory feed-forward projection and excitatory recurrence */
+    let mut snn = DynSnnBuilder::new(2)
+        .with_sign_policy(SignPolicy::Unconstrained)
+        .add_layer_with_same_neurons(neuron(), 2, vec![vec![1.0, -1.0], vec![1.0, 0.0]],
+                                     vec![vec![0.0, 0.0], vec![0.0, 0.0]])
+        .add_layer_with_same_neurons(neuron(), 2, vec![vec![1.0, 0.0], vec![0.0, 0.3]],
+                                     vec![vec![0.0, 0.2], vec![0.2, 0.0]])
+        .build();
+
+    let output = snn.process(&vec![vec![1, 1, 0], vec![0, 1, 1]]);
+    /* (the neuron 1 of the second layer fires at the instant 1 thanks to the excitatory link from the neuron 0) */
+    assert_eq!(output, vec![vec![1, 0, 0], vec![0, 1, 0]]);
+
+    /* the setters follow the policy of the network */
+    snn.set_weight(0, 0, 1, -0.7);
+    snn.set_intra_weight(1, 0, 1, 0.3);
+    assert_eq!(snn.get_layers()[1].get_sign_policy(), SignPolicy::Unconstrained);
+}
+
+#[test]
+#[should_panic(expected = "The outgoing weights of the neuron 1 must have the same sign (Dale's law)")]
+fn test_dale_intra_weights() {
+    let _ = DynSnnBuilder::new(1)
+        .with_sign_policy(SignPolicy::Dale)
+        .add_layer_with_same_neurons(neuron(), 3, vec![vec![1.0], vec![1.0], vec![1.0]],
+                                     vec![vec![0.0, 0.5, 0.0], vec![0.0, 0.0, 0.0], vec![0.0, -0.5, 0.0]])
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "in its layer and in the next one (Dale's law)")]
+fn test_dale_across_layers() {
+    /* the neuron 0 of the first layer is inhibitory in its layer, but excitatory towards the next one */
+    let _ = DynSnnBuilder::new(1)
+        .with_sign_policy(SignPolicy::Dale)
+        .add_layer_with_same_neurons(neuron(), 2, vec![vec![1.0], vec![1.0]], vec![vec![0.0, 0.0], vec![-0.5, 0.0]])
+        .add_layer_with_same_neurons(neuron(), 1, vec![vec![0.5, 0.5]], vec![vec![0.0]])
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "The outgoing weights of the input 0 must have the same sign (Dale's law)")]
+fn test_dale_setter() {
+    let mut snn = DynSnnBuilder::new(1)
+        .with_sign_policy(SignPolicy::Dale)
+        .add_layer_with_same_neurons(neuron(), 2, vec![vec![1.0], vec![-1.0]], vec![vec![0.0, 0.0], vec![0.0, 0.0]])
+        .build();
+
+    snn.set_weight(0, 1, 0, 0.0);
+    snn.set_weight(0, 0, 0, -0.5);
+    snn.set_weight(0, 0, 0, 0.5);
+    snn.set_weight(0, 1, 0, -0.5);
+}
+
+#[test]
+#[should_panic(expected = "The weights must be in the range [-1, 1]")]
+fn test_weight_limit() {
+    let _ = DynSnnBuilder::new(1)
+        .with_weight_limit(Some(1.0))
+        .add_layer_with_same_neurons(neuron(), 1, vec![vec![1.5]], vec![vec![0.0]])
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "The weights must be in the range [-1, 1]")]
+fn test_weight_limit_setter() {
+    let mut snn = DynSnnBuilder::new(1)
+        .with_weight_limit(Some(1.0))
+        .add_layer_with_same_neurons(neuron(), 2, vec![vec![1.0], vec![0.5]], vec![vec![0.0, -0.5], vec![-0.5, 0.0]])
+        .build();
+
+    snn.set_weight(0, 0, 0, 0.8);
+    snn.set_intra_weights(0, vec![vec![0.0, -1.0], vec![-1.0, 0.0]]);
+    assert_eq!(snn.get_layers()[0].get_weight_limit(), Some(1.0));
+
+    snn.set_intra_weight(0, 1, 0, -1.5);
+}
+
+#[test]
+#[should_panic(expected = "The weights must be in the range [-1, 1]")]
+fn test_static_weight_limit_setter() {
+    let mut snn = SnnBuilder::new()
+        .with_weight_limit(Some(1.0))
+        .add_layer()
+        .weights([[0.5]])
+        .neurons([neuron()])
+        .intra_weights([[0.0]])
+        .build();
+
+    snn.set_weights(0, vec![vec![2.0]]);
+}
+
+//This function builds a network respecting Dale's law, whose first layer has an inhibitory neuron (1)
+fn build_dale_snn() -> pds_snn::DynSNN<LifNeuron> {
+    DynSnnBuilder::new(1)
+        .with_sign_policy(SignPolicy::Dale)
+        .add_layer_with_same_neurons(neuron(), 2, vec![vec![1.0], vec![1.0]], vec![vec![0.0, -0.5], vec![0.0, 0.0]])
+        .add_layer_with_same_neurons(neuron(), 2, vec![vec![0.5, -0.5], vec![0.5, 0.0]], vec![vec![0.0, 0.0], vec![0.0, 0.0]])
+        .build()
+}
+
+#[test]
+#[should_panic(expected = "in its layer and in the next one (Dale's law)")]
+fn test_dale_setter_across_layers() {
+    let mut snn = build_dale_snn();
+
+    /* (the neuron 1 of the first layer stays inhibitory) */
+    snn.set_weight(1, 1, 1, -0.2);
+    snn.set_weight(1, 1, 1, 0.2);
+}
+
+#[test]
+#[should_panic(expected = "in its layer and in the next one (Dale's law)")]
+fn test_dale_intra_setter_across_layers() {
+    let mut snn = build_dale_snn();
+
+    /* the neuron 0 of the first layer is excitatory towards the next one */
+    snn.set_intra_weight(0, 1, 0, -0.5);
+}
+
+#[test]
+#[should_panic(expected = "in its layer and in the next one (Dale's law)")]
+fn test_dale_matrix_setters_across_layers() {
+    let mut snn = build_dale_snn();
+
+    snn.set_weights(1, vec![vec![0.5, -0.5], vec![0.0, -0.5]]);
+    snn.set_intra_weights(0, vec![vec![0.0, 0.0], vec![0.0, 0.0]]);
+    snn.set_weights(1, vec![vec![0.5, 0.5], vec![0.5, 0.5]]);
+    snn.set_intra_weights(0, vec![vec![0.0, -0.5], vec![0.0, 0.0]]);
+}
+
+#[test]
+#[should_panic(expected = "The sign policy must be set before adding the layers")]
+fn test_sign_policy_after_layers() {
+    let _ = DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(neuron(), 1, vec![vec![1.0]], vec![vec![0.0]])
+        .with_sign_policy(SignPolicy::Dale);
+}
+
+#[test]
+fn test_static_builder_sign_policy() {
+    let snn = SnnBuilder::new()
+        .with_sign_policy(SignPolicy::Unconstrained)
+        .add_layer()
+        .weights([[0.5, -0.5]])
+        .neurons([neuron()])
+        .intra_weights([[0.0]])
+        .build();
+
+    assert_eq!(snn.get_weight(0, 0, 1), -0.5);
+}
+
+#[test]
+fn test_graph_import_with_sign_policy() {
+    let snn = DynSnnBuilder::new(2)
+        .with_sign_policy(SignPolicy::Unconstrained)
+        .add_layer_with_same_neurons(neuron(), 2, vec![vec![0.5, -0.5], vec![0.2, 0.1]],
+                                     vec![vec![0.0, 0.3], vec![-0.3, 0.0]])
+        .build();
+    let graph = graph::export(&snn);
+
+    assert!(graph::import::<LifNeuron>(&graph, 1.0).is_err());
+    let imported = graph::import_with_sign_policy::<LifNeuron>(&graph, 1.0, SignPolicy::Unconstrained).unwrap();
+    assert_eq!(imported.get_weight(0, 0, 1), -0.5);
+    assert_eq!(imported.get_intra_weight(0, 0, 1), 0.3);
+}
@@ -0,0 +1,174 @@
+mod common;
+
+use std::fs;
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::fault::campaign::{Campaign, Outcome};
+use pds_snn::fault::model::{Fault, FaultTarget, FaultType, NeuronSite};
+use pds_snn::format::snapshot;
+use pds_snn::models::neuron::cuba_lif::CubaLifNeuron;
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::synapses::{DynamicSynapses, StpParams};
+use pds_snn::DynSNN;
+use common::{build_snn, temp_file};
+
+//Tests related to the snapshots of the state of a network
+
+/* a network of CUBA neurons with dynamic synapses (more state than the membrane potential) */
+fn build_cuba_snn() -> DynSNN<CubaLifNeuron> {
+    let mut snn = DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(CubaLifNeuron::new(0.5, 0.0, 0.0, 10.0, 3.0, 5.0, 1.0), 2,
+                                     vec![vec![0.4, 0.2], vec![0.1, 0.5]], vec![vec![0.0, -0.3], vec![-0.3, 0.0]])
+        .build();
+    snn.set_dynamic_synapses(0, Some(DynamicSynapses::new(2, 2, StpParams::new(0.5, 5.0, 10.0), 1.0)));
+    snn
+}
+
+fn first_half() -> Vec<Vec<u8>> {
+    vec![vec![1, 0, 1, 1, 0], vec![0, 1, 1, 0, 0]]
+}
+
+fn second_half() -> Vec<Vec<u8>> {
+    vec![vec![0, 0, 1, 0, 1], vec![0, 1, 1, 0, 0]]
+}
+
+#[test]
+fn test_resume_from_snapshot() {
+    let mut snn = build_snn();
+    snn.process(&first_half());
+    let snapshot = snn.snapshot();
+    assert_eq!(snapshot.get_elapsed_instants(), 5);
+    assert_eq!(snapshot.get_layers_number(), 2);
+    assert_eq!(snapshot.get_layer(0).get_neurons_number(), 3);
+
+    let expected = snn.process_continue(&second_half());
+
+    /* the same network, brought back to the snapshot */
+    snn.restore(&snapshot);
+    assert_eq!(snn.get_elapsed_instants(), 5);
+    assert_eq!(snn.process_continue(&second_half()), expected);
+
+    /* another network with the same structure */
+    let mut other = build_snn();
+    other.restore(&snapshot);
+    assert_eq!(other.snapshot(), snapshot);
+    assert_eq!(other.process_continue(&second_half()), expected);
+}
+
+#[test]
+fn test_branching_experiments() {
+    let mut snn = build_snn();
+    snn.process(&first_half());
+    let snapshot = snn.snapshot();
+
+    /* two what-if experiments from the same state: only the modified one changes */
+    let mut baseline = snn.replicate();
+    let mut modified = snn.replicate();
+    modified.set_weight(1, 0, 2, 0.0);
+    modified.set_weight(1, 0, 1, 0.0);
+    modified.set_weight(1, 0, 0, 0.0);
+
+    let expected = snn.process_continue(&second_half());
+    baseline.restore(&snapshot);
+    modified.restore(&snapshot);
+    assert_eq!(baseline.process_continue(&second_half()), expected);
+    assert_eq!(modified.process_continue(&second_half())[0], vec![0; 5]);
+}
+
+#[test]
+fn test_faults_from_warmed_up_state() {
+    let mut snn = build_snn();
+    snn.process(&first_half());
+    let snapshot = snn.snapshot();
+
+    let golden = snn.process_continue_with_faults(&second_half(), &[]);
+    let fault = Fault::new(1, FaultTarget::Neuron { neuron: 0, site: NeuronSite::Comparator }, FaultType::StuckAt0, 0);
+    let faulty = snn.process_continue_with_faults(&second_half(), &[fault]);
+
+    /* the network itself is never affected */
+    assert_eq!(snn.snapshot(), snapshot);
+    assert_eq!(snn.process_continue(&second_half()), golden);
+    assert_eq!(faulty[0], vec![0; 5]);
+}
+
+#[test]
+fn test_campaign_from_restored_snapshot() {
+    let mut snn = build_snn();
+    snn.process(&first_half());
+    let mut restored = build_snn();
+    restored.restore(&snn.snapshot());
+    /* (an input whose output depends on the state reached at the end of the first half) */
+    let inputs = vec![vec![vec![1, 1, 0, 0, 0], vec![0, 0, 0, 0, 0]]];
+    let input = &inputs[0];
+
+    let result = Campaign::new(30, 4).from_current_state(true).run(&restored, &inputs);
+
+    let golden = snn.process_continue_with_faults(input, &[]);
+    assert_eq!(result.golden, vec![golden.clone()]);
+    assert_ne!(golden, restored.process_with_faults(input, &[]));
+    for run in &result.runs {
+        /* (the transient faults hit the instants following the first half) */
+        assert!(run.fault.start == 0 || run.fault.start >= 5);
+        let faulty = restored.process_continue_with_faults(input, &[run.fault]);
+        assert_eq!(run.outcome == Outcome::Masked, faulty == golden);
+    }
+}
+
+#[test]
+fn test_snapshot_file() {
+    let mut snn = build_cuba_snn();
+    snn.process(&first_half());
+    let snapshot = snn.snapshot();
+    assert!(snapshot.get_layer(0).has_dynamic_synapses());
+    assert_eq!(snapshot.get_layer(0).get_neuron_state(0).len(), 6);
+
+    let path = temp_file("cuba.snap");
+    snapshot::save(&snapshot, &path).unwrap();
+    let loaded = snapshot::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, snapshot);
+
+    let expected = snn.process_continue(&second_half());
+    let mut other = build_cuba_snn();
+    other.restore(&loaded);
+    assert_eq!(other.process_continue(&second_half()), expected);
+
+    /* a snapshot taken after a reset */
+    snn.reset();
+    let mut bytes = vec![];
+    snapshot::write(&snn.snapshot(), &mut bytes).unwrap();
+    assert_eq!(snapshot::read(&mut bytes.as_slice()).unwrap(), snn.snapshot());
+}
+
+#[test]
+fn test_invalid_snapshot_file() {
+    let mut snn = build_snn();
+    snn.process(&first_half());
+    let mut bytes = vec![];
+    snapshot::write(&snn.snapshot(), &mut bytes).unwrap();
+
+    assert!(snapshot::read(&mut &bytes[..bytes.len() - 3]).is_err());
+    assert!(snapshot::read(&mut &b"PSNN"[..]).is_err());
+
+    bytes.push(0);
+    assert!(snapshot::read(&mut bytes.as_slice()).is_err());
+}
+
+#[test]
+#[should_panic(expected = "The snapshot has 2 layers, but the network has 1")]
+fn test_restore_wrong_network() {
+    let snapshot = build_snn().snapshot();
+    let mut snn = DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(LifNeuron::new(0.3, 0.05, 0.1, 1.0, 1.0), 3,
+                                     vec![vec![0.1, 0.2]; 3], vec![vec![0.0; 3]; 3])
+        .build();
+    snn.restore(&snapshot);
+}
+
+#[test]
+#[should_panic(expected = "The snapshot and the layer must both have (or not have) the dynamic synapses")]
+fn test_restore_without_synapses() {
+    let snapshot = build_cuba_snn().snapshot();
+    let mut snn = build_cuba_snn();
+    snn.set_dynamic_synapses(0, None);
+    snn.restore(&snapshot);
+}
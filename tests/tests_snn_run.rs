@@ -0,0 +1,144 @@
+mod common;
+
+use std::process::Command;
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::format::binary;
+use pds_snn::models::neuron::coba_lif::{CobaLifNeuron, Conductance};
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::weights::WeightsType;
+use common::temp_file;
+
+//Tests related to the snn-run command-line runner
+
+//This function returns the path of the graph fixture network (2 inputs, 2 outputs)
+fn network() -> String {
+    format!("{}/tests/fixtures/graph/two_layers.json", env!("CARGO_MANIFEST_DIR"))
+}
+
+#[test]
+fn test_run_spikes_and_events() {
+    let spikes_path = temp_file("spikes.csv");
+    let events_path = temp_file("events.txt");
+    std::fs::write(&spikes_path, "1,0,1,1,0,1\n0,0,1,1,1,0\n").unwrap();
+    std::fs::write(&events_path, "0,0\n0,2\n1,2\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_snn-run"))
+        .args(["--network", &network(), "--decoding", "count", "--no-rasters"])
+        .args(["--spikes", spikes_path.to_str().unwrap(), "--events", events_path.to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    std::fs::remove_file(&spikes_path).unwrap();
+    std::fs::remove_file(&events_path).unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(lines[0], "sample,duration,total_spikes,mean_rate,class,spikes_n0,spikes_n1");
+    assert_eq!(lines.len(), 3);
+    assert!(lines[1].starts_with("0,6,"));
+    assert!(lines[2].starts_with("1,3,"));
+}
+
+#[test]
+fn test_run_analog_batch_is_like_sequential() {
+    let analog_path = temp_file("analog.csv");
+    std::fs::write(&analog_path, "230,25\n51,204\n0,0\n128,128\n").unwrap();
+
+    let run = |batch: bool| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_snn-run"));
+        command.args(["--network", &network(), "--analog", analog_path.to_str().unwrap()])
+            .args(["--scale", "255", "--steps", "12", "--seed", "3"]);
+        if batch {
+            command.args(["--batch", "--threads", "3"]);
+        }
+        command.output().unwrap()
+    };
+
+    let sequential = run(false);
+    let batch = run(true);
+    std::fs::remove_file(&analog_path).unwrap();
+
+    assert!(sequential.status.success());
+    assert_eq!(sequential.stdout, batch.stdout);
+}
+
+#[test]
+fn test_run_wrong_input_dimension() {
+    let spikes_path = temp_file("wrong.csv");
+    std::fs::write(&spikes_path, "1,0,1\n0,0,1\n1,1,1\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_snn-run"))
+        .args(["--network", &network(), "--spikes", spikes_path.to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    std::fs::remove_file(&spikes_path).unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("the network expects 2"));
+}
+
+#[test]
+fn test_run_cuba_lif_graph() {
+    let network = format!("{}/tests/fixtures/graph/cuba.json", env!("CARGO_MANIFEST_DIR"));
+    let spikes_path = temp_file("cuba.csv");
+    std::fs::write(&spikes_path, "1,1,0,0,1,0\n1,0,1,0,0,0\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_snn-run"))
+        .args(["--network", &network, "--spikes", spikes_path.to_str().unwrap(), "--no-rasters"])
+        .output()
+        .unwrap();
+
+    std::fs::remove_file(&spikes_path).unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8(output.stdout).unwrap().lines().nth(1).unwrap().starts_with("0,6,"));
+}
+
+#[test]
+fn test_run_coba_lif_binary() {
+    let network_path = temp_file("coba.bin");
+    let spikes_path = temp_file("coba.csv");
+    let snn = DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(CobaLifNeuron::new(0.5, 0.0, 0.0, 10.0, Conductance::new(5.0, 100.0),
+                                                        Conductance::new(-1.0, 5.0), 1.0), 2,
+                                     vec![vec![0.5, 0.0], vec![0.0, 0.5]], vec![vec![0.0; 2]; 2])
+        .build();
+    binary::save(&snn, &network_path, WeightsType::F64).unwrap();
+    std::fs::write(&spikes_path, "1,1,1,0\n0,0,0,1\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_snn-run"))
+        .args(["--network", network_path.to_str().unwrap(), "--spikes", spikes_path.to_str().unwrap(), "--mmap"])
+        .output()
+        .unwrap();
+
+    std::fs::remove_file(&network_path).unwrap();
+    std::fs::remove_file(&spikes_path).unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_run_unknown_neuron_model() {
+    let network_path = temp_file("unknown.bin");
+    let snn = DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1.0, 1.0), 1, vec![vec![1.0]], vec![vec![0.0]])
+        .build();
+    let mut bytes = Vec::new();
+    binary::write(&snn, &mut bytes, WeightsType::F64).unwrap();
+    /* (model identifier of the first entry of the layer table) */
+    bytes[40..44].copy_from_slice(&7u32.to_le_bytes());
+    std::fs::write(&network_path, &bytes).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_snn-run"))
+        .args(["--network", network_path.to_str().unwrap(), "--events", network_path.to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    std::fs::remove_file(&network_path).unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("unknown neuron model 7"));
+}
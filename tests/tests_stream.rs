@@ -0,0 +1,137 @@
+mod common;
+
+use std::time::Duration;
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::layer::RecurrenceMode;
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::weights::SignPolicy;
+use pds_snn::SpikeEvent;
+use common::build_snn;
+
+//Tests related to the streaming inference
+
+/* (output spikes matrix of the given output events, as returned by process()) */
+fn to_spikes(events: &[SpikeEvent], dimension: usize, duration: usize) -> Vec<Vec<u8>> {
+    let mut spikes = vec![vec![0u8; duration]; dimension];
+    for event in events {
+        for (neuron, spike) in event.get_spikes().iter().enumerate() {
+            spikes[neuron][event.get_ts() as usize] = *spike;
+        }
+    }
+    spikes
+}
+
+fn columns(spikes: &[Vec<u8>]) -> Vec<SpikeEvent> {
+    (0..spikes[0].len()).map(|t| SpikeEvent::new(t as u64, spikes.iter().map(|row| row[t]).collect())).collect()
+}
+
+#[test]
+fn test_stream_matches_process() {
+    let input_spikes = vec![vec![1, 0, 1, 1, 0, 1], vec![0, 1, 1, 0, 0, 1]];
+    let mut snn = build_snn();
+    let expected = snn.process(&input_spikes);
+
+    let mut stream = snn.stream();
+    assert_eq!(stream.get_input_dimension(), 2);
+    columns(&input_spikes).into_iter().for_each(|event| stream.push(event));
+    let output_events = stream.close();
+
+    assert_eq!(to_spikes(&output_events, 2, 6), expected);
+}
+
+#[test]
+fn test_outputs_arrive_before_close() {
+    let mut snn = build_snn();
+    let mut stream = snn.stream();
+
+    stream.push(SpikeEvent::new(0, vec![1, 1]));
+    let event = stream.recv_timeout(Duration::from_secs(5)).expect("The output must arrive while the stream is open");
+    assert_eq!(event.get_ts(), 0);
+    assert_eq!(event.get_spikes(), &vec![1, 1]);
+
+    /* the events without spikes produce no output */
+    stream.push(SpikeEvent::new(1, vec![0, 0]));
+    assert!(stream.recv_timeout(Duration::from_millis(50)).is_none());
+    assert!(stream.close().is_empty());
+}
+
+#[test]
+fn test_state_persists_until_reset() {
+    let input_spikes = vec![vec![1, 0, 1, 1, 0, 1], vec![0, 1, 1, 0, 0, 1]];
+    let mut snn = build_snn();
+    let expected = snn.process(&input_spikes);
+
+    let mut stream = snn.stream();
+    let events = columns(&input_spikes);
+
+    /* the input split in two pieces gives the same output of the whole one */
+    let mut output_events = vec![];
+    for event in events.iter().take(3) {
+        stream.push(SpikeEvent::new(event.get_ts(), event.get_spikes().clone()));
+    }
+    while output_events.len() < 3 {
+        output_events.extend(stream.recv_timeout(Duration::from_secs(5)));
+    }
+    for event in events.iter().skip(3) {
+        stream.push(SpikeEvent::new(event.get_ts(), event.get_spikes().clone()));
+    }
+
+    /* after a reset the network starts again from the initial state */
+    stream.reset();
+    events.into_iter().for_each(|event| stream.push(event));
+    output_events.extend(stream.close());
+
+    let (first, second) = output_events.split_at(output_events.iter().position(|event| event.get_ts() == 5).unwrap() + 1);
+    assert_eq!(to_spikes(first, 2, 6), expected);
+    assert_eq!(to_spikes(second, 2, 6), expected);
+}
+
+#[test]
+fn test_sparse_events() {
+    let input_spikes = vec![vec![1, 0, 1, 1], vec![0, 1, 1, 0]];
+    let mut snn = build_snn();
+    let expected = snn.process(&input_spikes);
+
+    let mut stream = snn.stream();
+    stream.push_sparse(0, &[0]);
+    stream.push_sparse(1, &[1]);
+    stream.push_sparse(2, &[0, 1]);
+    stream.push_sparse(3, &[0]);
+
+    assert_eq!(to_spikes(&stream.close(), 2, 4), expected);
+}
+
+#[test]
+fn test_stream_advance() {
+    /* the neuron 1 repeats the spikes of the neuron 0 in the next instant */
+    let mut snn = DynSnnBuilder::new(1)
+        .with_sign_policy(SignPolicy::Unconstrained)
+        .add_layer_with_same_neurons(LifNeuron::new(1.0, 0.0, 0.0, 1e9, 1.0), 2,
+                                     vec![vec![1.5], vec![0.0]], vec![vec![0.0, 0.0], vec![1.5, 0.0]])
+        .build();
+    snn.set_recurrence_mode(RecurrenceMode::Delayed);
+
+    let mut stream = snn.stream();
+    stream.push(SpikeEvent::new(0, vec![1]));
+    stream.advance_to(5);
+    let output_events = stream.close();
+
+    assert_eq!(to_spikes(&output_events, 2, 5), vec![vec![1, 0, 0, 0, 0], vec![0, 1, 0, 0, 0]]);
+}
+
+#[test]
+#[should_panic(expected = "The instants of the input events must be increasing")]
+fn test_decreasing_instants() {
+    let mut snn = build_snn();
+    let mut stream = snn.stream();
+    stream.push(SpikeEvent::new(3, vec![1, 0]));
+    stream.push(SpikeEvent::new(3, vec![0, 1]));
+}
+
+#[test]
+#[should_panic(expected = "The input 2 does not exist")]
+fn test_sparse_wrong_input() {
+    let mut snn = build_snn();
+    let mut stream = snn.stream();
+    stream.push_sparse(0, &[2]);
+}
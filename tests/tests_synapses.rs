@@ -0,0 +1,125 @@
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::format::binary::SerializableNeuron;
+use pds_snn::models::neuron::cuba_lif::{CubaLifNeuron, SynapseKernel};
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::neuron::{Neuron, SynapticInput};
+
+//Tests related to the current-based synapses
+
+#[test]
+fn test_exponential_current_decay() {
+    /* a = exp(-1 / tau_exc) = 0.5, no membrane leak */
+    let mut neuron = CubaLifNeuron::new(100.0, 0.0, 0.0, f64::INFINITY, 1.0 / 2f64.ln(), 1.0, 1.0);
+
+    assert_eq!(neuron.compute_v_mem(0, 1.0, 0.0), 0);
+    assert!((neuron.get_i_exc() - 1.0).abs() < 1e-12);
+    assert!((neuron.get_v_mem() - 1.0).abs() < 1e-12);
+
+    /* two silent instants, then the event at t = 3 (without input) */
+    neuron.compute_v_mem(3, 0.0, 0.0);
+    assert!((neuron.get_i_exc() - 0.125).abs() < 1e-12);
+    assert!((neuron.get_v_mem() - (1.0 + 0.5 + 0.25 + 0.125)).abs() < 1e-12);
+
+    neuron.initialize();
+    assert_eq!(neuron.get_v_mem(), 0.0);
+    assert_eq!(neuron.get_i_exc(), 0.0);
+}
+
+#[test]
+fn test_alpha_kernel() {
+    let tau = 1.0 / 2f64.ln();
+    let mut exponential = CubaLifNeuron::new(100.0, 0.0, 0.0, f64::INFINITY, tau, tau, 1.0);
+    let mut alpha = exponential.clone().with_kernel(SynapseKernel::Alpha);
+
+    exponential.compute_v_mem(0, 1.0, 0.0);
+    alpha.compute_v_mem(0, 1.0, 0.0);
+
+    /* (1 - a) (k + 1) a^k: 0.5, 0.5, 0.375, ... */
+    let mut currents = vec![alpha.get_i_exc()];
+    for t in 1..4 {
+        alpha.compute_v_mem(t, 0.0, 0.0);
+        currents.push(alpha.get_i_exc());
+    }
+    assert_eq!(currents, vec![0.5, 0.5, 0.375, 0.25]);
+
+    /* same total charge of the exponential kernel (without leak, the charge is the final potential) */
+    exponential.compute_v_mem(200, 0.0, 0.0);
+    alpha.compute_v_mem(200, 0.0, 0.0);
+    assert!((exponential.get_v_mem() - 2.0).abs() < 1e-9);
+    assert!((alpha.get_v_mem() - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_separate_time_constants() {
+    let mut neuron = CubaLifNeuron::new(100.0, 0.0, 0.0, 10.0, 2.0, 8.0, 1.0);
+    let input = SynapticInput { extra_weighted_sum: 0.5, intra_weighted_sum: -0.5, excitatory: 1.0, inhibitory: -0.5 };
+
+    neuron.compute_v_mem_with_input(0, &input);
+    assert_eq!((neuron.get_i_exc(), neuron.get_i_inh()), (1.0, -0.5));
+
+    neuron.compute_v_mem(5, 0.0, 0.0);
+    assert!((neuron.get_i_exc() - (-5.0f64 / 2.0).exp()).abs() < 1e-12);
+    assert!((neuron.get_i_inh() + 0.5 * (-5.0f64 / 8.0).exp()).abs() < 1e-12);
+}
+
+#[test]
+fn test_instantaneous_synapses_match_lif() {
+    /* with null synaptic time constants the current lasts one instant, as in the LIF model */
+    let weights = vec![vec![0.4, 0.3], vec![0.2, 0.6]];
+    let intra_weights = vec![vec![0.0, -0.2], vec![-0.1, 0.0]];
+    let spikes = vec![vec![1, 0, 1, 1, 0, 0, 1, 1], vec![0, 1, 1, 0, 0, 1, 1, 0]];
+
+    let mut lif = DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.1, 2.0, 1.0), 2, weights.clone(), intra_weights.clone())
+        .build();
+    let mut cuba = DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(CubaLifNeuron::new(0.5, 0.0, 0.1, 2.0, 0.0, 0.0, 1.0), 2, weights, intra_weights)
+        .build();
+
+    assert_eq!(lif.process(&spikes), cuba.process(&spikes));
+}
+
+#[test]
+fn test_current_persists_between_events() {
+    /* the second input only makes the layer process the instant 1 */
+    let weights = vec![vec![0.6, 0.0]];
+    let spikes = vec![vec![1, 0], vec![0, 1]];
+
+    let mut lif = DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(LifNeuron::new(1.0, 0.0, 0.0, 1e9, 1.0), 1, weights.clone(), vec![vec![0.0]])
+        .build();
+    let mut cuba = DynSnnBuilder::new(2)
+        .add_layer_with_same_neurons(CubaLifNeuron::new(1.0, 0.0, 0.0, 1e9, 1e9, 1e9, 1.0), 1, weights, vec![vec![0.0]])
+        .build();
+
+    assert_eq!(lif.process(&spikes), vec![vec![0, 0]]);
+    assert_eq!(cuba.process(&spikes), vec![vec![0, 1]]);
+}
+
+#[test]
+fn test_fire_after_single_input() {
+    /* alpha kernel with a = 0.5 and no leak: v_mem = 0.5, 1.0, 1.375 (fire), then 0.25, ... */
+    let tau = 1.0 / 2f64.ln();
+    let mut snn = DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(CubaLifNeuron::new(1.2, 0.0, 0.0, f64::INFINITY, tau, tau, 1.0)
+                                         .with_kernel(SynapseKernel::Alpha), 1, vec![vec![1.0]], vec![vec![0.0]])
+        .add_layer_with_same_neurons(CubaLifNeuron::new(0.5, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0), 1, vec![vec![1.0]], vec![vec![0.0]])
+        .build();
+
+    /* (the spike of the delayed peak reaches the next layer) */
+    assert_eq!(snn.process(&vec![vec![1, 0, 0, 0, 0, 0]]), vec![vec![0, 0, 1, 0, 0, 0]]);
+
+    /* the same, in consecutive chunks */
+    snn.reset();
+    assert_eq!(snn.process_continue(&vec![vec![1, 0]]), vec![vec![0, 0]]);
+    assert_eq!(snn.process_continue(&vec![vec![0, 0, 0]]), vec![vec![1, 0, 0]]);
+}
+
+#[test]
+fn test_cuba_lif_params() {
+    let neuron = CubaLifNeuron::new(1.0, -0.1, 0.2, 10.0, 3.0, 6.0, 0.5).with_kernel(SynapseKernel::Alpha);
+    let copy = CubaLifNeuron::from_params(&neuron.to_params());
+
+    assert_eq!(copy.get_kernel(), SynapseKernel::Alpha);
+    assert_eq!((copy.get_tau_exc(), copy.get_tau_inh(), copy.get_dt()), (3.0, 6.0, 0.5));
+}
@@ -0,0 +1,186 @@
+use pds_snn::builders::DynSnnBuilder;
+use pds_snn::fault::campaign::{Campaign, Component};
+use pds_snn::fault::model::{Fault, FaultTarget, FaultType, NeuronSite};
+use pds_snn::layer::{Layer, SpikingLayer};
+use pds_snn::models::neuron::lif::LifNeuron;
+use pds_snn::neuron::{boxed, BoxedNeuron, Neuron};
+use pds_snn::wta::WtaMode;
+use pds_snn::SpikeEvent;
+
+//Tests related to the winner-take-all layers
+
+/* three neurons without leak, driven by the same input with different weights */
+fn layer(weights: [f64; 3], wta: WtaMode) -> Layer<LifNeuron> {
+    let mut layer = Layer::new(vec![LifNeuron::new(1.0, 0.0, 0.0, 1e9, 1.0); 3],
+                               weights.iter().map(|weight| vec![*weight]).collect(),
+                               vec![vec![0.0; 3]; 3]);
+    layer.set_wta_mode(wta);
+    layer
+}
+
+/* neuron model without the two-phase update (Neuron::integrate()) */
+#[derive(Debug, Clone)]
+struct ThresholdNeuron;
+
+impl Neuron for ThresholdNeuron {
+    fn compute_v_mem(&mut self, _t: u64, extra_weighted_sum: f64, intra_weighted_sum: f64) -> u8 {
+        (extra_weighted_sum + intra_weighted_sum > 1.0) as u8
+    }
+
+    fn initialize(&mut self) {}
+}
+
+fn fire(layer: &mut Layer<LifNeuron>) -> Option<Vec<u8>> {
+    layer.process_event(&SpikeEvent::new(0, vec![1])).map(|event| event.get_spikes().clone())
+}
+
+fn potentials(layer: &Layer<LifNeuron>) -> Vec<f64> {
+    (0..3).map(|i| layer.get_neuron(i).get_v_mem()).collect()
+}
+
+#[test]
+fn test_no_competition() {
+    let mut layer = layer([1.5, 2.0, 1.8], WtaMode::None);
+    assert_eq!(layer.get_wta_mode(), WtaMode::None);
+    assert_eq!(fire(&mut layer), Some(vec![1, 1, 1]));
+}
+
+#[test]
+fn test_hard_wta() {
+    let mut layer = layer([1.5, 2.0, 1.8], WtaMode::Hard);
+    assert_eq!(fire(&mut layer), Some(vec![0, 1, 0]));
+
+    /* the losers above the threshold are reset too */
+    assert_eq!(potentials(&layer), vec![0.0, 0.0, 0.0]);
+
+    /* the ties are broken in favour of the lowest index */
+    let mut tied = self::layer([1.5, 2.0, 2.0], WtaMode::Hard);
+    assert_eq!(fire(&mut tied), Some(vec![0, 1, 0]));
+
+    /* no neuron above the threshold: no output, and the potentials are kept */
+    let mut silent = self::layer([0.5, 0.9, 0.2], WtaMode::Hard);
+    assert_eq!(fire(&mut silent), None);
+    assert_eq!(potentials(&silent), vec![0.5, 0.9, 0.2]);
+}
+
+#[test]
+fn test_k_winners() {
+    let mut layer = layer([1.5, 2.0, 1.8], WtaMode::KWinners(2));
+    assert_eq!(fire(&mut layer), Some(vec![0, 1, 1]));
+
+    /* k greater than the number of candidates */
+    let mut few = self::layer([0.5, 2.0, 1.8], WtaMode::KWinners(3));
+    assert_eq!(fire(&mut few), Some(vec![0, 1, 1]));
+    assert_eq!(potentials(&few), vec![0.5, 0.0, 0.0]);
+}
+
+#[test]
+fn test_soft_wta() {
+    /* the first winner inhibits the others by 0.6: 1.8 - 0.6 still fires, 1.5 - 1.2 does not */
+    let mut weak = layer([1.5, 2.0, 1.8], WtaMode::Soft(0.6));
+    assert_eq!(fire(&mut weak), Some(vec![0, 1, 1]));
+    let values = potentials(&weak);
+    assert!((values[0] - 0.3).abs() < 1e-9);
+    assert_eq!(&values[1..], &[0.0, 0.0]);
+
+    /* a stronger inhibition leaves a single winner */
+    let mut strong = layer([1.5, 2.0, 1.8], WtaMode::Soft(1.0));
+    assert_eq!(fire(&mut strong), Some(vec![0, 1, 0]));
+    let values = potentials(&strong);
+    assert!((values[0] - 0.5).abs() < 1e-9);
+    assert!((values[2] - 0.8).abs() < 1e-9);
+}
+
+#[test]
+fn test_wta_network() {
+    let mut snn = DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(LifNeuron::new(1.0, 0.0, 0.0, 1e9, 1.0), 2,
+                                     vec![vec![1.2], vec![1.5]], vec![vec![0.0; 2]; 2])
+        .add_layer_with_same_neurons(LifNeuron::new(0.5, 0.0, 0.0, 1e9, 1.0), 2,
+                                     vec![vec![1.0, 0.0], vec![0.0, 1.0]], vec![vec![0.0; 2]; 2])
+        .build();
+    let spikes = vec![vec![1, 1, 0, 1]];
+
+    assert_eq!(snn.process(&spikes), vec![vec![1, 1, 0, 1], vec![1, 1, 0, 1]]);
+
+    /* only the winner of the first layer reaches the second one */
+    snn.set_wta_mode(0, WtaMode::Hard);
+    assert_eq!(snn.process(&spikes), vec![vec![0, 0, 0, 0], vec![1, 1, 0, 1]]);
+
+    snn.set_wta_mode(0, WtaMode::None);
+    assert_eq!(snn.process(&spikes), vec![vec![1, 1, 0, 1], vec![1, 1, 0, 1]]);
+}
+
+#[test]
+#[should_panic(expected = "The number of winners must be positive")]
+fn test_zero_winners() {
+    layer([1.5, 2.0, 1.8], WtaMode::KWinners(0));
+}
+
+#[test]
+#[should_panic(expected = "The strength of the inhibition must not be negative")]
+fn test_negative_inhibition() {
+    layer([1.5, 2.0, 1.8], WtaMode::Soft(-1.0));
+}
+
+#[test]
+#[should_panic(expected = "The layer 0 is a winner-take-all layer, which does not support the neuron faults")]
+fn test_neuron_faults_in_wta_layer() {
+    let mut snn = DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(LifNeuron::new(1.0, 0.0, 0.0, 1e9, 1.0), 2,
+                                     vec![vec![1.2], vec![1.5]], vec![vec![0.0; 2]; 2])
+        .build();
+    snn.set_wta_mode(0, WtaMode::Hard);
+
+    /* (the faults of the weights are injected as usual) */
+    let weight = Fault::new(0, FaultTarget::Weight { neuron: 1, input: 0 }, FaultType::StuckAt1, 63);
+    assert_eq!(snn.process_with_faults(&vec![vec![1, 1]], &[weight]), vec![vec![1, 1], vec![0, 0]]);
+
+    let comparator = Fault::new(0, FaultTarget::Neuron { neuron: 1, site: NeuronSite::Comparator }, FaultType::StuckAt0, 0);
+    snn.process_with_faults(&vec![vec![1, 1]], &[comparator]);
+}
+
+#[test]
+fn test_default_campaign_on_wta_layer() {
+    let mut snn = DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(LifNeuron::new(1.0, 0.0, 0.0, 1e9, 1.0), 2,
+                                     vec![vec![1.2], vec![1.5]], vec![vec![0.0; 2]; 2])
+        .build();
+    snn.set_wta_mode(0, WtaMode::KWinners(1));
+
+    let faults = Campaign::new(30, 1).sample_faults(&snn, 2);
+
+    assert_eq!(faults.len(), 30);
+    assert!(faults.iter().all(|fault| !matches!(fault.target, FaultTarget::Neuron { .. })));
+}
+
+#[test]
+#[should_panic(expected = "No layer has the components of the campaign")]
+fn test_campaign_on_wta_layer() {
+    let mut snn = DynSnnBuilder::new(1)
+        .add_layer_with_same_neurons(LifNeuron::new(1.0, 0.0, 0.0, 1e9, 1.0), 2,
+                                     vec![vec![1.2], vec![1.5]], vec![vec![0.0; 2]; 2])
+        .build();
+    snn.set_wta_mode(0, WtaMode::KWinners(1));
+
+    Campaign::new(10, 1).with_components(vec![Component::Neuron(NeuronSite::VMem)]).sample_faults(&snn, 2);
+}
+
+#[test]
+#[should_panic(expected = "The neuron model does not support the winner-take-all layers")]
+fn test_unsupported_model() {
+    let mut layer = Layer::new(vec![ThresholdNeuron; 2], vec![vec![1.0], vec![2.0]], vec![vec![0.0; 2]; 2]);
+
+    /* (it panics when the mode is set, not when the layer is processed) */
+    layer.set_wta_mode(WtaMode::Hard);
+}
+
+#[test]
+#[should_panic(expected = "The neuron model does not support the winner-take-all layers")]
+fn test_replace_neuron_with_unsupported_model() {
+    let neurons: Vec<BoxedNeuron> = vec![boxed(LifNeuron::new(1.0, 0.0, 0.0, 1e9, 1.0)); 2];
+    let mut layer = Layer::new(neurons, vec![vec![1.0], vec![2.0]], vec![vec![0.0; 2]; 2]);
+    layer.set_wta_mode(WtaMode::Hard);
+
+    layer.set_neuron(1, boxed(ThresholdNeuron));
+}